@@ -0,0 +1,51 @@
+use std::hint::black_box;
+
+use criterion::{BatchSize, Criterion, criterion_group, criterion_main};
+use silva::Arena;
+
+const NODES: usize = 100_000;
+
+fn push(c: &mut Criterion) {
+    c.bench_function("push flat", |b| {
+        b.iter_batched(
+            Arena::new,
+            |arena| {
+                let root = arena.push(None, 0).index();
+                for i in 1..NODES {
+                    black_box(arena.push(root, i));
+                }
+                arena
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    c.bench_function("push chain", |b| {
+        b.iter_batched(
+            Arena::new,
+            |arena| {
+                let mut parent = None;
+                for i in 0..NODES {
+                    parent = Some(arena.push(parent, i).index());
+                }
+                arena
+            },
+            BatchSize::LargeInput,
+        );
+    });
+}
+
+fn read(c: &mut Criterion) {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    for i in 1..NODES {
+        arena.push(root, i);
+    }
+
+    c.bench_function("children", |b| {
+        b.iter(|| root.children().map(|n| n.value).sum::<usize>());
+    });
+}
+
+criterion_group!(benches, push, read);
+criterion_main!(benches);
@@ -0,0 +1,128 @@
+//! Walks a directory into an arena, then prints it & answers a few queries
+//!
+//! ```sh
+//! cargo run --example filesystem -- [DIR]
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use silva::{Arena, Node};
+
+/// Walk `root` into a new arena, one node per entry
+fn walk(root: &Path) -> io::Result<Arena<PathBuf>> {
+    let arena = Arena::new();
+    let mut stack = vec![arena.push(None, root.to_path_buf()).index()];
+
+    while let Some(dir) = stack.pop() {
+        let mut entries = fs::read_dir(&arena[dir].value)?.collect::<io::Result<Vec<_>>>()?;
+        entries.sort_by_key(fs::DirEntry::file_name);
+
+        for entry in entries {
+            let node = arena.push(dir, entry.path());
+            if entry.file_type()?.is_dir() {
+                stack.push(node.index());
+            }
+        }
+    }
+    Ok(arena)
+}
+
+/// Every node below `root` along with its depth, in pre-order
+fn descendants<T>(root: &Node<T>) -> Vec<(usize, &Node<T>)> {
+    let mut out = Vec::new();
+    let mut stack = vec![(0, root)];
+    while let Some((depth, node)) = stack.pop() {
+        out.push((depth, node));
+        // children are newest first, so pushing them in order visits the
+        // oldest first
+        stack.extend(node.children().map(|child| (depth + 1, child)));
+    }
+    out
+}
+
+fn name(path: &Path) -> String {
+    path.file_name().map_or_else(
+        || path.display().to_string(),
+        |n| n.to_string_lossy().into(),
+    )
+}
+
+fn render(root: &Node<PathBuf>) -> String {
+    descendants(root)
+        .into_iter()
+        .map(|(depth, node)| format!("{:indent$}{}\n", "", name(&node.value), indent = depth * 2))
+        .collect()
+}
+
+/// The deepest entry & its depth
+fn deepest(root: &Node<PathBuf>) -> (usize, &Path) {
+    let (depth, node) = descendants(root)
+        .into_iter()
+        .max_by_key(|(depth, _)| *depth)
+        .expect("descendants always include the root");
+    (depth, &node.value)
+}
+
+/// The directory with the most entries & how many it has
+fn widest(root: &Node<PathBuf>) -> (usize, &Path) {
+    let (count, node) = descendants(root)
+        .into_iter()
+        .map(|(_, node)| (node.children().count(), node))
+        .max_by_key(|(count, _)| *count)
+        .expect("descendants always include the root");
+    (count, &node.value)
+}
+
+fn main() -> io::Result<()> {
+    let dir = env::args_os()
+        .nth(1)
+        .map_or_else(|| PathBuf::from("."), PathBuf::from);
+    let arena = walk(&dir)?;
+    let root = arena.roots().next().expect("the walk always pushes a root");
+
+    print!("{}", render(root));
+    let (depth, path) = deepest(root);
+    println!("\ndeepest: {} (depth {depth})", path.display());
+    let (count, path) = widest(root);
+    println!("widest: {} ({count} entries)", path.display());
+    println!("total: {} entries", arena.count());
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn temp_tree() {
+        let root = env::temp_dir().join(format!("silva-filesystem-{}", std::process::id()));
+        fs::create_dir_all(root.join("a/b/c")).unwrap();
+        fs::create_dir_all(root.join("d")).unwrap();
+        for file in ["a/one", "a/two", "a/three", "a/b/c/deep", "d/four"] {
+            fs::write(root.join(file), file).unwrap();
+        }
+
+        let arena = walk(&root).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+        let top = arena.roots().next().unwrap();
+
+        assert_eq!(arena.count(), 10);
+        assert_eq!(deepest(top), (4, root.join("a/b/c/deep").as_path()));
+        assert_eq!(widest(top), (4, root.join("a").as_path()));
+        assert_eq!(
+            render(top).lines().skip(1).collect::<Vec<_>>(),
+            [
+                "  a",
+                "    b",
+                "      c",
+                "        deep",
+                "    one",
+                "    three",
+                "    two",
+                "  d",
+                "    four"
+            ]
+        );
+    }
+}
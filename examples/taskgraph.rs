@@ -0,0 +1,117 @@
+//! Builds a task tree from several worker threads, then rolls up its costs
+//!
+//! ```sh
+//! cargo run --example taskgraph
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, mpsc};
+use std::thread;
+
+use silva::{Arena, Handle, Index, Node};
+
+#[derive(Debug)]
+struct Task {
+    name: String,
+    cost: u64,
+}
+
+impl Task {
+    fn new(name: impl Into<String>, cost: u64) -> Self {
+        Self {
+            name: name.into(),
+            cost,
+        }
+    }
+}
+
+/// Build a release task with one component per worker, each on its own thread
+///
+/// Workers report every task they push through a channel.
+fn build(workers: u64) -> (Arc<Arena<Task>>, Index, Vec<Handle<Task>>) {
+    let arena = Arc::new(Arena::new());
+    let release = arena.push(None, Task::new("release", 1)).handle(&arena);
+    let (tx, rx) = mpsc::channel();
+
+    let threads = (0..workers)
+        .map(|w| {
+            let release = release.clone();
+            let tx = tx.clone();
+            thread::spawn(move || {
+                let arena = release.arena();
+                let component = arena.push(&release, Task::new(format!("component-{w}"), w));
+                for step in ["compile", "test", "package"] {
+                    let task =
+                        arena.push(component, Task::new(format!("{step}-{w}"), 10 * (w + 1)));
+                    if step == "test" {
+                        for shard in 0..w {
+                            let shard =
+                                arena.push(task, Task::new(format!("shard-{w}-{shard}"), 5));
+                            tx.send(shard.handle(arena)).unwrap();
+                        }
+                    }
+                    tx.send(task.handle(arena)).unwrap();
+                }
+                tx.send(component.handle(arena)).unwrap();
+            })
+        })
+        .collect::<Vec<_>>();
+    drop(tx);
+
+    let pushed = rx.into_iter().collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    let release = release.index();
+    (arena, release, pushed)
+}
+
+/// The total cost of each task, including every task below it
+fn rollup(root: &Node<Task>) -> HashMap<Index, u64> {
+    let mut totals = HashMap::new();
+    root.fold_post_order(|node, children| {
+        let total = node.value.cost + children.sum::<u64>();
+        totals.insert(node.index(), total);
+        total
+    });
+    totals
+}
+
+fn main() {
+    let workers = thread::available_parallelism().map_or(4, |n| n.get() as u64);
+    let (arena, release, pushed) = build(workers);
+    println!("{} tasks reported by {workers} workers", pushed.len());
+
+    let totals = rollup(&arena[release]);
+    let mut components = arena[release].children().collect::<Vec<_>>();
+    components.sort_by_key(|c| c.index());
+    for component in components {
+        println!(
+            "{:>16}: {}",
+            component.value.name,
+            totals[&component.index()]
+        );
+    }
+    println!("{:>16}: {}", "release", totals[&release]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rollup_matches() {
+        let (arena, release, pushed) = build(4);
+        // 4 components, 3 steps each & 0 + 1 + 2 + 3 shards
+        assert_eq!(pushed.len(), 4 + 4 * 3 + 6);
+        assert_eq!(arena.count(), pushed.len() + 1);
+        assert!(pushed.iter().all(|h| Arc::ptr_eq(h.arena(), &arena)));
+
+        let totals = rollup(&arena[release]);
+        let expected: u64 = (0..4).map(|w| w + 3 * 10 * (w + 1) + 5 * w).sum();
+        assert_eq!(totals[&release], 1 + expected);
+        for shard in pushed.iter().filter(|h| h.value.name.starts_with("shard")) {
+            assert_eq!(totals[&shard.index()], 5);
+        }
+    }
+}
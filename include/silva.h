@@ -0,0 +1,43 @@
+/* The C API of silva, built with the `ffi` feature
+ *
+ * An arena may be used from any number of threads at once, through every
+ * function but silva_arena_free, which must not race with any other call on
+ * the same arena. No function unwinds, panics are reported as errors.
+ */
+
+#ifndef SILVA_H
+#define SILVA_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* an arena of pointer-sized values */
+typedef struct silva_arena_t silva_arena_t;
+
+/* the index given for a missing node, or used as the parent of a root */
+#define SILVA_NONE UINT64_MAX
+/* the index given when a call fails */
+#define SILVA_ERROR (UINT64_MAX - 1)
+
+#define SILVA_OK 0
+#define SILVA_ERR_NULL -1
+#define SILVA_ERR_NOT_FOUND -2
+#define SILVA_ERR_PANIC -3
+
+silva_arena_t *silva_arena_new(void);
+void silva_arena_free(silva_arena_t *arena);
+uint64_t silva_push(const silva_arena_t *arena, uint64_t parent, uintptr_t value);
+int32_t silva_get(const silva_arena_t *arena, uint64_t index, uintptr_t *out);
+uint64_t silva_parent(const silva_arena_t *arena, uint64_t index);
+uint64_t silva_child(const silva_arena_t *arena, uint64_t index);
+uint64_t silva_next(const silva_arena_t *arena, uint64_t index);
+uint64_t silva_count(const silva_arena_t *arena);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* SILVA_H */
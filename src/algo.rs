@@ -0,0 +1,516 @@
+//! Algorithms over trees of nodes
+
+use std::fmt;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::vec;
+
+use crate::{Arena, ChildOrder, Index, Node, SecondaryMap};
+
+/// A single step of a [`diff`]
+#[derive(Debug)]
+pub enum DiffOp<'a, T> {
+    /// Both nodes have equal values
+    Unchanged {
+        /// The node in the old tree
+        old: &'a Node<T>,
+        /// The node in the new tree
+        new: &'a Node<T>,
+    },
+    /// The node's value differs between the trees
+    ValueChanged {
+        /// The node in the old tree
+        old: &'a Node<T>,
+        /// The node in the new tree
+        new: &'a Node<T>,
+    },
+    /// The subtree only exists in the new tree
+    Inserted(&'a Node<T>),
+    /// The subtree only exists in the old tree
+    Removed(&'a Node<T>),
+}
+
+impl<T> Clone for DiffOp<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for DiffOp<'_, T> {}
+
+impl<T> PartialEq for DiffOp<'_, T> {
+    /// Ops are equal if they are the same kind over the same nodes
+    fn eq(&self, other: &Self) -> bool {
+        use std::ptr::eq;
+        match (self, other) {
+            (Self::Unchanged { old, new }, Self::Unchanged { old: o, new: n })
+            | (Self::ValueChanged { old, new }, Self::ValueChanged { old: o, new: n }) => {
+                eq(*old, *o) && eq(*new, *n)
+            }
+            (Self::Inserted(a), Self::Inserted(b)) | (Self::Removed(a), Self::Removed(b)) => {
+                eq(*a, *b)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<T> Eq for DiffOp<'_, T> {}
+
+/// Diff two trees by comparing their nodes position by position
+///
+/// Children are paired up in insertion order, so the `n`th child of a node
+/// in `old` is compared against the `n`th child of its counterpart in `new`.
+/// Moves are not detected, a child inserted in the middle shows up as every
+/// later sibling changing & the last being inserted.
+///
+/// Ops are given in pre-order over the merged shape of both trees, so they
+/// can be applied top-down. A [`DiffOp::Inserted`] or [`DiffOp::Removed`]
+/// stands for the whole subtree, whose descendants are not visited.
+pub fn diff<'a, T: PartialEq>(old_root: &'a Node<T>, new_root: &'a Node<T>) -> Vec<DiffOp<'a, T>> {
+    /// Work left to do, in reverse
+    enum Step<'a, T> {
+        Compare(&'a Node<T>, &'a Node<T>),
+        Emit(DiffOp<'a, T>),
+    }
+
+    let mut ops = Vec::new();
+    let mut stack = vec![Step::Compare(old_root, new_root)];
+
+    while let Some(step) = stack.pop() {
+        let (old, new) = match step {
+            Step::Compare(old, new) => (old, new),
+            Step::Emit(op) => {
+                ops.push(op);
+                continue;
+            }
+        };
+        ops.push(if old.value == new.value {
+            DiffOp::Unchanged { old, new }
+        } else {
+            DiffOp::ValueChanged { old, new }
+        });
+
+        // newest first, the reverse of the order they are visited in, which
+        // is the order the stack wants them pushed
+        let old_children: Vec<_> = old.children_in(ChildOrder::NewestFirst).collect();
+        let new_children: Vec<_> = new.children_in(ChildOrder::NewestFirst).collect();
+        let (old_len, new_len) = (old_children.len(), new_children.len());
+
+        stack.extend(
+            new_children[..new_len.saturating_sub(old_len)]
+                .iter()
+                .map(|&n| Step::Emit(DiffOp::Inserted(n))),
+        );
+        stack.extend(
+            old_children[..old_len.saturating_sub(new_len)]
+                .iter()
+                .map(|&n| Step::Emit(DiffOp::Removed(n))),
+        );
+        stack.extend(
+            old_children[old_len.saturating_sub(new_len)..]
+                .iter()
+                .zip(&new_children[new_len.saturating_sub(old_len)..])
+                .map(|(&old, &new)| Step::Compare(old, new)),
+        );
+    }
+    ops
+}
+
+/// A pair of nodes whose number of children differ, found by [`zip_trees`]
+#[derive(Debug)]
+pub struct Mismatch<'a, T> {
+    /// The node in the first tree
+    pub a: &'a Node<T>,
+    /// The node in the second tree
+    pub b: &'a Node<T>,
+}
+
+impl<T> Clone for Mismatch<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Mismatch<'_, T> {}
+
+/// Walk two trees in lockstep, pairing up nodes at the same position
+///
+/// Children are paired up in insertion order, whichever order each node
+/// links them in, & pairs are given in pre-order. Once a pair is found whose
+/// number of children differ, it is given as a [`Mismatch`] after the pair
+/// itself & the walk stops.
+pub fn zip_trees<'a, T>(a_root: &'a Node<T>, b_root: &'a Node<T>) -> ZipTrees<'a, T> {
+    ZipTrees {
+        stack: vec![(a_root, b_root)],
+        mismatch: None,
+    }
+}
+
+/// Iterates over two trees in lockstep
+///
+/// Created by [`zip_trees`]
+#[derive(Debug)]
+pub struct ZipTrees<'a, T> {
+    /// Pairs left to give, in reverse
+    stack: Vec<(&'a Node<T>, &'a Node<T>)>,
+    mismatch: Option<Mismatch<'a, T>>,
+}
+
+impl<T> Clone for ZipTrees<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            stack: self.stack.clone(),
+            mismatch: self.mismatch,
+        }
+    }
+}
+
+impl<'a, T> Iterator for ZipTrees<'a, T> {
+    type Item = Result<(&'a Node<T>, &'a Node<T>), Mismatch<'a, T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(mismatch) = self.mismatch.take() {
+            self.stack.clear();
+            return Some(Err(mismatch));
+        }
+        let (a, b) = self.stack.pop()?;
+        // newest first, the reverse of the order they are given in
+        let a_children: Vec<_> = a.children_in(ChildOrder::NewestFirst).collect();
+        let b_children: Vec<_> = b.children_in(ChildOrder::NewestFirst).collect();
+        if a_children.len() == b_children.len() {
+            self.stack.extend(a_children.into_iter().zip(b_children));
+        } else {
+            self.mismatch = Some(Mismatch { a, b });
+        }
+        Some(Ok((a, b)))
+    }
+}
+
+impl<T> FusedIterator for ZipTrees<'_, T> {}
+
+/// The outline of a laid out subtree, one `(left, right)` pair per level
+///
+/// Levels are stored deepest first, so a parent can add its own level in
+/// `O(1)`. Stored edges are relative to `shift`, letting a whole subtree be
+/// moved in `O(1)` too.
+struct Contour {
+    levels: Vec<(f32, f32)>,
+    shift: f32,
+}
+
+impl Contour {
+    /// The edges of the level `depth` levels below the top
+    fn level(&self, depth: usize) -> (f32, f32) {
+        let (left, right) = self.levels[self.levels.len() - 1 - depth];
+        (left + self.shift, right + self.shift)
+    }
+
+    /// Set the edges of the level `depth` levels below the top
+    fn set_level(&mut self, depth: usize, (left, right): (f32, f32)) {
+        let i = self.levels.len() - 1 - depth;
+        self.levels[i] = (left - self.shift, right - self.shift);
+    }
+
+    /// Add a level above the others
+    fn push_top(&mut self, (left, right): (f32, f32)) {
+        self.levels.push((left - self.shift, right - self.shift));
+    }
+
+    /// Merge in a contour lying to the right of this one
+    ///
+    /// Only the shared levels are visited, the longer contour's storage is
+    /// kept for the rest.
+    fn merge_right(mut self, mut right: Self) -> Self {
+        let shared = self.levels.len().min(right.levels.len());
+        if self.levels.len() < right.levels.len() {
+            for depth in 0..shared {
+                let (l, _) = self.level(depth);
+                let (_, r) = right.level(depth);
+                right.set_level(depth, (l, r));
+            }
+            right
+        } else {
+            for depth in 0..shared {
+                let (l, _) = self.level(depth);
+                let (_, r) = right.level(depth);
+                self.set_level(depth, (l, r));
+            }
+            self
+        }
+    }
+}
+
+/// Lay out the subtree rooted at `root` as a tidy tree
+///
+/// Uses the Reingold–Tilford algorithm: subtrees are laid out bottom-up,
+/// each pushed right until its outline is `spacing.0` clear of its older
+/// siblings' at every level, with parents centered over their first & last
+/// children. Children are placed left to right in insertion order, and levels
+/// are stacked top to bottom, `spacing.1` apart, each as tall as its tallest
+/// node.
+///
+/// `node_size` gives each node's `(width, height)` & is called once per node.
+/// The returned coordinates are of each node's top center, with `root` at
+/// `(0, 0)`. The subtree is walked using explicit stacks, so deep trees can't
+/// overflow the stack, & the layout only depends on the tree's shape & sizes.
+///
+/// Nodes pushed while this runs may be left out of the layout.
+pub fn layout_tidy<T>(
+    root: &Node<T>,
+    mut node_size: impl FnMut(&Node<T>) -> (f32, f32),
+    spacing: (f32, f32),
+) -> SecondaryMap<(f32, f32)> {
+    // each node's x relative to its parent, & its height
+    let mut placed = SecondaryMap::new();
+
+    root.fold_post_order(|node, children: vec::Drain<'_, (Index, Contour)>| {
+        let (width, height) = node_size(node);
+        let mut contour: Option<Contour> = None;
+        // the relative x of every child, in order
+        let mut offsets = Vec::new();
+        for (child, mut sub) in children {
+            let x = contour.as_ref().map_or(0.0, |contour| {
+                let shared = contour.levels.len().min(sub.levels.len());
+                (0..shared)
+                    .map(|depth| contour.level(depth).1 - sub.level(depth).0 + spacing.0)
+                    .fold(f32::MIN, f32::max)
+            });
+            sub.shift += x;
+            offsets.push((child, x));
+            contour = Some(match contour {
+                Some(contour) => contour.merge_right(sub),
+                None => sub,
+            });
+        }
+
+        let mid = match (offsets.first(), offsets.last()) {
+            (Some(&(_, first)), Some(&(_, last))) => f32::midpoint(first, last),
+            _ => 0.0,
+        };
+        for (child, x) in offsets {
+            if let Some((offset, _)) = placed.get_mut(child) {
+                *offset = x - mid;
+            }
+        }
+        let mut contour = contour.unwrap_or(Contour {
+            levels: Vec::new(),
+            shift: 0.0,
+        });
+        contour.shift -= mid;
+        contour.push_top((-width / 2.0, width / 2.0));
+
+        placed.insert(node.index(), (0.0, height));
+        (node.index(), contour)
+    });
+
+    // the tallest node of each level
+    let mut heights: Vec<f32> = Vec::new();
+    let mut order = Vec::new();
+    let mut stack = vec![(root, 0, 0.0)];
+    while let Some((node, depth, x)) = stack.pop() {
+        let Some(&(offset, height)) = placed.get(node.index()) else {
+            continue;
+        };
+        let x = x + offset;
+        if heights.len() == depth {
+            heights.push(height);
+        }
+        heights[depth] = heights[depth].max(height);
+        order.push((node.index(), depth, x));
+        stack.extend(node.children().map(|child| (child, depth + 1, x)));
+    }
+
+    let mut tops = Vec::with_capacity(heights.len());
+    let mut y = 0.0;
+    for height in heights {
+        tops.push(y);
+        y += height + spacing.1;
+    }
+
+    let mut layout = SecondaryMap::with_capacity(placed.len());
+    for (index, depth, x) in order {
+        layout.insert(index, (x, tops[depth]));
+    }
+    layout
+}
+
+/// Precomputed depths & ancestry over every node of an arena
+///
+/// Built once in `O(n log n)`, after which [`AncestorIndex::depth`] &
+/// [`AncestorIndex::is_ancestor`] take `O(1)`, & [`AncestorIndex::lca`]
+/// `O(1)` through a sparse table over the nodes' pre-order.
+///
+/// Answers reflect the arena when it was built. Indices handed out afterwards
+/// give `None`, as do nodes still being written or attached while building,
+/// & orphans attached later are still treated as the roots of their own
+/// trees. Only indices are
+/// checked, an index from another arena is looked up as if it were from this
+/// one.
+pub struct AncestorIndex<'a, T> {
+    /// The pre-order position of each index, [`AncestorIndex::MISSING`] if
+    /// it was not found
+    pos: Vec<usize>,
+    /// The index at each position
+    order: Vec<Index>,
+    /// The depth at each position
+    depth: Vec<usize>,
+    /// The position past the end of the subtree at each position
+    end: Vec<usize>,
+    /// The parent's position at each position, roots point to themselves
+    parent: Vec<usize>,
+    /// `sparse[k][i]` is the shallowest position in `i..i + 2^(k + 1)`
+    sparse: Vec<Vec<usize>>,
+    arena: PhantomData<&'a Arena<T>>,
+}
+
+impl<T> fmt::Debug for AncestorIndex<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AncestorIndex")
+            .field("len", &self.order.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> AncestorIndex<'a, T> {
+    /// The position of an index that was not found
+    const MISSING: usize = usize::MAX;
+
+    /// Index every node in `arena`
+    ///
+    /// Trees are walked from each root & unattached orphan, using an
+    /// explicit stack so deep trees can't overflow the stack.
+    pub fn build(arena: &'a Arena<T>) -> Self {
+        let len = arena.reserved();
+        let mut index = Self {
+            pos: vec![Self::MISSING; len],
+            order: Vec::with_capacity(len),
+            depth: Vec::with_capacity(len),
+            end: Vec::with_capacity(len),
+            parent: Vec::with_capacity(len),
+            sparse: Vec::new(),
+            arena: PhantomData,
+        };
+
+        // (node, parent's position), a node's end is set once it is popped
+        // a second time, marked by `None`
+        let mut stack = Vec::new();
+        for root in arena.iter_range(..len) {
+            if root.parent().is_some() || index.pos[root.index().as_usize()] != Self::MISSING {
+                continue;
+            }
+            stack.push((Some(root), None));
+            while let Some((node, parent)) = stack.pop() {
+                let Some(node) = node else {
+                    if let Some(at) = parent {
+                        index.end[at] = index.order.len();
+                    }
+                    continue;
+                };
+                let i = node.index().as_usize();
+                // pushed during the build, or reached twice through an attach
+                if i >= len || index.pos[i] != Self::MISSING {
+                    continue;
+                }
+                let at = index.order.len();
+                index.pos[i] = at;
+                index.order.push(node.index());
+                index
+                    .depth
+                    .push(parent.map_or(0, |p: usize| index.depth[p] + 1));
+                index.end.push(at + 1);
+                index.parent.push(parent.unwrap_or(at));
+                stack.push((None, Some(at)));
+                stack.extend(node.children().map(|child| (Some(child), Some(at))));
+            }
+        }
+
+        index.build_sparse();
+        index
+    }
+
+    /// Build the sparse table over every level above single positions
+    fn build_sparse(&mut self) {
+        let n = self.order.len();
+        let mut width = 2;
+        while width <= n {
+            let half = width / 2;
+            let level: Vec<_> = (0..=n - width)
+                .map(|i| {
+                    let (a, b) = self
+                        .sparse
+                        .last()
+                        .map_or((i, i + 1), |prev| (prev[i], prev[i + half]));
+                    self.shallower(a, b)
+                })
+                .collect();
+            self.sparse.push(level);
+            width *= 2;
+        }
+    }
+
+    /// The shallower of two positions
+    fn shallower(&self, a: usize, b: usize) -> usize {
+        if self.depth[b] < self.depth[a] { b } else { a }
+    }
+
+    /// The shallowest position within `range`, which must not be empty
+    fn shallowest(&self, range: Range<usize>) -> usize {
+        let len = range.len();
+        if len == 1 {
+            return range.start;
+        }
+        let level = len.ilog2() as usize;
+        let table = &self.sparse[level - 1];
+        self.shallower(table[range.start], table[range.end - (1 << level)])
+    }
+
+    /// The position of `index`, if it was indexed
+    fn position(&self, index: Index) -> Option<usize> {
+        self.pos
+            .get(index.as_usize())
+            .copied()
+            .filter(|&pos| pos != Self::MISSING)
+    }
+
+    /// The number of nodes indexed
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// returns `true` if no nodes were indexed
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Get the depth of a node, `0` for roots & unattached orphans
+    pub fn depth(&self, index: Index) -> Option<usize> {
+        Some(self.depth[self.position(index)?])
+    }
+
+    /// Check whether `ancestor` is a strict ancestor of `node`
+    ///
+    /// A node is not its own ancestor, matching [`Node::ancestors`].
+    pub fn is_ancestor(&self, ancestor: Index, node: Index) -> Option<bool> {
+        let (a, n) = (self.position(ancestor)?, self.position(node)?);
+        Some(a < n && n < self.end[a])
+    }
+
+    /// Get the lowest common ancestor of two nodes
+    ///
+    /// A node is counted as its own ancestor here, so the lca of a node & one
+    /// of its descendants is the node itself. Gives `None` if either node is
+    /// missing, or they are in different trees.
+    pub fn lca(&self, a: Index, b: Index) -> Option<Index> {
+        let (a, b) = (self.position(a)?, self.position(b)?);
+        let (a, b) = (a.min(b), a.max(b));
+        if b < self.end[a] {
+            return Some(self.order[a]);
+        }
+        // the subtree holding `b` that starts right after one holding `a`
+        // ends, is a child of the lca, unless it's another tree's root
+        let top = self.shallowest(a + 1..b + 1);
+        (self.depth[top] != 0).then(|| self.order[self.parent[top]])
+    }
+}
@@ -8,20 +8,91 @@
 //! [slotmap-boxcar]: https://github.com/SabrinaJewson/boxcar.rs
 //! [sharded-slab]: https://github.com/hawkw/sharded-slab
 
-use crate::{AsParent, Index, Node};
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Bound, Range, RangeBounds};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::algo::{self, ZipTrees};
+use crate::error::{
+    AllocError, AttachError, CheckError, DepthError, ForeignNodeError, PushError, ReparentError,
+    SwapError,
+};
+use crate::visit::{self, Visitor};
+use crate::{AsParent, ChildOrder, Handle, Index, Node, SecondaryMap};
 
 // NOTE: should move bucket & slot to be submodules of raw
 
 mod bucket;
+mod column;
 mod raw;
 mod slot;
+mod snapshot;
 
+pub use column::{Column, OnceEntry};
+pub use raw::ArenaLayout;
 // export just for Index
 pub use raw::MAX_INDEX;
+pub use snapshot::{ArenaSnapshot, SnapshotChildren, SnapshotDescendants};
+
+/// A type-erased push check
+///
+/// Stored as a function pointer rather than a boxed closure, so no `'static`
+/// bound is put on `T`.
+struct PushCheck<T> {
+    check: fn(),
+    call: CallCheck<T>,
+}
+
+type CallCheck<T> = fn(fn(), Option<&T>, &T) -> Result<(), CheckError>;
+
+impl<T> PushCheck<T> {
+    fn new<E: Error + Send + Sync + 'static>(check: fn(Option<&T>, &T) -> Result<(), E>) -> Self {
+        fn call<T, E: Error + Send + Sync + 'static>(
+            check: fn(),
+            parent: Option<&T>,
+            value: &T,
+        ) -> Result<(), CheckError> {
+            // SAFETY: `check` was erased from this exact type in PushCheck::new
+            let check: fn(Option<&T>, &T) -> Result<(), E> = unsafe { std::mem::transmute(check) };
+            check(parent, value).map_err(CheckError::from)
+        }
+
+        Self {
+            // SAFETY: fn pointers share a layout, `check` is only called after
+            // being transmuted back
+            check: unsafe {
+                std::mem::transmute::<fn(Option<&T>, &T) -> Result<(), E>, fn()>(check)
+            },
+            call: call::<T, E>,
+        }
+    }
+
+    fn call(&self, parent: Option<&T>, value: &T) -> Result<(), CheckError> {
+        (self.call)(self.check, parent, value)
+    }
+}
+
+impl<T> Clone for PushCheck<T> {
+    fn clone(&self) -> Self {
+        Self {
+            check: self.check,
+            call: self.call,
+        }
+    }
+}
 
 /// The arena where [`Node`]s are stored
 pub struct Arena<T> {
     raw: raw::Arena<T>,
+    /// Serializes [`Arena::attach`] so concurrent attaches can't form a cycle
+    attach: Mutex<()>,
+    /// Validates values before they are pushed
+    check: Option<PushCheck<T>>,
 }
 
 impl<T> Default for Arena<T> {
@@ -30,6 +101,134 @@ impl<T> Default for Arena<T> {
     }
 }
 
+impl<T: Clone> Clone for Arena<T> {
+    /// Clone every node, keeping their indices & the tree's structure
+    ///
+    /// Removed nodes stay removed, their indices unused. Subtree counts,
+    /// timestamps & the push check are kept, the clock being shared with the
+    /// original. Nodes pushed or attached while cloning may be left out.
+    fn clone(&self) -> Self {
+        Self {
+            raw: self.raw.clone(),
+            attach: Mutex::new(()),
+            check: self.check.clone(),
+        }
+    }
+}
+
+/// Serialized as a sequence of `(index, parent, value)` records
+///
+/// Parents come before their children, & siblings in the order they are
+/// linked, so indices & the tree's structure are kept. An unattached orphan
+/// is recorded as its own parent. Removed nodes are left out, their indices
+/// unused. Settings such as the push check, subtree counts, timestamps & the
+/// child order are not kept.
+///
+/// ```
+/// # use silva::Arena;
+/// let arena = Arena::new();
+/// let root = arena.push(None, "root");
+/// arena.push(root, "leaf");
+/// let json = serde_json::to_string(&arena).unwrap();
+/// assert_eq!(json, r#"[[0,null,"root"],[1,0,"leaf"]]"#);
+/// ```
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Arena<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+
+        let end = self.reserved();
+        let before_end = |node: &Node<T>| node.index().as_usize() < end;
+        // an orphan being attached can be reached both as a top & as a child
+        let mut seen = vec![false; end];
+        let mut records = Vec::with_capacity(self.len());
+        let mut stack = Vec::new();
+        for top in self.iter_range(..end) {
+            if top.parent().is_some_and(before_end) {
+                continue;
+            }
+            stack.push(top);
+            while let Some(node) = stack.pop() {
+                let index = node.index();
+                if mem::replace(&mut seen[index.as_usize()], true) {
+                    continue;
+                }
+                let parent = if node.is_orphan() {
+                    Some(index)
+                } else {
+                    node.parent().map(Node::index)
+                };
+                records.push((index, parent, &node.value));
+                // popped oldest first, so they are linked as they were
+                stack.extend(
+                    node.children_in(ChildOrder::NewestFirst)
+                        .filter(|&child| before_end(child)),
+                );
+            }
+        }
+
+        let mut seq = serializer.serialize_seq(Some(records.len()))?;
+        for record in &records {
+            seq.serialize_element(record)?;
+        }
+        seq.end()
+    }
+}
+
+/// Deserialized from the records written by its [`Serialize`] impl
+///
+/// # Errors
+///
+/// Fails if an index is given twice, a parent is not given before its
+/// children, or the storage for an index could not be allocated. Indices are
+/// taken as given, so a sparse arena holds the storage below its highest.
+///
+/// [`Serialize`]: serde::Serialize
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Arena<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{Error, SeqAccess, Visitor};
+
+        struct Records<T>(PhantomData<T>);
+
+        impl<'de, T: serde::Deserialize<'de>> Visitor<'de> for Records<T> {
+            type Value = Arena<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a sequence of (index, parent, value) records")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Arena<T>, A::Error> {
+                let arena = Arena::new();
+                while let Some((index, parent, value)) =
+                    seq.next_element::<(Index, Option<Index>, T)>()?
+                {
+                    if arena.get(index).is_some() {
+                        return Err(A::Error::custom(format_args!("duplicate index {index}")));
+                    }
+                    let orphan = parent == Some(index);
+                    let parent = match parent.filter(|_| !orphan) {
+                        Some(parent) => Some(arena.get(parent).ok_or_else(|| {
+                            A::Error::custom(format_args!(
+                                "parent {parent} of {index} is not given before it"
+                            ))
+                        })?),
+                        None => None,
+                    };
+                    // SAFETY: the index is unused & the arena is not shared,
+                    // its parent being found in it
+                    unsafe { arena.raw.try_insert_at(index, parent, orphan, value) }.map_err(
+                        |e| A::Error::custom(format_args!("can't store index {index}: {e}")),
+                    )?;
+                }
+                Ok(arena)
+            }
+        }
+
+        deserializer.deserialize_seq(Records(PhantomData))
+    }
+}
+
 impl<T> std::ops::Index<Index> for Arena<T> {
     type Output = Node<T>;
 
@@ -44,45 +243,795 @@ impl<T> Arena<T> {
     pub const fn new() -> Self {
         Self {
             raw: raw::Arena::new(),
+            attach: Mutex::new(()),
+            check: None,
         }
     }
 
     /// Create a tree with atleast the given capacity
+    ///
+    /// A capacity of `0` allocates nothing. Capacity beyond the most nodes an
+    /// arena can hold is ignored, as those nodes could never be pushed anyway,
+    /// see [`Arena::try_with_capacity`] to treat it as an error.
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             raw: raw::Arena::with_capacity(capacity),
+            attach: Mutex::new(()),
+            check: None,
+        }
+    }
+
+    /// Create a tree with atleast the given capacity
+    ///
+    /// # Errors
+    ///
+    /// Fails if `capacity` is more than an arena can hold, or if the memory
+    /// could not be allocated
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, AllocError> {
+        Ok(Self {
+            raw: raw::Arena::try_with_capacity(capacity)?,
+            ..Self::new()
+        })
+    }
+
+    /// Create a tree whose buckets follow the given layout
+    ///
+    /// Nothing is allocated until a node is pushed or capacity is reserved.
+    ///
+    /// ```
+    /// # use silva::{Arena, ArenaLayout};
+    /// // buckets of 32Mi, 64Mi, 128Mi... nodes
+    /// let layout = ArenaLayout::new(1 << 25, 2).unwrap();
+    /// let arena = Arena::<u32>::with_layout(layout);
+    /// assert_eq!(arena.layout(), layout);
+    /// assert_eq!(arena.capacity(), 0);
+    /// ```
+    pub const fn with_layout(layout: ArenaLayout) -> Self {
+        Self {
+            raw: raw::Arena::with_layout(layout),
+            attach: Mutex::new(()),
+            check: None,
         }
     }
 
+    /// The layout of this tree's buckets
+    pub const fn layout(&self) -> ArenaLayout {
+        self.raw.layout()
+    }
+
+    /// Create a tree whose nodes give their children in the given order
+    ///
+    /// In [`ChildOrder::InsertionOrder`], each node also keeps a pointer to
+    /// its last child so children can still be appended without locking.
+    ///
+    /// ```
+    /// # use silva::{Arena, ChildOrder};
+    /// let arena = Arena::with_child_order(ChildOrder::InsertionOrder);
+    /// let root = arena.push(None, 0);
+    /// arena.push_all(root, 1..4);
+    /// assert!(root.child_values().eq(&[1, 2, 3]));
+    /// ```
+    pub const fn with_child_order(order: ChildOrder) -> Self {
+        Self {
+            raw: raw::Arena::with_child_order(order),
+            attach: Mutex::new(()),
+            check: None,
+        }
+    }
+
+    /// The order this tree's nodes give their children in
+    pub const fn child_order(&self) -> ChildOrder {
+        self.raw.child_order()
+    }
+
+    /// Build a forest from `(depth, value)` items given in pre-order
+    ///
+    /// Items at depth `0` are roots, & an item at depth `d + 1` is a child of
+    /// the latest item at depth `d`. Nodes are indexed in stream order, see
+    /// [`Arena::to_depth_stream`] for the inverse.
+    ///
+    /// # Errors
+    ///
+    /// Fails at the first item more than one level deeper than the one before
+    /// it, or the first item if it isn't a root
+    pub fn from_depth_stream(
+        items: impl IntoIterator<Item = (usize, T)>,
+    ) -> Result<Self, DepthError> {
+        let arena = Self::new();
+        // the latest item at each depth above the next
+        let mut ancestors: Vec<Index> = Vec::new();
+        for (position, (depth, value)) in items.into_iter().enumerate() {
+            if depth > ancestors.len() {
+                return Err(DepthError {
+                    position,
+                    depth,
+                    max_depth: ancestors.len(),
+                });
+            }
+            ancestors.truncate(depth);
+            let node = arena.push(ancestors.last().copied(), value);
+            ancestors.push(node.index());
+        }
+        Ok(arena)
+    }
+
+    /// Iterate over `root`'s subtree as `(depth, value)` items
+    ///
+    /// Nodes are given in pre-order, with children in insertion order &
+    /// `root` at depth `0`, so the items can be given back to
+    /// [`Arena::from_depth_stream`]. The subtree is walked using an explicit
+    /// stack.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` does not belong to this arena
+    pub fn to_depth_stream<'a>(
+        &'a self,
+        root: &'a Node<T>,
+    ) -> impl FusedIterator<Item = (usize, &'a T)> + Clone {
+        assert!(self.contains(root), "root does not belong to this arena");
+        let mut stack = vec![(0, root)];
+        std::iter::from_fn(move || {
+            let (depth, node) = stack.pop()?;
+            // pushed newest first, so the oldest is popped first
+            stack.extend(
+                node.children_in(ChildOrder::NewestFirst)
+                    .map(|child| (depth + 1, child)),
+            );
+            Some((depth, &node.value))
+        })
+        .fuse()
+    }
+
+    /// Create a tree which validates every value before it is pushed
+    ///
+    /// `check` is given the parent's value, if any, & the value to be pushed.
+    /// [`Arena::try_push`] returns the rejection as an error, while the other
+    /// push methods panic with it.
+    ///
+    /// Checks run before a node is written, but a push that is given an
+    /// [`Index`] consumes it even if the value is then rejected.
+    pub fn with_push_check<E>(check: fn(Option<&T>, &T) -> Result<(), E>) -> Self
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        Self {
+            check: Some(PushCheck::new(check)),
+            ..Self::new()
+        }
+    }
+
+    /// Create a tree which timestamps every node using `clock`
+    ///
+    /// `clock` is called once per push, before the node is written. The
+    /// timestamps are kept in a side table & can be read using
+    /// [`Arena::timestamp`].
+    pub fn with_clock(clock: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        Self {
+            raw: raw::Arena::with_clock(Arc::new(clock)),
+            ..Self::new()
+        }
+    }
+
+    /// Get the time the node at `index` was pushed
+    ///
+    /// Returns `None` if this arena has no clock or the node does not exist.
+    pub fn timestamp(&self, index: Index) -> Option<u64> {
+        self.raw.timestamp(index)
+    }
+
+    /// Create a tree that keeps the size of every subtree as nodes are pushed
+    ///
+    /// Each push adds to the count of every ancestor, & attaching an orphan
+    /// adds its subtree's count to its new ancestors, so both take
+    /// O(depth). In exchange, [`Arena::subtree_len`] takes O(1).
+    pub fn with_subtree_counts() -> Self {
+        Self {
+            raw: raw::Arena::with_subtree_counts(),
+            ..Self::new()
+        }
+    }
+
+    /// Get the number of nodes in `node`'s subtree, including `node`
+    ///
+    /// This is O(1) in an arena made with [`Arena::with_subtree_counts`], & a
+    /// walk of the subtree otherwise, see [`Node::subtree_len`]. Nodes still
+    /// being pushed under `node` may not be counted yet.
+    ///
+    /// # Panics
+    ///
+    /// panics if `node` does not belong to this arena
+    pub fn subtree_len(&self, node: &Node<T>) -> usize {
+        assert!(self.contains(node), "node does not belong to this arena");
+        self.raw
+            .subtree_len(node)
+            .unwrap_or_else(|| node.subtree_len())
+    }
+
+    /// Run the push check, if there is one
+    fn check(&self, parent: Option<&Node<T>>, value: &T) -> Result<(), CheckError> {
+        self.check
+            .as_ref()
+            .map_or(Ok(()), |check| check.call(parent.map(|p| &p.value), value))
+    }
+
+    /// Run the push check, panicking if `value` is rejected
+    fn check_or_panic(&self, parent: Option<&Node<T>>, value: T) -> T {
+        if let Err(e) = self.check(parent, &value) {
+            panic!("{e}");
+        }
+        value
+    }
+
     /// Reserve atleast `additional` more slots
+    ///
+    /// Counted from [`Arena::reserved`] rather than the live nodes, as that is
+    /// where new nodes are placed. Side tables, such as clock stamps, are
+    /// reserved too, so the reserved slots can be filled by
+    /// [`Arena::push_within_capacity`].
     pub fn reserve(&self, additional: usize) {
         self.raw.reserve(additional);
     }
 
+    /// Reserve atleast `additional` more slots, like [`Vec::try_reserve`]
+    ///
+    /// Unlike [`Arena::reserve`], this never aborts on allocation failure, so
+    /// it's safe to call with sizes from untrusted input. Otherwise it
+    /// reserves the same slots.
+    ///
+    /// ```
+    /// # use silva::{AllocError, Arena};
+    /// let arena = Arena::<u8>::new();
+    /// arena.try_reserve(100).unwrap();
+    /// assert!(arena.capacity() >= 100);
+    /// assert_eq!(arena.try_reserve(usize::MAX), Err(AllocError::CapacityOverflow));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`AllocError::CapacityOverflow`] if the arena can never hold
+    /// that many nodes, or [`AllocError::Failed`] if the memory could not be
+    /// allocated. Space that was allocated before failing is kept
+    pub fn try_reserve(&self, additional: usize) -> Result<(), AllocError> {
+        self.raw.try_reserve(additional)
+    }
+
     /// Get the node of the given [`Index`]
     ///
-    /// This returns an option since `index` may have come from another [`Arena`]
+    /// This returns an option since `index` may have come from another [`Arena`],
+    /// or its node may have been removed
     pub fn get(&self, index: Index) -> Option<&Node<T>> {
         self.raw.get(index)
     }
 
+    /// Get a [`Handle`] to the node of the given [`Index`]
+    ///
+    /// Unlike [`Node::handle`], the node is only looked up once.
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use silva::Arena;
+    /// let arena = Arc::new(Arena::new());
+    /// let root = arena.push(None, "root").index();
+    /// let handle = arena.handle_at(root).unwrap();
+    /// assert_eq!(handle.value, "root");
+    /// ```
+    pub fn handle_at(self: &Arc<Self>, index: Index) -> Option<Handle<T>> {
+        let node = self.get(index)?;
+        // SAFETY: the node was just taken from this arena
+        Some(unsafe { Handle::new(node, self) })
+    }
+
+    /// Get the value of the node of the given [`Index`] mutably
+    ///
+    /// Only values can be borrowed mutably, nodes can't be moved without
+    /// breaking their links.
+    pub fn value_mut(&mut self, index: Index) -> Option<&mut T> {
+        // SAFETY: the arena is borrowed mutably, so nothing else is using the
+        // value
+        self.raw
+            .value_ptr(index)
+            .map(|value| unsafe { &mut *value })
+    }
+
+    /// Replace the value of the node of the given [`Index`], giving back the
+    /// old one
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no node at `index`
+    pub fn replace(&mut self, index: Index, value: T) -> T {
+        let old = self.value_mut(index).expect("no node at index");
+        mem::replace(old, value)
+    }
+
+    /// Take the value of the node of the given [`Index`], leaving the default
+    /// in its place
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no node at `index`
+    pub fn take(&mut self, index: Index) -> T
+    where
+        T: Default,
+    {
+        self.replace(index, T::default())
+    }
+
+    /// Get the nodes of the given indices, in order
+    ///
+    /// Each item is `None` where [`Arena::get`] would return `None`.
+    pub fn resolve_all(
+        &self,
+        indices: impl IntoIterator<Item = Index>,
+    ) -> impl Iterator<Item = Option<&Node<T>>> {
+        indices.into_iter().map(|index| self.get(index))
+    }
+
+    /// Get the node at the given dense index
+    ///
+    /// See [`Index::as_usize`]
+    pub fn get_dense(&self, index: usize) -> Option<&Node<T>> {
+        self.raw.get_dense(index)
+    }
+
+    /// Resolve a parent the way the push methods do, without panicking
+    ///
+    /// # Errors
+    ///
+    /// Fails if `parent` does not belong to this arena
+    pub fn try_resolve_parent(
+        &self,
+        parent: impl AsParent<T>,
+    ) -> Result<Option<&Node<T>>, ForeignNodeError> {
+        parent.try_get(self)
+    }
+
     /// Add a new node
+    ///
+    /// # Panics
+    ///
+    /// panics if the arena is full or the value is rejected by its push check
     pub fn push(&self, parent: impl AsParent<T>, value: T) -> &Node<T> {
-        self.raw.push_with(parent.get(self), |_| value)
+        let parent = parent.get(self);
+        let value = self.check_or_panic(parent, value);
+        self.raw.push_with(parent, |_| value)
+    }
+
+    /// Add a new node, returning the value if it could not be pushed
+    ///
+    /// # Errors
+    ///
+    /// Fails if the parent does not belong to this arena, the arena is full,
+    /// its storage could not be allocated, or the value is rejected by its
+    /// push check. The node's index is consumed if allocation fails.
+    pub fn try_push(&self, parent: impl AsParent<T>, value: T) -> Result<&Node<T>, PushError<T>> {
+        let parent = match parent.try_get(self) {
+            Ok(parent) => parent,
+            Err(e) => return Err(PushError::Foreign(e, value)),
+        };
+        if let Err(e) = self.check(parent, &value) {
+            return Err(PushError::Rejected(e, value));
+        }
+        self.raw.try_push(parent, value)
     }
 
     /// Add a new node using the given function
+    ///
+    /// # Panics
+    ///
+    /// panics if the arena is full or the value is rejected by its push check
     pub fn push_with(&self, parent: impl AsParent<T>, f: impl FnOnce(Index) -> T) -> &Node<T> {
-        self.raw.push_with(parent.get(self), f)
+        let parent = parent.get(self);
+        self.raw
+            .push_with(parent, |index| self.check_or_panic(parent, f(index)))
+    }
+
+    /// Add a new node without allocating, returning the value if there's no
+    /// room for it
+    ///
+    /// Only succeeds if the node's storage is already allocated, as by
+    /// [`Arena::reserve`], so a push never touches the allocator. No index is
+    /// taken on failure.
+    ///
+    /// ```
+    /// # use silva::Arena;
+    /// let arena = Arena::with_capacity(1);
+    /// let root = arena.push_within_capacity(None, 0).unwrap();
+    /// let full = arena.capacity() - 1;
+    /// for i in 0..full {
+    ///     arena.push_within_capacity(root, i).unwrap();
+    /// }
+    /// assert_eq!(arena.push_within_capacity(root, 0).unwrap_err(), 0);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the node's storage is not allocated, or the arena is full
+    ///
+    /// # Panics
+    ///
+    /// panics if the value is rejected by the push check
+    pub fn push_within_capacity(&self, parent: impl AsParent<T>, value: T) -> Result<&Node<T>, T> {
+        let parent = parent.get(self);
+        let value = self.check_or_panic(parent, value);
+        self.raw.push_within_capacity(parent, value)
+    }
+
+    /// Add a new node using the given function, returning the value if it
+    /// could not be pushed
+    ///
+    /// `f` is only called once the node's index & storage are secured, so
+    /// there is only a value to give back if it is rejected by the push check.
+    ///
+    /// # Errors
+    ///
+    /// Fails as [`Arena::try_push`] does. The node's index is consumed if
+    /// allocation fails or the value is rejected.
+    pub fn try_push_with(
+        &self,
+        parent: impl AsParent<T>,
+        f: impl FnOnce(Index) -> T,
+    ) -> Result<&Node<T>, PushError<Option<T>>> {
+        let parent = parent
+            .try_get(self)
+            .map_err(|e| PushError::Foreign(e, None))?;
+        let index = self.raw.try_reserve_index().map_err(|e| e.map(|()| None))?;
+        let value = f(index);
+        if let Err(e) = self.check(parent, &value) {
+            return Err(PushError::Rejected(e, Some(value)));
+        }
+        // SAFETY: the index was just reserved
+        Ok(unsafe { self.raw.push_at(index, parent, value) })
+    }
+
+    /// Add a new node, along with the number of siblings linked before it
+    ///
+    /// Ordinals are unique per parent, even when pushing concurrently, as each
+    /// child is linked in a different spot. Attached orphans count as
+    /// siblings too. Roots aren't linked to one another, so their ordinal is
+    /// always `0`.
+    ///
+    /// # Panics
+    ///
+    /// panics if the arena is full or the value is rejected by its push check
+    pub fn push_with_ordinal(&self, parent: impl AsParent<T>, value: T) -> (&Node<T>, usize) {
+        let node = self.push(parent, value);
+        // each child records its place as it is linked
+        (node, node.position().saturating_sub(1))
     }
 
     /// Add new nodes using the given iterator
+    ///
+    /// The nodes are given a contiguous range of indices & are all written
+    /// before this returns, in iteration order.
+    ///
+    /// # Panics
+    ///
+    /// panics if the arena is full or a value is rejected by its push check
     pub fn push_all(
         &self,
         parent: impl AsParent<T>,
         values: impl IntoIterator<Item = T, IntoIter: ExactSizeIterator>,
-    ) -> impl ExactSizeIterator<Item = &Node<T>> {
-        self.raw.push_all(parent.get(self), values.into_iter())
+    ) -> PushedNodes<'_, T> {
+        let parent = parent.get(self);
+        let values = values
+            .into_iter()
+            .map(move |value| self.check_or_panic(parent, value));
+        PushedNodes {
+            range: self.raw.push_all(parent, values),
+            arena: self,
+        }
+    }
+
+    /// Add new nodes using the given iterator, returning the values if they
+    /// could not all be pushed
+    ///
+    /// Either every value is pushed, as by [`Arena::push_all`], or none are.
+    /// The values are collected & checked before any index is taken.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the parent does not belong to this arena, the arena can't fit
+    /// every value, their storage could not be allocated, or any value is
+    /// rejected by the push check. The indices are consumed if allocation
+    /// fails.
+    pub fn try_push_all(
+        &self,
+        parent: impl AsParent<T>,
+        values: impl IntoIterator<Item = T>,
+    ) -> Result<PushedNodes<'_, T>, PushError<Vec<T>>> {
+        let values: Vec<T> = values.into_iter().collect();
+        let parent = match parent.try_get(self) {
+            Ok(parent) => parent,
+            Err(e) => return Err(PushError::Foreign(e, values)),
+        };
+        for value in &values {
+            if let Err(e) = self.check(parent, value) {
+                return Err(PushError::Rejected(e, values));
+            }
+        }
+        Ok(PushedNodes {
+            range: self.raw.try_push_all(parent, values)?,
+            arena: self,
+        })
+    }
+
+    /// Add a new orphan, a node without a parent that is not a root
+    ///
+    /// The orphan can be given a parent later on using [`Arena::attach`]. Until
+    /// then it is excluded from [`Arena::roots`].
+    pub fn push_orphan(&self, value: T) -> &Node<T> {
+        self.raw.push_orphan(value)
+    }
+
+    /// Give an orphan created by [`Arena::push_orphan`] its parent
+    ///
+    /// # Errors
+    ///
+    /// This can only succeed once per orphan, and fails if `parent` is the
+    /// orphan itself or one of its descendants.
+    ///
+    /// # Panics
+    ///
+    /// panics if either node does not belong to this arena
+    pub fn attach(&self, parent: &Node<T>, orphan: &Node<T>) -> Result<(), AttachError> {
+        assert!(
+            self.contains(parent) && self.contains(orphan),
+            "node does not belong to this arena"
+        );
+        let _guard = self.attach.lock().unwrap_or_else(PoisonError::into_inner);
+        // SAFETY: both nodes are in this arena, attaches are serialized
+        unsafe { Node::attach(self.raw.link(Some(parent)), self.raw.link(Some(orphan))) }?;
+        self.raw.attached(parent, orphan);
+        Ok(())
+    }
+
+    /// Remove the node at `index` along with its subtree, dropping their
+    /// values
+    ///
+    /// The nodes are unlinked from the tree & their slots left empty, so
+    /// getters & iterators skip them from then on. Indices are never reused,
+    /// so an index of a removed node stays dead. Returns the number of nodes
+    /// removed, `0` if there is no node at `index`.
+    pub fn remove_subtree(&mut self, index: Index) -> usize {
+        self.raw.remove_subtree(index)
+    }
+
+    /// Move the subtree rooted at the node at `root` into a new arena
+    ///
+    /// The subtree is removed from this arena, as by
+    /// [`Arena::remove_subtree`], & its values moved into the new one without
+    /// being cloned. Indices are given afresh, as by [`Arena::map_subtree`],
+    /// so the root is at index `0`. Subtree counts are kept, but timestamps &
+    /// the push check are not.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no node at `root`
+    #[must_use]
+    pub fn split_off_subtree(&mut self, root: Index) -> Self {
+        Self {
+            raw: self.raw.split_off_subtree(root),
+            attach: Mutex::new(()),
+            check: None,
+        }
+    }
+
+    /// Move every tree of `other` under `parent`, giving where each of its
+    /// nodes ended up
+    ///
+    /// Roots are taken in index order, each tree pushed in pre-order with
+    /// children in insertion order, so `other`'s values are moved without
+    /// being cloned. Unattached orphans of `other` stay orphans here, along
+    /// with their subtrees. The map is keyed by `other`'s indices.
+    ///
+    /// # Panics
+    ///
+    /// panics if the arena is full or a value is rejected by its push check
+    pub fn append(&mut self, mut other: Self, parent: impl AsParent<T>) -> SecondaryMap<Index> {
+        let parent = parent.get(self).map(Node::index);
+        // each node along with where its parent is in the list, or whether
+        // it is an orphan if it has none
+        let mut nodes = Vec::with_capacity(other.len());
+        for top in other
+            .iter_range(..)
+            .filter(|n| n.is_root() || n.is_orphan())
+        {
+            let mut stack = vec![(top, Err(top.is_orphan()))];
+            while let Some((node, up)) = stack.pop() {
+                let at = nodes.len();
+                nodes.push((node.index(), up));
+                stack.extend(
+                    node.children_in(ChildOrder::NewestFirst)
+                        .map(|child| (child, Ok(at))),
+                );
+            }
+        }
+
+        let mut map = SecondaryMap::with_capacity(other.reserved());
+        let mut pushed = Vec::with_capacity(nodes.len());
+        for (index, up) in nodes {
+            // SAFETY: `other` is dropped after this, without reaching the
+            // taken nodes through links
+            let node = unsafe { other.raw.take(index) }.expect("listed nodes are initialized");
+            let new = match up {
+                Ok(at) => self.push(pushed[at], node.value),
+                Err(true) => self.push_orphan(node.value),
+                Err(false) => self.push(parent, node.value),
+            };
+            pushed.push(new.index());
+            map.insert(index, new.index());
+        }
+        map
+    }
+
+    /// Keep only the nodes for which `pred` returns `true`, removing the rest
+    /// along with their subtrees
+    ///
+    /// Nodes are checked in index order. A node removed with the subtree of
+    /// one checked before it is not checked itself. See
+    /// [`Arena::remove_subtree`].
+    pub fn retain(&mut self, mut pred: impl FnMut(&Node<T>) -> bool) {
+        for index in 0..self.raw.watermark() {
+            let Some(node) = self.raw.get_dense(index) else {
+                continue;
+            };
+            if !pred(node) {
+                let index = node.index();
+                self.raw.remove_subtree(index);
+            }
+        }
+    }
+
+    /// Move the node at `node`, along with its subtree, under the node at
+    /// `parent`
+    ///
+    /// The node is unlinked from its old parent, if it had one, & linked as
+    /// [`Arena::push`] would link a new child. Roots & unattached orphans can
+    /// be moved too.
+    ///
+    /// # Errors
+    ///
+    /// Fails if there is no node at either index, or if `parent` is the node
+    /// itself or one of its descendants
+    pub fn reparent(&mut self, node: Index, parent: Index) -> Result<(), ReparentError> {
+        self.raw.reparent(node, parent)
+    }
+
+    /// Exchange the places of the nodes at `a` & `b`, along with their
+    /// subtrees
+    ///
+    /// Each takes the other's parent & spot among its siblings, so a root or
+    /// unattached orphan swapped with a child becomes a child, & the child a
+    /// root or orphan. Values are left in place, only links change.
+    ///
+    /// ```
+    /// # use silva::Arena;
+    /// let mut arena = Arena::new();
+    /// let root = arena.push(None, "+");
+    /// let left = arena.push(root, "1");
+    /// let right = arena.push(root, "*");
+    /// arena.push_all(right, ["2", "3"]);
+    /// let (root, left, right) = (root.index(), left.index(), right.index());
+    ///
+    /// arena.swap_subtrees(left, right).unwrap();
+    /// assert!(arena[root].child_values().eq(&["1", "*"]));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if there is no node at either index, or if either node is in the
+    /// other's subtree
+    pub fn swap_subtrees(&mut self, a: Index, b: Index) -> Result<(), SwapError> {
+        self.raw.swap_subtrees(a, b)
+    }
+
+    /// Sort the children of the node at `index` by `cmp`, relinking them in
+    /// place
+    ///
+    /// [`Node::children`] gives them in sorted order from then on, as if they
+    /// had been pushed to come out that way. The sort is stable, keeping
+    /// equal children in the order they were given in.
+    ///
+    /// ```
+    /// # use silva::Arena;
+    /// let mut arena = Arena::new();
+    /// let dir = arena.push(None, "dir");
+    /// arena.push_all(dir, ["b.txt", "c.txt", "a.txt"]);
+    /// let dir = dir.index();
+    ///
+    /// arena.sort_children_by(dir, |a, b| a.value.cmp(b.value));
+    /// let dir = arena.get(dir).unwrap();
+    /// assert!(dir.child_values().eq(&["a.txt", "b.txt", "c.txt"]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no node at `index`
+    pub fn sort_children_by(
+        &mut self,
+        index: Index,
+        cmp: impl FnMut(&Node<T>, &Node<T>) -> Ordering,
+    ) {
+        self.raw.sort_children_by(index, cmp);
+    }
+
+    /// Remove every node, dropping their values
+    ///
+    /// Allocated storage is kept for the nodes pushed afterwards, which are
+    /// given indices from `0` again. Indices from before clearing may point
+    /// to new nodes, unless the `generations` feature is on, in which case
+    /// they point to nothing, see `Index::generation`.
+    pub fn clear(&mut self) {
+        self.raw.clear();
+    }
+
+    /// Iterate over the roots of this arena, in index order
+    ///
+    /// Unattached orphans are not roots, and so are skipped.
+    ///
+    /// ```
+    /// # use silva::Arena;
+    /// let arena = Arena::new();
+    /// let a = arena.push(None, "a");
+    /// arena.push(a, "a1");
+    /// arena.push(None, "b");
+    /// arena.push_orphan("orphan");
+    /// assert!(arena.roots().map(|root| root.value).eq(["a", "b"]));
+    /// ```
+    pub fn roots(&self) -> Roots<'_, T> {
+        Roots(self.iter_range(..))
+    }
+
+    /// Walk the subtree rooted at `root`, calling `visitor` as each node is
+    /// entered & left
+    ///
+    /// See [`visit::walk`], which this forwards to. Returns `false` if the
+    /// walk was stopped by [`Flow::Break`](visit::Flow::Break).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` does not belong to this arena
+    pub fn walk<'a, V>(&'a self, root: &'a Node<T>, visitor: &mut V) -> bool
+    where
+        V: Visitor<'a, T> + ?Sized,
+    {
+        assert!(self.contains(root), "root does not belong to this arena");
+        visit::walk(root, visitor)
+    }
+
+    /// Walk the subtrees rooted at `a_root` & `b_root` in lockstep
+    ///
+    /// See [`algo::zip_trees`], which this forwards to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either root does not belong to this arena
+    pub fn zip_trees<'a>(&'a self, a_root: &'a Node<T>, b_root: &'a Node<T>) -> ZipTrees<'a, T> {
+        assert!(
+            self.contains(a_root) && self.contains(b_root),
+            "root does not belong to this arena"
+        );
+        algo::zip_trees(a_root, b_root)
+    }
+
+    /// A wrapper for debugging the shape of every tree in this arena
+    ///
+    /// Prints a list holding each root's [`Node::debug_structure`], so `T`
+    /// needn't be [`Debug`](fmt::Debug). Unattached orphans are skipped.
+    pub fn debug_structure(&self) -> impl fmt::Debug + '_ {
+        struct DebugRoots<'a, T>(&'a Arena<T>);
+
+        impl<T> fmt::Debug for DebugRoots<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list()
+                    .entries(self.0.roots().map(Node::debug_structure))
+                    .finish()
+            }
+        }
+
+        DebugRoots(self)
     }
 
     /// returns `true` if the given node belongs to this arena
@@ -91,18 +1040,493 @@ impl<T> Arena<T> {
     }
 
     /// Get the number of available nodes
+    ///
+    /// See [`Arena::len`]
     pub fn count(&self) -> usize {
         self.raw.count()
     }
 
+    /// Get the number of nodes whose push has completed, as visible to this
+    /// thread
+    ///
+    /// While pushes are ongoing this may lag behind, & the counted nodes need
+    /// not be the first `len` indices. See [`Arena::len_acquire`] for a count
+    /// that can be relied on to read nodes.
+    pub fn len(&self) -> usize {
+        self.raw.count()
+    }
+
+    /// returns `true` if no push has completed, as visible to this thread
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the number of nodes whose push has completed, synchronizing with
+    /// those pushes
+    ///
+    /// Every node counted is visible to this thread once this returns. Once
+    /// this reaches the number of pushes made, every node can be read.
+    pub fn len_acquire(&self) -> usize {
+        self.raw.count_acquire()
+    }
+
+    /// Get the number of indices handed out so far
+    ///
+    /// This counts pushes that are still ongoing, along with those that
+    /// panicked or failed to allocate.
+    pub fn reserved(&self) -> usize {
+        self.raw.watermark()
+    }
+
+    /// Iterate over the initialized nodes whose index falls in `range`
+    ///
+    /// Nodes are yielded in index order. The range is clamped to the indices
+    /// handed out when this is called, nodes pushed afterwards are excluded
+    /// and nodes still being initialized are skipped.
+    pub fn iter_range(&self, range: impl RangeBounds<usize>) -> Nodes<'_, T> {
+        let start = match range.start_bound() {
+            Bound::Included(&n) => n,
+            Bound::Excluded(&n) => n.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&n) => n.saturating_add(1),
+            Bound::Excluded(&n) => n,
+            Bound::Unbounded => usize::MAX,
+        };
+        Nodes {
+            pos: start,
+            end: end.min(self.raw.watermark()),
+            arena: self,
+        }
+    }
+
+    /// Iterate over the initialized nodes starting from index `start`
+    ///
+    /// See [`Arena::iter_range`].
+    pub fn iter_from(&self, start: usize) -> Nodes<'_, T> {
+        self.iter_range(start..)
+    }
+
+    /// Find the first initialized node, in index order, that matches `pred`
+    ///
+    /// See [`Arena::iter_range`]
+    pub fn find(&self, mut pred: impl FnMut(&Node<T>) -> bool) -> Option<&Node<T>> {
+        self.iter_range(..).find(|node| pred(node))
+    }
+
+    /// Iterate over the values of the initialized nodes, in index order
+    ///
+    /// See [`Arena::iter_range`]
+    pub fn values(&self) -> impl FusedIterator<Item = &T> + Clone {
+        self.iter_range(..).map(|node| &node.value)
+    }
+
+    /// Iterate over the values of the initialized nodes mutably, in index
+    /// order
+    ///
+    /// See [`Arena::iter_mut`]
+    pub fn values_mut(&mut self) -> impl FusedIterator<Item = &mut T> {
+        self.iter_mut().map(|(_, value)| value)
+    }
+
+    /// Iterate over the indices & values of the initialized nodes, in index
+    /// order
+    ///
+    /// See [`Arena::iter_range`]
+    pub fn entries(&self) -> impl FusedIterator<Item = (Index, &T)> + Clone {
+        self.iter_range(..).map(|node| (node.index(), &node.value))
+    }
+
+    /// Iterate over the indices & mutable values of the initialized nodes,
+    /// in index order
+    ///
+    /// See [`Arena::value_mut`]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            pos: 0,
+            end: self.raw.watermark(),
+            arena: self,
+            marker: PhantomData,
+        }
+    }
+
+    /// Move the values out of the initialized nodes, in index order
+    ///
+    /// See [`IntoIter`]
+    pub fn into_values(self) -> impl FusedIterator<Item = T> {
+        self.into_iter().map(|(_, value)| value)
+    }
+
+    /// Take a point-in-time view of this arena
+    ///
+    /// See [`ArenaSnapshot`]
+    pub fn snapshot(&self) -> ArenaSnapshot<'_, T> {
+        ArenaSnapshot::new(self)
+    }
+
+    /// Count the initialized nodes whose index is below `watermark`
+    ///
+    /// This walks every index below `watermark`.
+    pub fn count_initialized_below(&self, watermark: usize) -> usize {
+        self.iter_range(..watermark).count()
+    }
+
+    /// Map every value into a new arena, keeping each node's index & links
+    ///
+    /// `f` is called in index order. Timestamps are kept, but the push check
+    /// is not.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Arena<U> {
+        Arena {
+            raw: self.raw.map(f),
+            attach: Mutex::new(()),
+            check: None,
+        }
+    }
+
+    /// Copy the subtree rooted at `root` into a new arena, mapping each value
+    ///
+    /// The new arena gives children in the same order as this one, & `f` is
+    /// called on each node in pre-order, where it is pushed. So `root` ends up
+    /// at index `0`, & each node after its parent & older siblings. Nodes
+    /// pushed while this runs may be left out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` does not belong to this arena
+    pub fn map_subtree<U>(&self, root: &Node<T>, f: impl Fn(&T) -> U) -> Arena<U> {
+        assert!(self.contains(root), "root does not belong to this arena");
+        let arena = Arena::with_child_order(self.child_order());
+        // newest first, the reverse of the order they are pushed in
+        let mut stack = vec![(root, None)];
+        while let Some((node, parent)) = stack.pop() {
+            let copy = arena.push(parent, f(&node.value)).index();
+            stack.extend(
+                node.children_in(ChildOrder::NewestFirst)
+                    .map(|child| (child, Some(copy))),
+            );
+        }
+        arena
+    }
+
+    /// Copy the subtree rooted at `root` under `parent`, giving the copy of
+    /// `root`
+    ///
+    /// The subtree is gathered before anything is pushed, so `parent` may be
+    /// within it without the copy taking in its own nodes. Nodes are pushed
+    /// in pre-order with children in insertion order, as by
+    /// [`Arena::map_subtree`], & nodes pushed while this runs may be left out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` does not belong to this arena, the arena is full or a
+    /// value is rejected by its push check
+    pub fn duplicate_subtree(&self, root: &Node<T>, parent: impl AsParent<T>) -> &Node<T>
+    where
+        T: Clone,
+    {
+        assert!(self.contains(root), "root does not belong to this arena");
+        let parent = parent.get(self);
+        // each node along with where its parent is in the list
+        let mut nodes: Vec<(&Node<T>, Option<usize>)> = Vec::new();
+        let mut stack = vec![(root, None)];
+        while let Some((node, up)) = stack.pop() {
+            let at = nodes.len();
+            nodes.push((node, up));
+            stack.extend(
+                node.children_in(ChildOrder::NewestFirst)
+                    .map(|child| (child, Some(at))),
+            );
+        }
+
+        let mut copies: Vec<&Node<T>> = Vec::with_capacity(nodes.len());
+        for (node, up) in nodes {
+            let parent = up.map_or(parent, |at| Some(copies[at]));
+            let value = self.check_or_panic(parent, node.value.clone());
+            copies.push(self.raw.push_with(parent, |_| value));
+        }
+        copies[0]
+    }
+
+    /// Get how long readers have spent waiting on nodes being written
+    #[cfg(feature = "contention-stats")]
+    pub fn contention_stats(&self) -> ContentionStats {
+        let (spins, yields) = self.raw.contention();
+        ContentionStats { spins, yields }
+    }
+
     /// Get the number of available slots
     ///
-    /// `capacity` + `SLOTS`([`usize::BITS`]) should always be a power of two.
+    /// With the default layout, `capacity` + `SLOTS`([`usize::BITS`]) should
+    /// always be a power of two.
     pub fn capacity(&self) -> usize {
         self.raw.capacity()
     }
 }
 
+impl<T: ?Sized + ToOwned> Arena<&T> {
+    /// Copy every borrowed value into a new arena, keeping each node's index
+    ///
+    /// See [`Arena::map`]
+    pub fn map_into_owned(self) -> Arena<T::Owned> {
+        self.map(T::to_owned)
+    }
+}
+
+/// Iterates over an arena's initialized nodes in index order
+///
+/// Created by [`Arena::iter_range`]
+pub struct Nodes<'a, T> {
+    pos: usize,
+    end: usize,
+    arena: &'a Arena<T>,
+}
+
+impl<T> fmt::Debug for Nodes<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Nodes")
+            .field("pos", &self.pos)
+            .field("end", &self.end)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Clone for Nodes<'_, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'a, T> Iterator for Nodes<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.end {
+            let pos = self.pos;
+            self.pos += 1;
+            if let Some(node) = self.arena.raw.get_dense(pos) {
+                return Some(node);
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end.saturating_sub(self.pos)))
+    }
+}
+
+impl<T> FusedIterator for Nodes<'_, T> {}
+
+impl<'a, T> IntoIterator for &'a mut Arena<T> {
+    type Item = (Index, &'a mut T);
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> IntoIterator for Arena<T> {
+    type Item = (Index, T);
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            pos: 0,
+            end: self.raw.watermark(),
+            raw: self.raw,
+        }
+    }
+}
+
+/// Moves the indices & values out of an arena's nodes, in index order
+///
+/// Values not yet given are dropped along with this.
+pub struct IntoIter<T> {
+    pos: usize,
+    end: usize,
+    raw: raw::Arena<T>,
+}
+
+impl<T> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IntoIter")
+            .field("pos", &self.pos)
+            .field("end", &self.end)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = (Index, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.end {
+            // SAFETY: pos is below the watermark
+            let index = unsafe { self.raw.index_at(self.pos) };
+            self.pos += 1;
+            // SAFETY: the arena is owned, & no node is reached through links
+            if let Some(node) = unsafe { self.raw.take(index) } {
+                return Some((index, node.value));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end.saturating_sub(self.pos)))
+    }
+}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// Iterates over the indices & mutable values of an arena's nodes
+///
+/// Created by [`Arena::iter_mut`]
+pub struct IterMut<'a, T> {
+    pos: usize,
+    end: usize,
+    /// Only ever used to reach the values, each given out once
+    arena: &'a Arena<T>,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<T> fmt::Debug for IterMut<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IterMut")
+            .field("pos", &self.pos)
+            .field("end", &self.end)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = (Index, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pos < self.end {
+            // SAFETY: pos is below the watermark
+            let index = unsafe { self.arena.raw.index_at(self.pos) };
+            self.pos += 1;
+            if let Some(value) = self.arena.raw.value_ptr(index) {
+                // SAFETY: the arena is borrowed mutably for 'a, & each index
+                // is only given once
+                return Some((index, unsafe { &mut *value }));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end.saturating_sub(self.pos)))
+    }
+}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// The nodes added by [`Arena::push_all`]
+///
+/// Every node has already been written, this only yields them in index order.
+pub struct PushedNodes<'a, T> {
+    range: Range<usize>,
+    arena: &'a Arena<T>,
+}
+
+impl<'a, T> PushedNodes<'a, T> {
+    /// The dense indices of the nodes not yet yielded
+    ///
+    /// These can be used with [`Arena::get_dense`].
+    pub fn index_range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    fn node(&self, index: usize) -> &'a Node<T> {
+        self.arena
+            .get_dense(index)
+            .expect("pushed nodes are always initialized")
+    }
+}
+
+impl<T> fmt::Debug for PushedNodes<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PushedNodes")
+            .field("range", &self.range)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Clone for PushedNodes<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            range: self.range.clone(),
+            arena: self.arena,
+        }
+    }
+}
+
+impl<'a, T> Iterator for PushedNodes<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.range.next()?;
+        Some(self.node(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for PushedNodes<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.range.next_back()?;
+        Some(self.node(index))
+    }
+}
+
+impl<T> ExactSizeIterator for PushedNodes<'_, T> {}
+
+impl<T> FusedIterator for PushedNodes<'_, T> {}
+
+/// Iterates over an arena's roots in index order
+///
+/// Created by [`Arena::roots`]
+#[derive(Debug)]
+pub struct Roots<'a, T>(Nodes<'a, T>);
+
+impl<T> Clone for Roots<'_, T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<'a, T> Iterator for Roots<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.find(|node| node.is_root())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> FusedIterator for Roots<'_, T> {}
+
+/// How long readers have waited on nodes that were still being written
+///
+/// Returned by [`Arena::contention_stats`]
+#[cfg(feature = "contention-stats")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ContentionStats {
+    /// The total spin loop iterations
+    pub spins: u64,
+    /// The total times a reader yielded its thread
+    pub yields: u64,
+}
+
 // struct IterNodes<'a, T> {
 //     pos: usize,
 //     arena: &'a Arena<T>,
@@ -1,15 +1,27 @@
-use std::ptr::NonNull;
+use std::alloc;
+use std::ptr::{self, NonNull};
 use std::sync::OnceLock;
-use std::{alloc, slice};
 
-use super::raw::Location;
+use crate::error::AllocError;
 
-pub struct Bucket<T> {
+/// Types for which the all-zero bit pattern is a valid value
+///
+/// # Safety
+///
+/// A zeroed allocation of `Self` must be safe to read, use, and drop as is.
+pub unsafe trait ZeroInit {}
+
+// SAFETY: atomics share the layout of their integer, which is valid when zeroed
+unsafe impl ZeroInit for std::sync::atomic::AtomicU64 {}
+// SAFETY: as above
+unsafe impl ZeroInit for std::sync::atomic::AtomicUsize {}
+
+pub struct Bucket<T: ZeroInit> {
     // NOTE: seems to be about as performant on linux, should check other platforms
     entries: OnceLock<NonNull<T>>,
 }
 
-impl<T> Bucket<T> {
+impl<T: ZeroInit> Bucket<T> {
     #[expect(clippy::declare_interior_mutable_const)]
     pub const EMPTY: Self = Self {
         entries: OnceLock::new(),
@@ -27,17 +39,17 @@ impl<T> Bucket<T> {
 
     /// Acquires an item from this entry
     ///
-    /// The bucket will be initialized if it is null.
+    /// The bucket will be initialized with `len` entries if it is null.
     ///
     /// # Safety
     ///
-    /// The given [`Location`] must be valid for this bucket.
-    pub unsafe fn acquire(&self, loc: Location) -> &T {
-        // SAFETY: loc soundness upheld by caller
+    /// `len` must be the correct amount for this bucket, & `entry` below it.
+    pub unsafe fn acquire(&self, entry: usize, len: usize) -> &T {
+        // SAFETY: entry & len soundness upheld by caller
         unsafe {
             self.entries
-                .get_or_init(|| Self::alloc(Location::capacity(loc.bucket)).0)
-                .add(loc.entry)
+                .get_or_init(|| Self::alloc(len))
+                .add(entry)
                 .as_ref()
         }
     }
@@ -50,36 +62,72 @@ impl<T> Bucket<T> {
     /// This bucket's entries must also be uninitialized.
     pub unsafe fn overwrite(&self, len: usize) {
         // SAFETY: len soundness upheld by caller
-        let r = self.entries.set(unsafe { Self::alloc(len) }.0);
+        let r = self.entries.set(unsafe { Self::alloc(len) });
         debug_assert!(r.is_ok(), "entries overwritten");
     }
 
-    /// Allocate an array of entries of the specified length.
+    /// Inititializes a bucket, failing if it could not be allocated
     ///
     /// # Safety
     ///
-    /// `len` must be non-zero & the correct amount for the given bucket
-    unsafe fn alloc(len: usize) -> (NonNull<T>, alloc::Layout) {
-        let layout = alloc::Layout::array::<T>(len).unwrap();
+    /// See [`Bucket::overwrite`]
+    pub unsafe fn try_overwrite(&self, len: usize) -> Result<(), AllocError> {
         // SAFETY: len soundness upheld by caller
-        NonNull::new(unsafe { alloc::alloc_zeroed(layout) }).map_or_else(
-            || alloc::handle_alloc_error(layout),
-            |ptr| (ptr.cast(), layout),
-        )
+        let entries = unsafe { Self::try_alloc(len) }?;
+        let r = self.entries.set(entries);
+        debug_assert!(r.is_ok(), "entries overwritten");
+        Ok(())
+    }
+
+    /// The layout of a bucket holding `len` entries
+    ///
+    /// Used for both allocation & deallocation so the two can never disagree.
+    fn layout(len: usize) -> alloc::Layout {
+        alloc::Layout::array::<T>(len).expect("bucket layout overflow")
+    }
+
+    /// Allocate an array of zeroed entries of the specified length.
+    ///
+    /// # Safety
+    ///
+    /// `len` must be non-zero & the correct amount for the given bucket
+    unsafe fn alloc(len: usize) -> NonNull<T> {
+        // SAFETY: upheld by caller
+        unsafe { Self::try_alloc(len) }
+            .unwrap_or_else(|_| alloc::handle_alloc_error(Self::layout(len)))
+    }
+
+    /// Allocate an array of zeroed entries, failing if its layout overflows or
+    /// allocation failed
+    ///
+    /// # Safety
+    ///
+    /// See [`Bucket::alloc`]
+    unsafe fn try_alloc(len: usize) -> Result<NonNull<T>, AllocError> {
+        let layout = alloc::Layout::array::<T>(len).map_err(|_| AllocError::CapacityOverflow)?;
+        debug_assert_ne!(layout.size(), 0, "zero sized bucket");
+        // SAFETY: len soundness upheld by caller, zeroed entries are valid
+        // since T: ZeroInit
+        NonNull::new(unsafe { alloc_zeroed(layout) })
+            .map(NonNull::cast)
+            .ok_or(AllocError::Failed)
     }
 
     /// Try to dealloc this bucket, does nothing if bucket is `null`.
     ///
     /// # Safety
     ///
-    /// This bucket must be correctly allocated
-    pub unsafe fn try_dealloc(&mut self, bucket: usize) -> bool {
+    /// This bucket must be correctly allocated, with `len` entries
+    pub unsafe fn try_dealloc(&mut self, len: usize) -> bool {
         let Some(entries) = self.entries.get_mut() else {
             return false;
         };
-        let len = Location::capacity(bucket);
-        // SAFETY: entry soundness upheld by caller
-        drop(unsafe { Box::from_raw(slice::from_raw_parts_mut(entries.as_ptr(), len)) });
+        // SAFETY: entry soundness upheld by caller, the layout is the one
+        // used in Self::alloc
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(entries.as_ptr(), len));
+            alloc::dealloc(entries.as_ptr().cast(), Self::layout(len));
+        }
         true
     }
 
@@ -87,11 +135,30 @@ impl<T> Bucket<T> {
     ///
     /// # Safety
     ///
-    /// `bucket` must refer to this specific bucket
-    pub unsafe fn reserve(&self, bucket: usize) {
-        // SAFETY: bucket soundness upheld by caller
-        self.entries
-            .get_or_init(|| unsafe { Self::alloc(Location::capacity(bucket)).0 });
+    /// `len` must be the correct amount for this bucket
+    pub unsafe fn reserve(&self, len: usize) {
+        // SAFETY: len soundness upheld by caller
+        self.entries.get_or_init(|| unsafe { Self::alloc(len) });
+    }
+
+    /// Reserve space in this bucket if it is uninit, failing if it could not
+    /// be allocated
+    ///
+    /// # Safety
+    ///
+    /// `len` must be the correct amount for this bucket
+    pub unsafe fn try_reserve(&self, len: usize) -> Result<(), AllocError> {
+        if self.is_alloc() {
+            return Ok(());
+        }
+        // SAFETY: len soundness upheld by caller
+        let entries = unsafe { Self::try_alloc(len) }?;
+        if let Err(entries) = self.entries.set(entries) {
+            // SAFETY: lost the race to another thread, entries are unused &
+            // were allocated with this layout
+            unsafe { alloc::dealloc(entries.as_ptr().cast(), Self::layout(len)) };
+        }
+        Ok(())
     }
 
     /// returns `true` if this bucket is allocated
@@ -99,3 +166,76 @@ impl<T> Bucket<T> {
         self.entries.get().is_some()
     }
 }
+
+/// Allocate zeroed memory, this can be made to fail in unit tests
+///
+/// # Safety
+///
+/// See [`alloc::alloc_zeroed`]
+unsafe fn alloc_zeroed(layout: alloc::Layout) -> *mut u8 {
+    #[cfg(test)]
+    if test::should_fail() {
+        return ptr::null_mut();
+    }
+    // SAFETY: upheld by caller
+    unsafe { alloc::alloc_zeroed(layout) }
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::cell::Cell;
+    use std::sync::atomic::AtomicU8;
+    use std::sync::atomic::Ordering::Relaxed;
+
+    use super::super::raw::ArenaLayout;
+    use super::*;
+
+    thread_local! {
+        static ALLOCS_LEFT: Cell<Option<usize>> = const { Cell::new(None) };
+    }
+
+    /// Make bucket allocations on this thread fail after `n` more succeed
+    ///
+    /// `None` lets every allocation succeed again.
+    pub fn fail_allocs_after(n: Option<usize>) {
+        ALLOCS_LEFT.set(n);
+    }
+
+    pub(super) fn should_fail() -> bool {
+        match ALLOCS_LEFT.get() {
+            Some(0) => true,
+            Some(n) => {
+                ALLOCS_LEFT.set(Some(n - 1));
+                false
+            }
+            None => false,
+        }
+    }
+
+    #[repr(align(64))]
+    struct Aligned(AtomicU8);
+
+    // SAFETY: AtomicU8 is valid when zeroed
+    unsafe impl ZeroInit for Aligned {}
+
+    #[test]
+    fn aligned_buckets() {
+        for i in 0..4 {
+            let len = ArenaLayout::DEFAULT.capacity(i);
+            let mut bucket = Bucket::<Aligned>::EMPTY;
+            // SAFETY: bucket is uninit, capacity is based on i
+            unsafe { bucket.overwrite(len) };
+
+            for entry in 0..len {
+                // SAFETY: entry is within the bucket's capacity
+                let item = unsafe { bucket.get(entry) }.unwrap();
+                assert!(std::ptr::from_ref(item).is_aligned());
+                assert_eq!(item.0.load(Relaxed), 0);
+                item.0.store(1, Relaxed);
+            }
+
+            // SAFETY: bucket was allocated with len entries
+            assert!(unsafe { bucket.try_dealloc(len) });
+        }
+    }
+}
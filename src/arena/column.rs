@@ -0,0 +1,184 @@
+use std::ptr;
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicPtr;
+use std::sync::atomic::Ordering::{AcqRel, Acquire};
+
+use crate::Index;
+
+use super::bucket::{Bucket, ZeroInit};
+use super::raw::{ArenaLayout, BUCKETS, MAX_INDEX};
+use crate::error::AllocError;
+
+/// A side table holding a value for each index of an arena
+///
+/// Uses the default bucket layout whatever the arena's, so its entries never
+/// move.
+pub struct Column<V: ZeroInit> {
+    buckets: [Bucket<V>; BUCKETS],
+}
+
+impl<V: ZeroInit> Drop for Column<V> {
+    fn drop(&mut self) {
+        let buckets = Self::LAYOUT.buckets();
+        for (i, bucket) in self.buckets[..buckets].iter_mut().enumerate() {
+            // SAFETY: Column.buckets is sound, & uses the default layout
+            unsafe { bucket.try_dealloc(Self::LAYOUT.capacity(i)) };
+        }
+    }
+}
+
+impl<V: ZeroInit> Column<V> {
+    const LAYOUT: ArenaLayout = ArenaLayout::DEFAULT;
+
+    #[expect(clippy::declare_interior_mutable_const)]
+    const EMPTY: Self = Self {
+        buckets: [Bucket::EMPTY; BUCKETS],
+    };
+
+    /// Construct a new, empty, column
+    pub const fn new() -> Self {
+        Self::EMPTY
+    }
+
+    /// Get the entry at `index`, or `None` if its bucket is unallocated
+    pub fn get(&self, index: Index) -> Option<&V> {
+        let loc = Self::LAYOUT.locate(index);
+        // SAFETY: Location.bucket & Location.entry are always in bounds
+        unsafe { self.buckets.get_unchecked(loc.bucket).get(loc.entry) }
+    }
+
+    /// Get the entry at `index`, allocating its bucket if needed
+    pub fn acquire(&self, index: Index) -> &V {
+        let loc = Self::LAYOUT.locate(index);
+        // SAFETY: Location.bucket & Location.entry are always in bounds
+        unsafe {
+            self.buckets
+                .get_unchecked(loc.bucket)
+                .acquire(loc.entry, Self::LAYOUT.capacity(loc.bucket))
+        }
+    }
+
+    /// Allocate the buckets holding the first `capacity` indices
+    pub fn reserve(&self, capacity: usize) {
+        let layout = Self::LAYOUT;
+        let buckets = layout
+            .buckets_for(capacity.min(MAX_INDEX + 1))
+            .unwrap_or_else(|| layout.buckets());
+        for (i, bucket) in self.buckets[..buckets].iter().enumerate() {
+            // SAFETY: the length is that of bucket i
+            unsafe { bucket.reserve(layout.capacity(i)) };
+        }
+    }
+
+    /// Allocate the buckets holding the first `capacity` indices, stopping at
+    /// the first failure
+    pub fn try_reserve_for(&self, capacity: usize) -> Result<(), AllocError> {
+        let layout = Self::LAYOUT;
+        let buckets = layout
+            .buckets_for(capacity.min(MAX_INDEX + 1))
+            .unwrap_or_else(|| layout.buckets());
+        for (i, bucket) in self.buckets[..buckets].iter().enumerate() {
+            // SAFETY: the length is that of bucket i
+            unsafe { bucket.try_reserve(layout.capacity(i)) }?;
+        }
+        Ok(())
+    }
+
+    /// Allocate the bucket holding `index`, if it isn't already
+    pub fn try_reserve(&self, index: Index) -> Result<(), AllocError> {
+        let loc = Self::LAYOUT.locate(index);
+        // SAFETY: Location.bucket is always in bounds, & refers to this bucket
+        unsafe {
+            self.buckets
+                .get_unchecked(loc.bucket)
+                .try_reserve(Self::LAYOUT.capacity(loc.bucket))
+        }
+    }
+}
+
+/// A lazily set value, boxed so an empty entry is a null pointer
+///
+/// Each cell is keyed by the full index it was created for. A cell is never
+/// replaced, so one for an index of another generation is chained after it, &
+/// references to a value live as long as the entry.
+pub struct OnceEntry<V> {
+    cell: AtomicPtr<KeyedCell<V>>,
+}
+
+/// A cell of a [`OnceEntry`], & the next one in its slot
+struct KeyedCell<V> {
+    index: Index,
+    lock: OnceLock<V>,
+    next: OnceEntry<V>,
+}
+
+// SAFETY: a zeroed pointer is null, an empty entry
+unsafe impl<V> ZeroInit for OnceEntry<V> {}
+
+impl<V> Drop for OnceEntry<V> {
+    fn drop(&mut self) {
+        let cell = *self.cell.get_mut();
+        if !cell.is_null() {
+            // SAFETY: non-null cells always come from Box::into_raw
+            drop(unsafe { Box::from_raw(cell) });
+        }
+    }
+}
+
+impl<V> OnceEntry<V> {
+    /// Construct an empty entry
+    const fn new() -> Self {
+        Self {
+            cell: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Get the value for `index`, if it is set
+    pub fn get(&self, index: Index) -> Option<&V> {
+        let mut entry = self;
+        loop {
+            // SAFETY: non-null cells are valid until the entry is dropped
+            let cell = unsafe { entry.cell.load(Acquire).as_ref() }?;
+            if cell.index == index {
+                return cell.lock.get();
+            }
+            entry = &cell.next;
+        }
+    }
+
+    /// Get the cell for `index`, allocating it if it is missing
+    pub fn cell(&self, index: Index) -> &OnceLock<V> {
+        let mut entry = self;
+        let mut new = None;
+        loop {
+            let mut cell = entry.cell.load(Acquire);
+            if cell.is_null() {
+                let ptr = Box::into_raw(new.take().unwrap_or_else(|| {
+                    Box::new(KeyedCell {
+                        index,
+                        lock: OnceLock::new(),
+                        next: Self::new(),
+                    })
+                }));
+                match entry
+                    .cell
+                    .compare_exchange(ptr::null_mut(), ptr, AcqRel, Acquire)
+                {
+                    // SAFETY: ptr is now owned by the entry
+                    Ok(_) => return unsafe { &(*ptr).lock },
+                    Err(found) => {
+                        // SAFETY: lost the race, ptr was never shared
+                        new = Some(unsafe { Box::from_raw(ptr) });
+                        cell = found;
+                    }
+                }
+            }
+            // SAFETY: cell is non-null & valid until the entry is dropped
+            let cell = unsafe { &*cell };
+            if cell.index == index {
+                return &cell.lock;
+            }
+            entry = &cell.next;
+        }
+    }
+}
@@ -1,28 +1,70 @@
+use std::cmp::Ordering;
+use std::mem;
+use std::ops::Range;
 use std::ptr;
-use std::sync::atomic::AtomicUsize;
-use std::sync::atomic::Ordering::{Acquire, Relaxed};
+use std::sync::Arc;
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release};
+use std::sync::atomic::{AtomicU64, AtomicUsize};
 
+use crate::ChildOrder;
 use crate::Index;
 use crate::Node;
+use crate::error::PushError;
 
 use super::bucket::Bucket;
-use super::slot::Slot;
+use super::column::Column;
+use super::slot::{Contention, Slot};
+use crate::error::{AllocError, LayoutError, ReparentError, SwapError};
 
 /// The base for `slot_cap`
 pub const SLOTS: usize = usize::BITS as usize;
-/// The number of skipped slots
-pub const ZERO_SLOT: usize = SLOTS - 1;
 /// The number of skipped buckets
-pub const ZERO_BUCKET: usize = SLOTS - ZERO_SLOT.leading_zeros() as usize;
-/// The number of buckets to be used
+pub const ZERO_BUCKET: usize = SLOTS.trailing_zeros() as usize;
+/// The most buckets any layout uses, that of [`ArenaLayout::DEFAULT`]
 pub const BUCKETS: usize = SLOTS - 1 - ZERO_BUCKET;
 /// The inclusive max index(slot) able to be stored
+#[cfg(not(feature = "compact-index"))]
 pub const MAX_INDEX: usize = isize::MAX as usize - SLOTS;
+/// The inclusive max index(slot) able to be stored, leaving room for the `+ 1`
+/// kept by [`Index`]
+#[cfg(feature = "compact-index")]
+pub const MAX_INDEX: usize = u32::MAX as usize - 1;
 
+// `index` is a plain counter, all accesses are `Relaxed`, it only needs to hand
+// out unique values & nodes are published through their slot. `count` is
+// incremented with `Release` once a node is written, so an `Acquire` load that
+// sees `n` sees every one of the `n` counted nodes, as increments form a
+// release sequence. Other loads of `count` are `Relaxed`.
+//
+// Subtree counts are only ever changed by `AcqRel` RMWs. Attaching an orphan
+// sets `LINKED` on its count after setting its parent, so a push that climbs
+// to the orphan either adds to its count before the flag is set, & is carried
+// up by the attach, or sees the flag & the parent along with it, & carries
+// itself up.
 pub struct Arena<T> {
     buckets: [Bucket<Slot<T>>; BUCKETS],
+    layout: ArenaLayout,
+    order: ChildOrder,
     index: AtomicUsize,
     count: AtomicUsize,
+    /// The number of times this arena has been cleared, stamped on indices
+    generation: u32,
+    clock: Option<Clock>,
+    /// The number of nodes in each node's subtree, when kept
+    counts: Option<Column<AtomicUsize>>,
+    contention: Contention,
+}
+
+/// Set on a subtree count once it is added to the node's ancestors, which it
+/// is for every node but an unattached orphan
+const LINKED: usize = 1 << (usize::BITS - 1);
+
+/// Timestamps nodes as they are pushed
+struct Clock {
+    /// Shared with the arena's clones
+    now: Arc<dyn Fn() -> u64 + Send + Sync>,
+    /// Written before a node's slot is, & so published along with it
+    stamps: Column<AtomicU64>,
 }
 
 #[allow(clippy::non_send_fields_in_send_ty)]
@@ -33,21 +75,94 @@ unsafe impl<T: Send + Sync> Sync for Arena<T> {}
 
 impl<T> Drop for Arena<T> {
     fn drop(&mut self) {
-        debug_assert_eq!(*self.index.get_mut(), *self.count.get_mut());
+        // a push that panicked midway leaves its index unused
+        debug_assert!(*self.count.get_mut() <= *self.index.get_mut());
 
-        for (i, bucket) in self.buckets.iter_mut().enumerate() {
-            // SAFETY: Arena.buckets is sound
-            unsafe { bucket.try_dealloc(i) };
+        let layout = self.layout;
+        for (i, bucket) in self.buckets[..layout.buckets()].iter_mut().enumerate() {
+            // SAFETY: Arena.buckets is sound, & allocated with this layout
+            unsafe { bucket.try_dealloc(layout.capacity(i)) };
         }
     }
 }
 
+impl<T: Clone> Clone for Arena<T> {
+    /// Clone every node into a new arena, keeping their indices
+    ///
+    /// Links are rebuilt from the tree rather than copied, with parents
+    /// cloned before their children & siblings in the order they are linked.
+    /// Nodes pushed or attached while cloning may be left out, a node attached
+    /// to one that is left out being cloned as an orphan.
+    fn clone(&self) -> Self {
+        let end = self.watermark();
+        let mut arena = Self::with_layout(self.layout);
+        arena.order = self.order;
+        arena.generation = self.generation;
+        *arena.index.get_mut() = end;
+        // timestamps are copied over rather than taken anew
+        let stamps = Column::<AtomicU64>::new();
+        if self.counts.is_some() {
+            arena.counts = Some(Column::new());
+        }
+
+        let before_end = |node: &Node<T>| node.index().as_usize() < end;
+        // an orphan being attached can be reached both as a top & as a child
+        let mut cloned = vec![false; end];
+        let mut stack = Vec::new();
+        for top in (0..end).filter_map(|index| self.get_dense(index)) {
+            if top.parent().is_some_and(before_end) {
+                continue;
+            }
+            stack.push((top, None));
+            while let Some((node, parent)) = stack.pop() {
+                let index = node.index();
+                if mem::replace(&mut cloned[index.as_usize()], true) {
+                    continue;
+                }
+                let value = node.value.clone();
+                let parent = parent.and_then(|parent| arena.get(parent));
+                // SAFETY: each index is cloned once, `parent` is from the new
+                // arena & linked to from its slot
+                unsafe {
+                    let new = if parent.is_none() && !node.is_root() {
+                        Node::orphan(index, arena.order, value)
+                    } else {
+                        Node::new(index, arena.link(parent), arena.order, value)
+                    };
+                    arena.add_node(parent, new);
+                }
+                if let Some(clock) = &self.clock {
+                    let stamp = clock.stamps.get(index).map_or(0, |s| s.load(Relaxed));
+                    stamps.acquire(index).store(stamp, Relaxed);
+                }
+                // popped oldest first, so they are linked as they were
+                stack.extend(
+                    node.children_in(ChildOrder::NewestFirst)
+                        .filter(|&child| before_end(child))
+                        .map(|child| (child, Some(index))),
+                );
+            }
+        }
+        arena.clock = self.clock.as_ref().map(|clock| Clock {
+            now: Arc::clone(&clock.now),
+            stamps,
+        });
+        arena
+    }
+}
+
 impl<T> Arena<T> {
     #[expect(clippy::declare_interior_mutable_const)]
     const EMPTY: Self = Self {
         buckets: [Bucket::EMPTY; BUCKETS],
+        layout: ArenaLayout::DEFAULT,
+        order: ChildOrder::NewestFirst,
         index: AtomicUsize::new(0),
         count: AtomicUsize::new(0),
+        generation: 0,
+        clock: None,
+        counts: None,
+        contention: Contention::new(),
     };
 
     /// Construct a new, empty, arena
@@ -55,104 +170,684 @@ impl<T> Arena<T> {
         Self::EMPTY
     }
 
+    /// Construct a new, empty, arena with the given layout
+    pub const fn with_layout(layout: ArenaLayout) -> Self {
+        let mut arena = Self::new();
+        arena.layout = layout;
+        arena
+    }
+
+    /// The layout of this arena's buckets
+    pub const fn layout(&self) -> ArenaLayout {
+        self.layout
+    }
+
+    /// Construct a new, empty, arena linking children in the given order
+    pub const fn with_child_order(order: ChildOrder) -> Self {
+        let mut arena = Self::new();
+        arena.order = order;
+        arena
+    }
+
+    /// The order this arena links children in
+    pub const fn child_order(&self) -> ChildOrder {
+        self.order
+    }
+
     pub fn with_capacity(capacity: usize) -> Self {
-        // SAFETY: capacity is bounded to MIN_INDEX
-        let loc = unsafe { Location::new_unchecked(capacity.min(MAX_INDEX)) };
+        // nodes past MAX_INDEX can never be pushed, so there's nothing to
+        // reserve for them
+        let layout = ArenaLayout::DEFAULT;
+        let buckets = layout
+            .buckets_for(capacity)
+            .unwrap_or_else(|| layout.buckets());
 
         let mut arena = Self::new();
-        for (i, bucket) in arena.buckets[..=loc.bucket].iter_mut().enumerate() {
+        for (i, bucket) in arena.buckets[..buckets].iter_mut().enumerate() {
+            // SAFETY: bucket is uninit, capacity is based on i, which is correct
+            unsafe { bucket.overwrite(layout.capacity(i)) };
+        }
+        arena
+    }
+
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, AllocError> {
+        let layout = ArenaLayout::DEFAULT;
+        let buckets = layout
+            .buckets_for(capacity)
+            .ok_or(AllocError::CapacityOverflow)?;
+
+        let arena = Self::new();
+        for (i, bucket) in arena.buckets[..buckets].iter().enumerate() {
             // SAFETY: bucket is uninit, capacity is based on i, which is correct
-            unsafe { bucket.overwrite(Location::capacity(i)) };
+            unsafe { bucket.try_overwrite(layout.capacity(i)) }?;
         }
+        Ok(arena)
+    }
+
+    pub fn with_clock(now: Arc<dyn Fn() -> u64 + Send + Sync>) -> Self {
+        let mut arena = Self::new();
+        arena.clock = Some(Clock {
+            now,
+            stamps: Column::new(),
+        });
+        arena
+    }
+
+    pub fn with_subtree_counts() -> Self {
+        let mut arena = Self::new();
+        arena.counts = Some(Column::new());
         arena
     }
 
+    /// The number of nodes in `node`'s subtree, if counts are kept
+    pub fn subtree_len(&self, node: &Node<T>) -> Option<usize> {
+        let count = self.counts.as_ref()?.get(node.index())?;
+        Some(count.load(Acquire) & !LINKED)
+    }
+
+    /// Add `n` nodes to the subtree counts of `node` & its ancestors
+    ///
+    /// Stops at an unattached orphan, whose count is added once it is
+    /// attached.
+    fn count_up(counts: &Column<AtomicUsize>, mut node: &Node<T>, n: usize) {
+        loop {
+            let prev = counts.acquire(node.index()).fetch_add(n, AcqRel);
+            if prev & LINKED == 0 {
+                break;
+            }
+            let Some(parent) = node.parent() else {
+                break;
+            };
+            node = parent;
+        }
+    }
+
+    /// Add an orphan's subtree to its new ancestors' counts
+    ///
+    /// Should be called once `orphan` has been attached to `parent`.
+    pub fn attached(&self, parent: &Node<T>, orphan: &Node<T>) {
+        if let Some(counts) = &self.counts {
+            let n = counts.acquire(orphan.index()).fetch_or(LINKED, AcqRel);
+            Self::count_up(counts, parent, n & !LINKED);
+        }
+    }
+
+    pub fn timestamp(&self, index: Index) -> Option<u64> {
+        let clock = self.clock.as_ref()?;
+        self.get(index)?;
+        Some(clock.stamps.get(index)?.load(Relaxed))
+    }
+
+    /// The spin iterations & yields readers have waited on slots for
+    #[cfg(feature = "contention-stats")]
+    pub fn contention(&self) -> (u64, u64) {
+        self.contention.load()
+    }
+
     /// Get a node at index
     pub fn get(&self, index: Index) -> Option<&Node<T>> {
+        #[cfg(feature = "generations")]
+        if index.generation() != self.generation {
+            return None;
+        }
         // SAFETY: using loc.bucket & loc.entry always results in sound indexing
-        let loc = Location::new(index);
-        unsafe { self.bucket_at(loc).get(loc.entry) }?.get()
+        let loc = self.layout.locate(index);
+        unsafe { self.bucket_at(loc).get(loc.entry) }?.get(&self.contention)
+    }
+
+    /// Get a pointer to the value of the node at index, if it is init
+    pub fn value_ptr(&self, index: Index) -> Option<*mut T> {
+        #[cfg(feature = "generations")]
+        if index.generation() != self.generation {
+            return None;
+        }
+        // SAFETY: using loc.bucket & loc.entry always results in sound indexing
+        let loc = self.layout.locate(index);
+        unsafe { self.bucket_at(loc).get(loc.entry) }?.value_ptr()
+    }
+
+    /// Get a node at the given dense index
+    pub fn get_dense(&self, index: usize) -> Option<&Node<T>> {
+        if index > MAX_INDEX {
+            return None;
+        }
+        // SAFETY: index checked above
+        self.get(unsafe { self.index_at(index) })
+    }
+
+    /// Get the index at the given dense position, in this arena's current
+    /// generation
+    ///
+    /// # Safety
+    ///
+    /// `index` <= [`MAX_INDEX`]
+    #[inline]
+    pub const unsafe fn index_at(&self, index: usize) -> Index {
+        // SAFETY: upheld by caller
+        unsafe { Index::new_unchecked(index) }.with_generation(self.generation)
+    }
+
+    /// The number of indices handed out so far
+    pub fn watermark(&self) -> usize {
+        self.index.load(Relaxed).min(MAX_INDEX + 1)
     }
 
     /// Returns a unique index for insertion.
     fn next_index(&self) -> Index {
+        self.try_next_index().expect("capacity overflow")
+    }
+
+    /// Returns a unique index for insertion, or `None` if the arena is full.
+    fn try_next_index(&self) -> Option<Index> {
         if let index @ ..=MAX_INDEX = self.index.fetch_add(1, Relaxed) {
             // SAFETY: checked above
-            unsafe { Index::new_unchecked(index) }
+            Some(unsafe { self.index_at(index) })
         } else {
             self.index.fetch_sub(1, Relaxed);
-            panic!("capacity overflow");
+            None
         }
     }
 
     pub fn push_with(&self, parent: Option<&Node<T>>, f: impl FnOnce(Index) -> T) -> &Node<T> {
         let index = self.next_index();
         // SAFETY: Index is unique
-        unsafe { self.add_node(parent, index, f(index)) }
+        unsafe {
+            self.add_node(
+                parent,
+                Node::new(index, self.link(parent), self.order, f(index)),
+            )
+        }
+    }
+
+    /// Add a new node, giving back `value` if the arena is full or its
+    /// storage could not be allocated
+    pub fn try_push(&self, parent: Option<&Node<T>>, value: T) -> Result<&Node<T>, PushError<T>> {
+        match self.try_reserve_index() {
+            // SAFETY: the index was just reserved
+            Ok(index) => Ok(unsafe { self.push_at(index, parent, value) }),
+            Err(e) => Err(e.map(|()| value)),
+        }
     }
 
+    /// Add a new node only if its storage is already allocated, giving back
+    /// `value` otherwise
+    ///
+    /// No index is taken on failure.
+    pub fn push_within_capacity(&self, parent: Option<&Node<T>>, value: T) -> Result<&Node<T>, T> {
+        let reserved = self.index.fetch_update(Relaxed, Relaxed, |index| {
+            (index <= MAX_INDEX && self.is_allocated(index)).then_some(index + 1)
+        });
+        let Ok(index) = reserved else {
+            return Err(value);
+        };
+        // SAFETY: checked above
+        let index = unsafe { self.index_at(index) };
+        // SAFETY: the index was just taken, & its storage is allocated
+        Ok(unsafe { self.push_at(index, parent, value) })
+    }
+
+    /// returns `true` if everything needed to store a node at `index` is
+    /// allocated
+    ///
+    /// `index` must be at most [`MAX_INDEX`].
+    fn is_allocated(&self, index: usize) -> bool {
+        // SAFETY: upheld by caller
+        let index = unsafe { self.index_at(index) };
+        self.bucket_at(self.layout.locate(index)).is_alloc()
+            && (self.clock.as_ref()).is_none_or(|clock| clock.stamps.get(index).is_some())
+            && (self.counts.as_ref()).is_none_or(|counts| counts.get(index).is_some())
+    }
+
+    /// Take an index & allocate everything needed to store its node
+    ///
+    /// The index is consumed if allocation fails.
+    pub fn try_reserve_index(&self) -> Result<Index, PushError<()>> {
+        let index = self.try_next_index().ok_or(PushError::Full(()))?;
+        self.try_alloc_index(index)
+            .map_err(|_| PushError::Alloc(()))?;
+        Ok(index)
+    }
+
+    /// Write a new node to an index from [`Arena::try_reserve_index`]
+    ///
+    /// # Safety
+    ///
+    /// The index must have been taken from this arena with its storage
+    /// allocated, as by [`Arena::try_reserve_index`], & only be written once.
+    pub unsafe fn push_at(&self, index: Index, parent: Option<&Node<T>>, value: T) -> &Node<T> {
+        // SAFETY: upheld by caller
+        unsafe {
+            self.add_node(
+                parent,
+                Node::new(index, self.link(parent), self.order, value),
+            )
+        }
+    }
+
+    /// Allocate everything needed to store a node at `index`
+    fn try_alloc_index(&self, index: Index) -> Result<(), AllocError> {
+        let loc = self.layout.locate(index);
+        // SAFETY: same index used = same bucket
+        unsafe {
+            self.bucket_at(loc)
+                .try_reserve(self.layout.capacity(loc.bucket))
+        }?;
+        if let Some(clock) = &self.clock {
+            clock.stamps.try_reserve(index)?;
+        }
+        if let Some(counts) = &self.counts {
+            counts.try_reserve(index)?;
+        }
+        Ok(())
+    }
+
+    pub fn push_orphan(&self, value: T) -> &Node<T> {
+        let index = self.next_index();
+        // SAFETY: Index is unique
+        unsafe { self.add_node(None, Node::orphan(index, self.order, value)) }
+    }
+
+    /// Add every value, returning the range of indices they were written to
+    ///
+    /// All values are written before returning.
     pub fn push_all(
         &self,
         parent: Option<&Node<T>>,
         values: impl ExactSizeIterator<Item = T>,
-    ) -> impl ExactSizeIterator<Item = &Node<T>> {
-        let origin = self
-            .index
+    ) -> Range<usize> {
+        let origin = self.reserve_range(values.len()).expect("capacity overflow");
+        // SAFETY: the range was just reserved
+        unsafe { self.write_all(origin, parent, values) }
+    }
+
+    /// Add every value, giving them back if the arena can't fit them all or
+    /// their storage could not be allocated
+    ///
+    /// The indices are consumed if allocation fails.
+    pub fn try_push_all(
+        &self,
+        parent: Option<&Node<T>>,
+        values: Vec<T>,
+    ) -> Result<Range<usize>, PushError<Vec<T>>> {
+        let Some(origin) = self.reserve_range(values.len()) else {
+            return Err(PushError::Full(values));
+        };
+        for index in origin..origin + values.len() {
+            // SAFETY: the index was just reserved
+            let index = unsafe { self.index_at(index) };
+            if self.try_alloc_index(index).is_err() {
+                return Err(PushError::Alloc(values));
+            }
+        }
+        // SAFETY: the range was just reserved
+        Ok(unsafe { self.write_all(origin, parent, values.into_iter()) })
+    }
+
+    /// Take `len` contiguous indices, returning the first, or `None` if the
+    /// arena can't fit them
+    fn reserve_range(&self, len: usize) -> Option<usize> {
+        self.index
             .fetch_update(Relaxed, Relaxed, |index| {
-                index.checked_add(values.len()).filter(|&n| n <= MAX_INDEX)
+                index.checked_add(len).filter(|&n| n <= MAX_INDEX + 1)
             })
-            .expect("capacity overflow");
-        let len = values.len();
+            .ok()
+    }
 
-        values.enumerate().map(move |(i, value)| {
-            assert!(i < len, "iterator returned extra value");
+    /// Write every value from `origin` on
+    ///
+    /// # Safety
+    ///
+    /// The indices must come from [`Arena::reserve_range`], with room for
+    /// every value.
+    unsafe fn write_all(
+        &self,
+        origin: usize,
+        parent: Option<&Node<T>>,
+        values: impl ExactSizeIterator<Item = T>,
+    ) -> Range<usize> {
+        let len = values.len();
+        let mut written = 0;
+        for value in values {
+            assert!(written < len, "iterator returned extra value");
             // SAFETY: index is unique & checked above
-            unsafe { self.add_node(parent, Index::new_unchecked(origin + i), value) }
-        })
+            unsafe {
+                let index = self.index_at(origin + written);
+                self.add_node(
+                    parent,
+                    Node::new(index, self.link(parent), self.order, value),
+                );
+            }
+            written += 1;
+        }
+        origin..origin + written
+    }
+
+    /// Write a node at the given index, under `parent` or as an orphan,
+    /// failing if its storage could not be allocated
+    ///
+    /// # Safety
+    ///
+    /// No node may have been written to `index`, nor can any push be ongoing.
+    /// `parent` must be from this arena.
+    #[cfg(feature = "serde")]
+    pub unsafe fn try_insert_at(
+        &self,
+        index: Index,
+        parent: Option<&Node<T>>,
+        orphan: bool,
+        value: T,
+    ) -> Result<&Node<T>, AllocError> {
+        self.try_alloc_index(index)?;
+        self.index.fetch_max(index.as_usize() + 1, Relaxed);
+        let node = if orphan {
+            Node::orphan(index, self.order, value)
+        } else {
+            // SAFETY: upheld by caller
+            unsafe { Node::new(index, self.link(parent), self.order, value) }
+        };
+        // SAFETY: upheld by caller, its storage is allocated
+        Ok(unsafe { self.add_node(parent, node) })
     }
 
     /// add a new node
     ///
     /// # Safety
     ///
-    /// Index must be unique, `parent` must be from this arena
+    /// The node's index must be unique, `parent` must be from this arena &
+    /// match the one the node was created with
     #[inline]
-    unsafe fn add_node(&self, parent: Option<&Node<T>>, index: Index, value: T) -> &Node<T> {
-        let loc = Location::new(index);
+    unsafe fn add_node(&self, parent: Option<&Node<T>>, node: Node<T>) -> &Node<T> {
+        let loc = self.layout.locate(node.index());
+        if let Some(clock) = &self.clock {
+            clock
+                .stamps
+                .acquire(node.index())
+                .store((clock.now)(), Relaxed);
+        }
+        if let Some(counts) = &self.counts {
+            let count = if node.is_orphan() { 1 } else { 1 | LINKED };
+            counts.acquire(node.index()).store(count, Relaxed);
+        }
         // SAFETY: index is unique
-        let node = unsafe {
-            self.bucket_at(loc)
-                .acquire(loc)
-                .write(Node::new(index, parent, value), parent)
-        };
+        let node = unsafe { self.acquire(loc).write(node, parent) };
+        if let (Some(counts), Some(parent)) = (&self.counts, parent) {
+            Self::count_up(counts, parent, 1);
+        }
 
-        self.count.fetch_add(1, Relaxed);
+        self.count.fetch_add(1, Release);
         node
     }
 
-    pub fn reserve(&self, additional: usize) {
-        let index = self
-            .count
-            .load(Acquire)
-            .saturating_add(additional)
-            .min(MAX_INDEX);
-        // SAFETY: index checked above
-        let mut loc = unsafe { Location::new_unchecked(index) };
-        while !self.bucket_at(loc).is_alloc() {
-            // SAFETY: same index used = same bucket
-            unsafe { self.bucket_at(loc).reserve(loc.bucket) };
-            if loc.bucket == 0 {
-                break;
+    /// Map every node into a new arena, keeping their indices & links
+    ///
+    /// Values are mapped in index order.
+    pub fn map<U>(mut self, mut f: impl FnMut(T) -> U) -> Arena<U> {
+        let len = self.watermark();
+        let mut arena = Arena::with_layout(self.layout);
+        arena.order = self.order;
+        arena.generation = self.generation;
+        *arena.index.get_mut() = len;
+        arena.clock = self.clock.take();
+        arena.counts = self.counts.take();
+
+        // SAFETY: links only point to nodes within this arena, whose memory is
+        // kept until it is dropped
+        let relink = |node: *mut Node<T>| arena.node_ptr(unsafe { (*node).index() });
+        for index in 0..len {
+            // SAFETY: index is below the watermark
+            let index = unsafe { self.index_at(index) };
+            // SAFETY: nodes are only read from after being taken
+            let Some(node) = (unsafe { self.take(index) }) else {
+                continue;
+            };
+            // SAFETY: relink gives each node's counterpart in the new arena
+            let node = unsafe { node.map(relink, &mut f) };
+            // SAFETY: index is unique, the node is already linked
+            unsafe { arena.acquire(arena.layout.locate(index)).write(node, None) };
+            arena.count.fetch_add(1, Relaxed);
+        }
+        arena
+    }
+
+    /// Unlink the subtree rooted at the node at `index` & drop its nodes,
+    /// giving how many there were
+    ///
+    /// Their slots are left uninitialized, as tombstones, since indices are
+    /// never handed out twice.
+    pub fn remove_subtree(&mut self, index: Index) -> usize {
+        let Some(root) = self.get(index) else {
+            return 0;
+        };
+        let indices: Vec<_> = root.descendants().map(Node::index).collect();
+        self.detach(root, indices.len());
+        for &index in &indices {
+            // SAFETY: the subtree is unlinked, so its nodes can't be reached
+            drop(unsafe { self.take(index) });
+        }
+        *self.count.get_mut() -= indices.len();
+        indices.len()
+    }
+
+    /// Move the subtree rooted at the node at `index` into a new arena
+    ///
+    /// The nodes are pushed in pre-order, with children in insertion order,
+    /// so `index`'s node ends up at index `0`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no node at `index`
+    pub fn split_off_subtree(&mut self, index: Index) -> Self {
+        let root = self.get(index).expect("no node at index");
+        // each node along with where its parent is in the list, newest first
+        // being the reverse of the order they are pushed in
+        let mut nodes = Vec::new();
+        let mut stack = vec![(root, None)];
+        while let Some((node, parent)) = stack.pop() {
+            let at = nodes.len();
+            nodes.push((node.index(), parent));
+            stack.extend(
+                node.children_in(ChildOrder::NewestFirst)
+                    .map(|child| (child, Some(at))),
+            );
+        }
+        self.detach(root, nodes.len());
+
+        let mut arena = Self::with_layout(self.layout);
+        arena.order = self.order;
+        if self.counts.is_some() {
+            arena.counts = Some(Column::new());
+        }
+        for &(index, parent) in &nodes {
+            // SAFETY: the subtree is unlinked, so its nodes can't be reached
+            let node = unsafe { self.take(index) }.expect("subtree nodes are initialized");
+            let parent = parent.and_then(|parent| arena.get_dense(parent));
+            arena.push_with(parent, |_| node.value);
+        }
+        *self.count.get_mut() -= nodes.len();
+        arena
+    }
+
+    /// Unlink `root` from its parent, taking its subtree of `len` nodes away
+    /// from the counts above it
+    fn detach(&self, root: &Node<T>, len: usize) {
+        // SAFETY: only called with self borrowed mutably, so nothing else is
+        // using the tree
+        unsafe { root.unlink() };
+        if let (Some(counts), Some(parent)) = (&self.counts, root.parent()) {
+            // adding wraps around, so this takes the subtree's nodes away
+            Self::count_up(counts, parent, len.wrapping_neg());
+        }
+    }
+
+    /// Move the node at `node` under the node at `parent`
+    pub fn reparent(&mut self, node: Index, parent: Index) -> Result<(), ReparentError> {
+        let child = self.get(node).ok_or(ReparentError::Missing(node))?;
+        let new_parent = self.get(parent).ok_or(ReparentError::Missing(parent))?;
+        if ptr::eq(child, new_parent) || new_parent.ancestors().any(|n| ptr::eq(n, child)) {
+            return Err(ReparentError::Cycle { node, parent });
+        }
+        let moved = self.counts.as_ref().map(|counts| {
+            let count = counts.acquire(node).fetch_or(LINKED, Relaxed);
+            if let (true, Some(old_parent)) = (count & LINKED != 0, child.parent()) {
+                // adding wraps around, so this takes the subtree's nodes away
+                Self::count_up(counts, old_parent, (count & !LINKED).wrapping_neg());
+            }
+            count & !LINKED
+        });
+        // SAFETY: both are in this arena, the parent isn't in the node's
+        // subtree, & self is borrowed mutably
+        unsafe { Node::move_to(self.link(Some(child)), self.link(Some(new_parent))) };
+        if let (Some(counts), Some(moved)) = (&self.counts, moved) {
+            Self::count_up(counts, new_parent, moved);
+        }
+        Ok(())
+    }
+
+    /// Exchange the places of the nodes at `a` & `b` in the tree
+    pub fn swap_subtrees(&mut self, a: Index, b: Index) -> Result<(), SwapError> {
+        let a_node = self.get(a).ok_or(SwapError::Missing(a))?;
+        let b_node = self.get(b).ok_or(SwapError::Missing(b))?;
+        if ptr::eq(a_node, b_node) {
+            return Ok(());
+        }
+        for (up, down) in [(a_node, b_node), (b_node, a_node)] {
+            if down.ancestors().any(|n| ptr::eq(n, up)) {
+                return Err(SwapError::Nested {
+                    ancestor: up.index(),
+                    descendant: down.index(),
+                });
+            }
+        }
+        if let Some(counts) = &self.counts {
+            let (a_count, b_count) = (counts.acquire(a), counts.acquire(b));
+            let (a_len, b_len) = (a_count.load(Relaxed), b_count.load(Relaxed));
+            // each takes the other's place, along with whether its ancestors
+            // count it
+            a_count.store((a_len & !LINKED) | (b_len & LINKED), Relaxed);
+            b_count.store((b_len & !LINKED) | (a_len & LINKED), Relaxed);
+            let (a_len, b_len) = (a_len & !LINKED, b_len & !LINKED);
+            // adding wraps around, so this can take nodes away
+            if let Some(parent) = a_node.parent() {
+                Self::count_up(counts, parent, b_len.wrapping_sub(a_len));
+            }
+            if let Some(parent) = b_node.parent() {
+                Self::count_up(counts, parent, a_len.wrapping_sub(b_len));
             }
-            loc.bucket -= 1;
         }
+        // SAFETY: both are in this arena, neither is in the other's subtree,
+        // & self is borrowed mutably
+        unsafe { Node::swap(self.link(Some(a_node)), self.link(Some(b_node))) };
+        Ok(())
+    }
+
+    /// Sort the children of the node at `index`
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no node at `index`
+    // borrowed mutably so no other thread can see the links change
+    #[expect(clippy::needless_pass_by_ref_mut)]
+    pub fn sort_children_by(
+        &mut self,
+        index: Index,
+        cmp: impl FnMut(&Node<T>, &Node<T>) -> Ordering,
+    ) {
+        let node = self.get(index).expect("no node at index");
+        // SAFETY: self is borrowed mutably, so nothing else is using the tree
+        unsafe { node.sort_children_by(cmp) };
+    }
+
+    /// Drop every node, keeping the buckets allocated, & start handing out
+    /// indices from `0` again, in the next generation
+    pub fn clear(&mut self) {
+        for index in 0..self.watermark() {
+            // SAFETY: index is below the watermark
+            let index = unsafe { self.index_at(index) };
+            // SAFETY: every node is taken, so none are reached through links
+            drop(unsafe { self.take(index) });
+        }
+        *self.index.get_mut() = 0;
+        *self.count.get_mut() = 0;
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// Move the node at `index` out, leaving its slot uninitialized
+    ///
+    /// # Safety
+    ///
+    /// Links to the node are left in place, so nodes must no longer be
+    /// reached through links, only read from after being taken.
+    pub unsafe fn take(&mut self, index: Index) -> Option<Node<T>> {
+        let loc = self.layout.locate(index);
+        // SAFETY: loc is valid for its bucket, self is borrowed mutably so no
+        // other thread can access the slot
+        unsafe { self.bucket_at(loc).get(loc.entry)?.take() }
+    }
+
+    /// Get a pointer to link to `node` by, or null
+    ///
+    /// Links are always taken from the node's slot, so they stay usable after
+    /// its value is borrowed mutably.
+    pub fn link(&self, node: Option<&Node<T>>) -> *mut Node<T> {
+        node.map_or(ptr::null_mut(), |node| self.node_ptr(node.index()))
+    }
+
+    /// Get where the node at `index` is, or will be, stored
+    fn node_ptr(&self, index: Index) -> *mut Node<T> {
+        let loc = self.layout.locate(index);
+        // SAFETY: loc is valid for its bucket
+        unsafe { self.acquire(loc) }.node_ptr()
+    }
+
+    /// Reserve storage for the next `additional` nodes, side tables included
+    ///
+    /// Nodes are placed at the watermark rather than after the live ones, so
+    /// this counts from there.
+    pub fn reserve(&self, additional: usize) {
+        let total = self.watermark().saturating_add(additional);
+        if let Some(clock) = &self.clock {
+            clock.stamps.reserve(total);
+        }
+        if let Some(counts) = &self.counts {
+            counts.reserve(total);
+        }
+        let buckets = self
+            .layout
+            .buckets_for(total.min(MAX_INDEX + 1))
+            .unwrap_or_else(|| self.layout.buckets());
+        for (i, bucket) in self.buckets[..buckets].iter().enumerate() {
+            // SAFETY: the length is that of bucket i
+            unsafe { bucket.reserve(self.layout.capacity(i)) };
+        }
+    }
+
+    pub fn try_reserve(&self, additional: usize) -> Result<(), AllocError> {
+        let total = self
+            .watermark()
+            .checked_add(additional)
+            .ok_or(AllocError::CapacityOverflow)?;
+        let buckets = self
+            .layout
+            .buckets_for(total)
+            .ok_or(AllocError::CapacityOverflow)?;
+        if let Some(clock) = &self.clock {
+            clock.stamps.try_reserve_for(total)?;
+        }
+        if let Some(counts) = &self.counts {
+            counts.try_reserve_for(total)?;
+        }
+        for (i, bucket) in self.buckets[..buckets].iter().enumerate() {
+            // SAFETY: the length is that of bucket i
+            unsafe { bucket.try_reserve(self.layout.capacity(i)) }?;
+        }
+        Ok(())
     }
 
     pub fn capacity(&self) -> usize {
         let mut total = 0;
-        for bucket in 0..BUCKETS {
+        for bucket in 0..self.layout.buckets() {
             if self.buckets[bucket].is_alloc() {
-                total += Location::capacity(bucket);
+                total += self.layout.capacity(bucket);
             }
         }
         total
@@ -167,6 +862,24 @@ impl<T> Arena<T> {
         self.count.load(Relaxed)
     }
 
+    pub fn count_acquire(&self) -> usize {
+        self.count.load(Acquire)
+    }
+
+    /// Get the slot at the given `Location`, allocating its bucket if needed
+    ///
+    /// # Safety
+    ///
+    /// `loc` must come from this arena's layout
+    #[inline]
+    unsafe fn acquire(&self, loc: Location) -> &Slot<T> {
+        // SAFETY: upheld by caller
+        unsafe {
+            self.bucket_at(loc)
+                .acquire(loc.entry, self.layout.capacity(loc.bucket))
+        }
+    }
+
     /// Get the bucket at the given `Location`
     ///
     /// This is safe since `Location.bucket` is always within bounds
@@ -186,37 +899,155 @@ pub struct Location {
     pub entry: usize,
 }
 
-impl Location {
-    /// Create a new location without checking
+/// How an arena's buckets are sized
+///
+/// The first bucket holds `first_bucket` nodes, & each bucket after it holds
+/// `growth` times as many as the one before, until every possible index is
+/// covered. Both are powers of two, checked by
+/// [`ArenaLayout::new`].
+///
+/// The default starts at `usize::BITS` nodes & doubles. Larger values trade
+/// memory held upfront for fewer, larger allocations, useful when a huge
+/// number of nodes is known to be coming.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArenaLayout {
+    /// log2 of the first bucket's capacity
+    shift: u32,
+    /// log2 of the growth factor
+    growth: u32,
+}
+
+impl Default for ArenaLayout {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl ArenaLayout {
+    /// The layout used by every constructor but [`Arena::with_layout`]
+    ///
+    /// [`Arena::with_layout`]: crate::Arena::with_layout
+    pub const DEFAULT: Self = Self {
+        shift: SLOTS.trailing_zeros(),
+        growth: 1,
+    };
+
+    /// The largest first bucket allowed
+    const MAX_FIRST_BUCKET: usize = 1 << (usize::BITS - 2);
+
+    /// Describe a layout
+    ///
+    /// # Errors
+    ///
+    /// `first_bucket` must be a power of two between `usize::BITS` &
+    /// `2^(usize::BITS - 2)`, `growth` must be a power of two between `2` &
+    /// `2^(usize::BITS - 2)`.
+    pub const fn new(first_bucket: usize, growth: usize) -> Result<Self, LayoutError> {
+        if !first_bucket.is_power_of_two()
+            || first_bucket < SLOTS
+            || first_bucket > Self::MAX_FIRST_BUCKET
+        {
+            return Err(LayoutError::FirstBucket(first_bucket));
+        }
+        if !growth.is_power_of_two() || growth < 2 || growth > Self::MAX_FIRST_BUCKET {
+            return Err(LayoutError::Growth(growth));
+        }
+        Ok(Self {
+            shift: first_bucket.trailing_zeros(),
+            growth: growth.trailing_zeros(),
+        })
+    }
+
+    /// The number of nodes held by the first bucket
+    pub const fn first_bucket(self) -> usize {
+        1 << self.shift
+    }
+
+    /// How many times larger each bucket is than the one before it
+    pub const fn growth(self) -> usize {
+        1 << self.growth
+    }
+
+    /// The number of buckets needed to cover every index
+    pub const fn buckets(self) -> usize {
+        // SAFETY: MAX_INDEX is in bounds
+        unsafe { self.locate_unchecked(MAX_INDEX) }.bucket + 1
+    }
+
+    /// The number of nodes the given bucket holds
+    ///
+    /// The last bucket is cut short at the largest possible index, so the
+    /// capacities of all [`ArenaLayout::buckets`] always sum to the most nodes
+    /// an arena can hold.
+    ///
+    /// `bucket` must be below [`ArenaLayout::buckets`].
+    pub const fn capacity(self, bucket: usize) -> usize {
+        let rest = MAX_INDEX + 1 - self.start(bucket);
+        // never overflows, as the bucket starts at or below MAX_INDEX
+        let full = 1_u128 << (self.shift as usize + self.growth as usize * bucket);
+        if full < rest as u128 {
+            full as usize
+        } else {
+            rest
+        }
+    }
+
+    /// The first index held by the given bucket
+    ///
+    /// The bucket must start at or below [`MAX_INDEX`].
+    #[inline]
+    const fn start(self, bucket: usize) -> usize {
+        if self.growth == 1 {
+            return (self.first_bucket() << bucket) - self.first_bucket();
+        }
+        // first * (growth^bucket - 1) / (growth - 1)
+        let scaled = ((1_u128 << (self.growth as usize * bucket)) - 1) / ((1 << self.growth) - 1);
+        (scaled << self.shift) as usize
+    }
+
+    /// Find where `index` is stored
     ///
     /// # Safety
     ///
     /// `index` <= [`MAX_INDEX`]
     #[inline]
-    pub const unsafe fn new_unchecked(index: usize) -> Self {
+    pub(crate) const unsafe fn locate_unchecked(self, index: usize) -> Location {
         debug_assert!(index <= MAX_INDEX);
-        let index = index + ZERO_SLOT;
-        let bucket = Self::bucket(index);
-        let entry = index - (Self::capacity(bucket) - 1);
-        Self { bucket, entry }
+        let bucket = if self.growth == 1 {
+            // the first bucket is at most a quarter of the index space, so
+            // this can't overflow
+            (index + self.first_bucket()).ilog2() - self.shift
+        } else {
+            // the bucket b holding index is the largest where
+            // growth^b <= index * (growth - 1) / first + 1
+            let scaled = ((index as u128 * ((1 << self.growth) - 1)) >> self.shift) + 1;
+            scaled.ilog2() / self.growth
+        } as usize;
+        Location {
+            bucket,
+            entry: index - self.start(bucket),
+        }
     }
 
+    /// Find where `index` is stored
     #[inline]
-    pub const fn new(index: Index) -> Self {
+    pub(crate) const fn locate(self, index: Index) -> Location {
         // SAFETY: Index is always <= MAX_INDEX
-        unsafe { Self::new_unchecked(index.get()) }
-    }
-
-    /// The bucket `index` - [`ZERO_SLOT`] belongs to
-    #[inline]
-    pub const fn bucket(index: usize) -> usize {
-        BUCKETS - (index + 1).leading_zeros() as usize
+        unsafe { self.locate_unchecked(index.get()) }
     }
 
-    /// The number of slots in the given bucket
-    #[inline]
-    pub const fn capacity(bucket: usize) -> usize {
-        1 << (bucket + ZERO_BUCKET)
+    /// The number of buckets needed to hold `capacity` nodes
+    ///
+    /// Returns `None` if `capacity` is more than the arena can ever hold
+    pub(super) const fn buckets_for(self, capacity: usize) -> Option<usize> {
+        if capacity == 0 {
+            Some(0)
+        } else if capacity <= MAX_INDEX + 1 {
+            // SAFETY: capacity - 1 <= MAX_INDEX
+            Some(unsafe { self.locate_unchecked(capacity - 1) }.bucket + 1)
+        } else {
+            None
+        }
     }
 }
 
@@ -224,52 +1055,171 @@ impl Location {
 mod test {
     use super::*;
 
-    impl From<usize> for Location {
-        fn from(index: usize) -> Self {
-            assert!(index <= MAX_INDEX, "index out of bounds");
-            // SAFETY: index checked abvoe
-            Location::new(unsafe { Index::new_unchecked(index) })
-        }
+    /// Layouts covering the edges of what [`ArenaLayout::new`] allows
+    fn layouts() -> [ArenaLayout; 6] {
+        let max = ArenaLayout::MAX_FIRST_BUCKET;
+        [
+            ArenaLayout::DEFAULT,
+            ArenaLayout::new(SLOTS, 4).unwrap(),
+            ArenaLayout::new(1 << 10, 2).unwrap(),
+            ArenaLayout::new(1 << 20, 16).unwrap(),
+            ArenaLayout::new(max, 2).unwrap(),
+            ArenaLayout::new(SLOTS, max).unwrap(),
+        ]
+    }
+
+    fn locate(layout: ArenaLayout, index: usize) -> (usize, usize) {
+        assert!(index <= MAX_INDEX, "index out of bounds");
+        // SAFETY: index checked above
+        let loc = unsafe { layout.locate_unchecked(index) };
+        (loc.bucket, loc.entry)
     }
 
     #[test]
     fn location() {
-        assert_eq!(Location::capacity(0), SLOTS);
+        let layout = ArenaLayout::DEFAULT;
+        assert_eq!(layout, ArenaLayout::new(SLOTS, 2).unwrap());
+        assert_eq!(layout.capacity(0), SLOTS);
 
         for i in 0..SLOTS {
-            let loc = Location::from(i);
-            assert_eq!(loc.bucket, 0);
-            assert_eq!(loc.entry, i);
+            assert_eq!(locate(layout, i), (0, i));
         }
 
-        assert_eq!(Location::capacity(1), SLOTS * 2);
+        assert_eq!(layout.capacity(1), SLOTS * 2);
 
         for i in SLOTS..SLOTS * 3 {
-            let loc = Location::from(i);
-            assert_eq!(loc.bucket, 1);
-            assert_eq!(loc.entry, i - SLOTS);
+            assert_eq!(locate(layout, i), (1, i - SLOTS));
         }
 
-        assert_eq!(Location::capacity(2), SLOTS * 4);
+        assert_eq!(layout.capacity(2), SLOTS * 4);
 
         for i in SLOTS * 3..SLOTS * 7 {
-            let loc = Location::from(i);
-            assert_eq!(loc.bucket, 2);
-            assert_eq!(loc.entry, i - SLOTS * 3);
+            assert_eq!(locate(layout, i), (2, i - SLOTS * 3));
+        }
+    }
+
+    #[test]
+    fn location_growth() {
+        let layout = ArenaLayout::new(SLOTS, 4).unwrap();
+        let mut start = 0;
+        for bucket in 0..4 {
+            let len = layout.capacity(bucket);
+            assert_eq!(len, SLOTS << (2 * bucket));
+            for i in start..start + len {
+                assert_eq!(locate(layout, i), (bucket, i - start));
+            }
+            start += len;
         }
     }
 
     #[test]
     fn max_entries() {
-        let mut slots = 0;
-        for i in 0..BUCKETS {
-            slots += Location::capacity(i);
+        for layout in layouts() {
+            assert!(layout.buckets() <= BUCKETS, "{layout:?}");
+
+            let mut slots = 0;
+            for i in 0..layout.buckets() {
+                let len = layout.capacity(i);
+                // every bucket starts right after the last
+                assert_eq!(locate(layout, slots), (i, 0), "{layout:?}");
+                assert_eq!(locate(layout, slots + len - 1), (i, len - 1), "{layout:?}");
+                slots += len;
+            }
+
+            assert_eq!(slots, MAX_INDEX + 1, "{layout:?}");
+        }
+
+        #[cfg(not(feature = "compact-index"))]
+        {
+            let max = locate(ArenaLayout::DEFAULT, MAX_INDEX);
+            assert_eq!(max, (BUCKETS - 1, (1 << (usize::BITS - 2)) - 1));
+        }
+    }
+
+    #[test]
+    fn invalid_layout() {
+        let max = ArenaLayout::MAX_FIRST_BUCKET;
+        for size in [0, 1, SLOTS / 2, SLOTS + 1, max * 2, usize::MAX] {
+            let err = ArenaLayout::new(size, 2);
+            assert_eq!(err, Err(LayoutError::FirstBucket(size)));
         }
+        for growth in [0, 1, 3, max * 2, usize::MAX] {
+            let err = ArenaLayout::new(SLOTS, growth);
+            assert_eq!(err, Err(LayoutError::Growth(growth)));
+        }
+    }
+
+    #[test]
+    fn buckets_for() {
+        let layout = ArenaLayout::DEFAULT;
+        assert_eq!(layout.buckets_for(0), Some(0));
+        assert_eq!(layout.buckets_for(1), Some(1));
+        assert_eq!(layout.buckets_for(SLOTS), Some(1));
+        assert_eq!(layout.buckets_for(SLOTS + 1), Some(2));
+        assert_eq!(layout.buckets_for(MAX_INDEX), Some(layout.buckets()));
+        assert_eq!(layout.buckets_for(MAX_INDEX + 1), Some(layout.buckets()));
+        assert_eq!(layout.buckets_for(MAX_INDEX + 2), None);
+        #[cfg(not(feature = "compact-index"))]
+        assert_eq!(layout.buckets(), BUCKETS);
+
+        // with compact indices, the largest first bucket covers every index
+        for layout in layouts()
+            .into_iter()
+            .filter(|l| l.first_bucket() <= MAX_INDEX)
+        {
+            assert_eq!(layout.buckets_for(MAX_INDEX + 1), Some(layout.buckets()));
+            assert_eq!(layout.buckets_for(layout.first_bucket()), Some(1));
+            assert_eq!(layout.buckets_for(layout.first_bucket() + 1), Some(2));
+        }
+    }
+
+    #[test]
+    fn alloc_failure() {
+        use super::super::bucket::test::fail_allocs_after;
 
-        assert_eq!(slots, MAX_INDEX + 1);
+        // the second bucket fails
+        fail_allocs_after(Some(1));
+        assert!(Arena::<usize>::try_with_capacity(SLOTS * 3).is_err());
+
+        // the largest capacities are only refused by the allocator
+        for capacity in [MAX_INDEX, MAX_INDEX + 1] {
+            fail_allocs_after(Some(0));
+            let err = Arena::<usize>::try_with_capacity(capacity).err();
+            assert_eq!(err, Some(AllocError::Failed));
+        }
+
+        fail_allocs_after(Some(1));
+        let arena = Arena::try_with_capacity(SLOTS - 1).unwrap();
+        assert_eq!(arena.capacity(), SLOTS);
+        assert!(arena.try_reserve(SLOTS * 2).is_err());
+
+        for i in 0..SLOTS {
+            arena.try_push(None, i).unwrap();
+        }
+        let err = arena.try_push(None, SLOTS).unwrap_err();
+        assert!(matches!(err, PushError::Alloc(SLOTS)));
+        let err = arena.try_push_all(None, vec![1, 2]).unwrap_err();
+        assert!(matches!(err, PushError::Alloc(ref values) if values == &[1, 2]));
+        assert!(matches!(
+            arena.try_reserve_index(),
+            Err(PushError::Alloc(()))
+        ));
+        // nothing past the index space is taken
+        assert!(arena.reserve_range(MAX_INDEX).is_none());
+        let empty = arena.try_push_all(None, Vec::new()).unwrap();
+        assert_eq!(empty, SLOTS + 4..SLOTS + 4);
+
+        // the arena is still usable within its capacity
+        assert_eq!(arena.count(), SLOTS);
+        assert_eq!(arena.capacity(), SLOTS);
+        for i in 0..SLOTS {
+            assert_eq!(arena.get_dense(i).unwrap().value, i);
+        }
 
-        let max = Location::from(MAX_INDEX);
-        assert_eq!(max.bucket, BUCKETS - 1);
-        assert_eq!(max.entry, (1 << (usize::BITS - 2)) - 1);
+        fail_allocs_after(None);
+        arena.try_reserve(SLOTS).unwrap();
+        assert_eq!(arena.capacity(), SLOTS * 3);
+        let root = arena.get_dense(0);
+        assert_eq!(arena.try_push(root, 0).unwrap().index().get(), SLOTS + 4);
     }
 }
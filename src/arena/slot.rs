@@ -1,17 +1,87 @@
 use std::cell::UnsafeCell;
 use std::mem::MaybeUninit;
 use std::sync::atomic::AtomicU8;
-use std::sync::atomic::Ordering::{Acquire, Release};
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use std::thread;
 
+use super::bucket::ZeroInit;
 use crate::Node;
 
 // NOTE: could move uninit to node.value
 
+// # Synchronization
+//
+// A slot is written exactly once, by the thread that reserved its index, so
+// writers never contend on `state`:
+//
+// - `Uninit -> Middle` is `Relaxed`. Nothing is published by it, readers that
+//   observe `Middle` only wait for `Active`.
+// - `Middle -> Active` is `Release`, publishing the node (& its links) to any
+//   reader that loads `Active` with `Acquire`.
+//
+// A node can become reachable through its parent before it is `Active`, see
+// `Node::add_child` for that half of the protocol.
+// # Backoff
+//
+// A writer only holds a slot in `Middle` while linking it to its parent, a
+// handful of instructions, so readers first spin & expect to see it finish.
+// Each spin step waits twice as long as the last, up to `SPIN_STEPS` steps.
+// Past that the writer has most likely been descheduled, so readers yield
+// their thread to let it run instead of burning a core.
+
+/// The number of doubling spin steps before readers start yielding
+const SPIN_STEPS: u32 = 6;
+
+/// Counts the time readers spend waiting on slots
+///
+/// Only counted with the `contention-stats` feature, otherwise this is empty.
+#[derive(Debug, Default)]
+pub struct Contention {
+    #[cfg(feature = "contention-stats")]
+    spins: std::sync::atomic::AtomicU64,
+    #[cfg(feature = "contention-stats")]
+    yields: std::sync::atomic::AtomicU64,
+}
+
+impl Contention {
+    pub const fn new() -> Self {
+        Self {
+            #[cfg(feature = "contention-stats")]
+            spins: std::sync::atomic::AtomicU64::new(0),
+            #[cfg(feature = "contention-stats")]
+            yields: std::sync::atomic::AtomicU64::new(0),
+        }
+    }
+
+    #[cfg(feature = "contention-stats")]
+    fn record(&self, spins: u64, yields: u64) {
+        self.spins.fetch_add(spins, Relaxed);
+        self.yields.fetch_add(yields, Relaxed);
+    }
+
+    #[cfg(not(feature = "contention-stats"))]
+    #[expect(clippy::unused_self)]
+    const fn record(&self, _: u64, _: u64) {}
+
+    /// The spin iterations & yields recorded so far
+    #[cfg(feature = "contention-stats")]
+    pub fn load(&self) -> (u64, u64) {
+        (self.spins.load(Relaxed), self.yields.load(Relaxed))
+    }
+}
+
 pub struct Slot<T> {
     state: AtomicU8,
     slot: UnsafeCell<MaybeUninit<Node<T>>>,
 }
 
+// a zeroed state must always read as uninit
+const _: () = assert!(State::Uninit as u8 == 0);
+
+// SAFETY: a zeroed `state` is `State::Uninit`, meaning `slot` is never read or
+// dropped, and `MaybeUninit` tolerates any bytes
+unsafe impl<T> ZeroInit for Slot<T> {}
+
 impl<T> Drop for Slot<T> {
     fn drop(&mut self) {
         if matches!(self.state_mut(), State::Active) {
@@ -23,9 +93,13 @@ impl<T> Drop for Slot<T> {
 
 impl<T> Slot<T> {
     /// get the node if it is init
-    pub fn get(&self) -> Option<&Node<T>> {
+    ///
+    /// Waits for the node if it is being written, recording the wait in
+    /// `contention`
+    pub fn get(&self, contention: &Contention) -> Option<&Node<T>> {
         // SAFETY: state is checked
-        self.acquire().then(|| unsafe { self.get_unchecked() })
+        self.acquire(contention)
+            .then(|| unsafe { self.get_unchecked() })
     }
 
     /// gets slot
@@ -45,37 +119,77 @@ impl<T> Slot<T> {
     /// The slot must be uninitialized, `parent` should be from the arena
     /// this slot belongs to
     pub unsafe fn write(&self, node: Node<T>, parent: Option<&crate::Node<T>>) -> &Node<T> {
-        self.state.store(State::Middle as u8, Release); //could be relaxed
+        self.state.store(State::Middle as u8, Relaxed);
         // SAFETY: upheld by caller
         unsafe { (*self.slot.get()).write(node) };
         if let Some(parent) = parent {
-            unsafe { parent.add_child(UnsafeCell::raw_get(&raw const self.slot).cast()) };
+            // SAFETY: upheld by caller, node is new & pointed to from its slot
+            unsafe { parent.add_child(self.node_ptr()) };
         }
         self.state.store(State::Active as u8, Release);
-
         // SAFETY: has been init above
         unsafe { self.get_unchecked() }
     }
 
-    fn acquire(&self) -> bool {
+    /// A pointer to where this slot's node is, or will be, stored
+    pub const fn node_ptr(&self) -> *mut Node<T> {
+        self.slot.get().cast()
+    }
+
+    /// A pointer to the node's value, if it is init
+    ///
+    /// Writing through it must not race with any other use of the value.
+    pub fn value_ptr(&self) -> Option<*mut T> {
+        // SAFETY: the node is init, so its value can be pointed to
+        matches!(self.state(), State::Active).then(|| unsafe { &raw mut (*self.node_ptr()).value })
+    }
+
+    /// move the node out, leaving the slot uninitialized
+    ///
+    /// # Safety
+    ///
+    /// The slot must not be accessed by any other thread. Pointers to the
+    /// node may still be read from, but its value must not be used.
+    pub unsafe fn take(&self) -> Option<Node<T>> {
+        if !matches!(self.state(), State::Active) {
+            return None;
+        }
+        self.state.store(State::Uninit as u8, Relaxed);
+        // SAFETY: slot was init, & is now marked uninit so won't be dropped
+        Some(unsafe { (*self.slot.get()).assume_init_read() })
+    }
+
+    fn acquire(&self, contention: &Contention) -> bool {
         match self.state() {
             State::Uninit => false,
-            State::Middle => self.spin(),
+            State::Middle => self.wait(contention),
             State::Active => true,
         }
     }
 
+    /// Wait for the slot to leave `Middle`, backing off as described above
     #[cold]
-    fn spin(&self) -> bool {
-        // maybe should use exponential backoff
-        loop {
-            // could use a relaxed ordering here, confirming with Acquire
+    fn wait(&self, contention: &Contention) -> bool {
+        let (mut step, mut spins, mut yields) = (0, 0, 0);
+        let active = loop {
             match self.state() {
                 State::Uninit => break false,
-                State::Middle => std::hint::spin_loop(),
+                State::Middle => {}
                 State::Active => break true,
             }
-        }
+            if step < SPIN_STEPS {
+                for _ in 0..1 << step {
+                    std::hint::spin_loop();
+                }
+                spins += 1 << step;
+                step += 1;
+            } else {
+                thread::yield_now();
+                yields += 1;
+            }
+        };
+        contention.record(spins, yields);
+        active
     }
 
     fn state(&self) -> State {
@@ -107,3 +221,49 @@ impl From<u8> for State {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::ptr;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{ChildOrder, Index};
+
+    struct Stalled(Slot<i32>);
+
+    // SAFETY: the slot is only written by one thread, as the arena does
+    unsafe impl Sync for Stalled {}
+
+    #[test]
+    fn stalled_writer() {
+        let stalled = &Stalled(Slot {
+            state: AtomicU8::new(State::Middle as u8),
+            slot: UnsafeCell::new(MaybeUninit::uninit()),
+        });
+        let (slot, contention) = (&stalled.0, &Contention::new());
+
+        thread::scope(|s| {
+            let reader = s.spawn(move || stalled.0.get(contention).map(|node| node.value));
+            thread::sleep(Duration::from_millis(20));
+            // SAFETY: the slot is uninit, & only the reader is waiting on it
+            unsafe {
+                let node = Node::new(
+                    Index::new_unchecked(0),
+                    ptr::null_mut(),
+                    ChildOrder::NewestFirst,
+                    7,
+                );
+                (*slot.slot.get()).write(node);
+            }
+            slot.state.store(State::Active as u8, Release);
+            assert_eq!(reader.join().unwrap(), Some(7));
+        });
+
+        #[cfg(feature = "contention-stats")]
+        {
+            let (spins, yields) = contention.load();
+            assert!(spins > 0 && yields > 0);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::fmt;
+use std::iter::FusedIterator;
+
+use super::{Arena, Nodes, Roots};
+use crate::{Index, Next, Node};
+
+/// A point-in-time view of an arena
+///
+/// Created by [`Arena::snapshot`]. Every traversal through a snapshot skips
+/// the nodes whose index was handed out after it was taken, so repeated
+/// traversals see the same nodes while pushes continue.
+///
+/// Nodes that were still being written when the snapshot was taken show up
+/// once they are written, and orphans attached afterwards are still
+/// reachable from their new parent.
+pub struct ArenaSnapshot<'a, T> {
+    arena: &'a Arena<T>,
+    watermark: usize,
+}
+
+impl<T> fmt::Debug for ArenaSnapshot<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArenaSnapshot")
+            .field("watermark", &self.watermark)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Clone for ArenaSnapshot<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ArenaSnapshot<'_, T> {}
+
+impl<'a, T> ArenaSnapshot<'a, T> {
+    pub(super) fn new(arena: &'a Arena<T>) -> Self {
+        Self {
+            arena,
+            watermark: arena.raw.watermark(),
+        }
+    }
+
+    /// The number of indices handed out when this snapshot was taken
+    pub const fn watermark(&self) -> usize {
+        self.watermark
+    }
+
+    /// Get the underlying arena
+    pub const fn arena(&self) -> &'a Arena<T> {
+        self.arena
+    }
+
+    /// returns `true` if `node` belongs to the arena & predates this snapshot
+    pub fn contains(&self, node: &Node<T>) -> bool {
+        node.index().as_usize() < self.watermark && self.arena.contains(node)
+    }
+
+    /// Get the node of the given [`Index`], if it predates this snapshot
+    pub fn get(&self, index: Index) -> Option<&'a Node<T>> {
+        if index.as_usize() < self.watermark {
+            self.arena.get(index)
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the nodes in this snapshot, in index order
+    pub fn iter(&self) -> Nodes<'a, T> {
+        self.arena.iter_range(..self.watermark)
+    }
+
+    /// Iterate over the roots in this snapshot, in index order
+    pub fn roots(&self) -> Roots<'a, T> {
+        Roots(self.iter())
+    }
+
+    /// Iterate over the children of `node` in this snapshot
+    pub fn children(&self, node: &'a Node<T>) -> SnapshotChildren<'a, T> {
+        SnapshotChildren {
+            children: node.children(),
+            watermark: self.watermark,
+        }
+    }
+
+    /// Iterate over the subtree rooted at `node` in pre-order, starting with
+    /// `node`
+    pub const fn descendants(&self, node: &'a Node<T>) -> SnapshotDescendants<'a, T> {
+        SnapshotDescendants {
+            root: Some(node),
+            stack: Vec::new(),
+            watermark: self.watermark,
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &ArenaSnapshot<'a, T> {
+    type Item = &'a Node<T>;
+    type IntoIter = Nodes<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterates over the children of a node in an [`ArenaSnapshot`]
+///
+/// Created by [`ArenaSnapshot::children`]
+#[derive(Debug)]
+pub struct SnapshotChildren<'a, T> {
+    children: Next<'a, T>,
+    watermark: usize,
+}
+
+impl<T> Clone for SnapshotChildren<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            children: self.children.clone(),
+            watermark: self.watermark,
+        }
+    }
+}
+
+impl<'a, T> Iterator for SnapshotChildren<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // links aren't in index order, so later nodes can be anywhere
+        let watermark = self.watermark;
+        self.children
+            .find(|node| node.index().as_usize() < watermark)
+    }
+}
+
+impl<T> FusedIterator for SnapshotChildren<'_, T> {}
+
+/// Iterates over a subtree of an [`ArenaSnapshot`] in pre-order
+///
+/// Created by [`ArenaSnapshot::descendants`]
+#[derive(Debug)]
+pub struct SnapshotDescendants<'a, T> {
+    root: Option<&'a Node<T>>,
+    /// The children left to visit at each level
+    stack: Vec<SnapshotChildren<'a, T>>,
+    watermark: usize,
+}
+
+impl<T> Clone for SnapshotDescendants<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root,
+            stack: self.stack.clone(),
+            watermark: self.watermark,
+        }
+    }
+}
+
+impl<'a, T> Iterator for SnapshotDescendants<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = match self.root.take() {
+            Some(root) => root,
+            None => loop {
+                let children = self.stack.last_mut()?;
+                if let Some(node) = children.next() {
+                    break node;
+                }
+                self.stack.pop();
+            },
+        };
+        self.stack.push(SnapshotChildren {
+            children: node.children(),
+            watermark: self.watermark,
+        });
+        Some(node)
+    }
+}
+
+impl<T> FusedIterator for SnapshotDescendants<'_, T> {}
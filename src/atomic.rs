@@ -0,0 +1,265 @@
+//! Values that can be updated without locking while their arena is shared
+
+use std::fmt;
+use std::sync::atomic::Ordering::{self, AcqRel, Acquire, Release};
+use std::sync::atomic::{
+    AtomicBool, AtomicI8, AtomicI16, AtomicI32, AtomicIsize, AtomicU8, AtomicU16, AtomicU32,
+    AtomicUsize,
+};
+#[cfg(target_has_atomic = "64")]
+use std::sync::atomic::{AtomicI64, AtomicU64};
+
+use crate::{Arena, AsParent, Node};
+
+mod sealed {
+    use std::sync::atomic::Ordering;
+
+    /// The std atomic backing a value, & the operations used on it
+    pub trait Sealed: Copy {
+        type Cell: Send + Sync;
+
+        fn new_cell(value: Self) -> Self::Cell;
+        fn load(cell: &Self::Cell, order: Ordering) -> Self;
+        fn store(cell: &Self::Cell, value: Self, order: Ordering);
+        fn swap(cell: &Self::Cell, value: Self, order: Ordering) -> Self;
+        fn compare_exchange_weak(
+            cell: &Self::Cell,
+            current: Self,
+            new: Self,
+            success: Ordering,
+            failure: Ordering,
+        ) -> Result<Self, Self>;
+        fn into_inner(cell: Self::Cell) -> Self;
+    }
+}
+
+/// A value that fits in one of the std atomics, see [`Atomic`]
+///
+/// Implemented for the primitive integers, `bool`, `f32` & `f64`, where the
+/// target supports atomics of their size.
+pub trait AtomicValue: sealed::Sealed {}
+
+macro_rules! atomic_value {
+    ($($(#[$meta:meta])* $ty:ty => $cell:ty),* $(,)?) => {$(
+        $(#[$meta])*
+        impl sealed::Sealed for $ty {
+            type Cell = $cell;
+
+            fn new_cell(value: Self) -> Self::Cell {
+                <$cell>::new(value)
+            }
+
+            fn load(cell: &Self::Cell, order: Ordering) -> Self {
+                cell.load(order)
+            }
+
+            fn store(cell: &Self::Cell, value: Self, order: Ordering) {
+                cell.store(value, order);
+            }
+
+            fn swap(cell: &Self::Cell, value: Self, order: Ordering) -> Self {
+                cell.swap(value, order)
+            }
+
+            fn compare_exchange_weak(
+                cell: &Self::Cell,
+                current: Self,
+                new: Self,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<Self, Self> {
+                cell.compare_exchange_weak(current, new, success, failure)
+            }
+
+            fn into_inner(cell: Self::Cell) -> Self {
+                cell.into_inner()
+            }
+        }
+
+        $(#[$meta])*
+        impl AtomicValue for $ty {}
+    )*};
+}
+
+atomic_value! {
+    bool => AtomicBool,
+    u8 => AtomicU8,
+    u16 => AtomicU16,
+    u32 => AtomicU32,
+    #[cfg(target_has_atomic = "64")]
+    u64 => AtomicU64,
+    usize => AtomicUsize,
+    i8 => AtomicI8,
+    i16 => AtomicI16,
+    i32 => AtomicI32,
+    #[cfg(target_has_atomic = "64")]
+    i64 => AtomicI64,
+    isize => AtomicIsize,
+}
+
+/// Floats are stored as their bits, & compared by them
+macro_rules! atomic_float {
+    ($($(#[$meta:meta])* $ty:ty => $cell:ty),* $(,)?) => {$(
+        $(#[$meta])*
+        impl sealed::Sealed for $ty {
+            type Cell = $cell;
+
+            fn new_cell(value: Self) -> Self::Cell {
+                <$cell>::new(value.to_bits())
+            }
+
+            fn load(cell: &Self::Cell, order: Ordering) -> Self {
+                <$ty>::from_bits(cell.load(order))
+            }
+
+            fn store(cell: &Self::Cell, value: Self, order: Ordering) {
+                cell.store(value.to_bits(), order);
+            }
+
+            fn swap(cell: &Self::Cell, value: Self, order: Ordering) -> Self {
+                <$ty>::from_bits(cell.swap(value.to_bits(), order))
+            }
+
+            fn compare_exchange_weak(
+                cell: &Self::Cell,
+                current: Self,
+                new: Self,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<Self, Self> {
+                cell.compare_exchange_weak(current.to_bits(), new.to_bits(), success, failure)
+                    .map(<$ty>::from_bits)
+                    .map_err(<$ty>::from_bits)
+            }
+
+            fn into_inner(cell: Self::Cell) -> Self {
+                <$ty>::from_bits(cell.into_inner())
+            }
+        }
+
+        $(#[$meta])*
+        impl AtomicValue for $ty {}
+    )*};
+}
+
+atomic_float! {
+    f32 => AtomicU32,
+    #[cfg(target_has_atomic = "64")]
+    f64 => AtomicU64,
+}
+
+/// A value updated in place through atomic operations
+///
+/// Like [`Locked`](crate::Locked), this lets a node's value change while the
+/// arena is shared, but without blocking, for values small enough to fit in
+/// an atomic. Loads are `Acquire`, stores `Release` & read-modify-writes
+/// `AcqRel`, so a value stored is published along with what was written
+/// before it.
+///
+/// ```
+/// # use silva::Arena;
+/// let arena = Arena::new();
+/// let root = arena.push_atomic(None, 0u32);
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| root.fetch_update(|n| n.checked_add(1)));
+///     }
+/// });
+/// assert_eq!(root.load(), 4);
+/// ```
+pub struct Atomic<T: AtomicValue>(<T as sealed::Sealed>::Cell);
+
+impl<T: AtomicValue + fmt::Debug> fmt::Debug for Atomic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Atomic").field(&self.load()).finish()
+    }
+}
+
+impl<T: AtomicValue + Default> Default for Atomic<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: AtomicValue> From<T> for Atomic<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: AtomicValue> Atomic<T> {
+    /// Store `value` in a new cell
+    pub fn new(value: T) -> Self {
+        Self(T::new_cell(value))
+    }
+
+    /// Get the current value
+    pub fn load(&self) -> T {
+        T::load(&self.0, Acquire)
+    }
+
+    /// Replace the value
+    pub fn store(&self, value: T) {
+        T::store(&self.0, value, Release);
+    }
+
+    /// Replace the value, giving the one it replaced
+    pub fn swap(&self, value: T) -> T {
+        T::swap(&self.0, value, AcqRel)
+    }
+
+    /// Replace the value with the one given by `f`, giving the one replaced
+    ///
+    /// `f` may be called more than once if the value changes meanwhile.
+    ///
+    /// # Errors
+    ///
+    /// Fails with the current value if `f` returns `None`, leaving it as is
+    pub fn fetch_update(&self, mut f: impl FnMut(T) -> Option<T>) -> Result<T, T> {
+        let mut prev = self.load();
+        while let Some(next) = f(prev) {
+            match T::compare_exchange_weak(&self.0, prev, next, AcqRel, Acquire) {
+                Ok(prev) => return Ok(prev),
+                Err(actual) => prev = actual,
+            }
+        }
+        Err(prev)
+    }
+
+    /// Take the value out of the cell
+    pub fn into_inner(self) -> T {
+        T::into_inner(self.0)
+    }
+}
+
+impl<T: AtomicValue> Node<Atomic<T>> {
+    /// Get this node's value, see [`Atomic::load`]
+    pub fn load(&self) -> T {
+        self.value.load()
+    }
+
+    /// Replace this node's value, see [`Atomic::store`]
+    pub fn store(&self, value: T) {
+        self.value.store(value);
+    }
+
+    /// Update this node's value using `f`, see [`Atomic::fetch_update`]
+    ///
+    /// # Errors
+    ///
+    /// Fails with the current value if `f` returns `None`
+    pub fn fetch_update(&self, f: impl FnMut(T) -> Option<T>) -> Result<T, T> {
+        self.value.fetch_update(f)
+    }
+}
+
+impl<T: AtomicValue> Arena<Atomic<T>> {
+    /// Add a new node, storing `value` in an atomic cell
+    ///
+    /// # Panics
+    ///
+    /// panics if the arena is full or the value is rejected by its push check
+    pub fn push_atomic(&self, parent: impl AsParent<Atomic<T>>, value: T) -> &Node<Atomic<T>> {
+        self.push(parent, Atomic::new(value))
+    }
+}
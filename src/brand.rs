@@ -0,0 +1,155 @@
+//! Indices tied to their arena at compile time
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::{Arena, Index, Node};
+
+/// An invariant lifetime, so no two brands can be unified
+type Brand<'id> = PhantomData<fn(&'id ()) -> &'id ()>;
+
+/// An [`Index`] that can only be used with the [`BrandedArena`] it came from
+///
+/// The `'id` lifetime is unique to each call of [`Arena::branded`], so using
+/// an index with another arena is caught at compile time.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BrandedIndex<'id> {
+    index: Index,
+    brand: Brand<'id>,
+}
+
+impl fmt::Debug for BrandedIndex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BrandedIndex")
+            .field(&self.index.as_usize())
+            .finish()
+    }
+}
+
+impl fmt::Display for BrandedIndex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.index.fmt(f)
+    }
+}
+
+impl BrandedIndex<'_> {
+    /// Get the underlying index
+    pub const fn index(self) -> Index {
+        self.index
+    }
+}
+
+impl From<BrandedIndex<'_>> for Index {
+    fn from(index: BrandedIndex<'_>) -> Self {
+        index.index
+    }
+}
+
+/// An [`Arena`] borrowed under a unique brand, see [`Arena::branded`]
+///
+/// Each of its indices is known to point to one of its nodes, so lookups
+/// never fail.
+pub struct BrandedArena<'a, 'id, T> {
+    arena: &'a Arena<T>,
+    brand: Brand<'id>,
+}
+
+impl<T> fmt::Debug for BrandedArena<'_, '_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BrandedArena")
+            .field("count", &self.arena.count())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Clone for BrandedArena<'_, '_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for BrandedArena<'_, '_, T> {}
+
+impl<'id, T> std::ops::Index<BrandedIndex<'id>> for BrandedArena<'_, 'id, T> {
+    type Output = Node<T>;
+
+    #[inline]
+    fn index(&self, index: BrandedIndex<'id>) -> &Self::Output {
+        &self.arena[index.index]
+    }
+}
+
+impl<'a, 'id, T> BrandedArena<'a, 'id, T> {
+    const fn brand(index: Index) -> BrandedIndex<'id> {
+        BrandedIndex {
+            index,
+            brand: PhantomData,
+        }
+    }
+
+    /// Add a new node, returning its index
+    ///
+    /// # Panics
+    ///
+    /// See [`Arena::push`]
+    pub fn push(&self, parent: Option<BrandedIndex<'id>>, value: T) -> BrandedIndex<'id> {
+        let parent = parent.map(BrandedIndex::index);
+        Self::brand(self.arena.push(parent, value).index())
+    }
+
+    /// Get the node at `index`
+    pub fn get(&self, index: BrandedIndex<'id>) -> &'a Node<T> {
+        &self.arena[index.index]
+    }
+
+    /// Brand an index, if it points to a node of this arena
+    pub fn index(&self, index: Index) -> Option<BrandedIndex<'id>> {
+        self.arena.get(index).map(|_| Self::brand(index))
+    }
+
+    /// Get the index of a node, if it belongs to this arena
+    pub fn index_of(&self, node: &Node<T>) -> Option<BrandedIndex<'id>> {
+        self.arena.contains(node).then(|| Self::brand(node.index()))
+    }
+
+    /// Get the underlying arena
+    pub const fn arena(&self) -> &'a Arena<T> {
+        self.arena
+    }
+}
+
+impl<T> Arena<T> {
+    /// Run `f` with this arena under a brand no other arena shares
+    ///
+    /// Indices given out by the [`BrandedArena`] can't leave `f`, & can't be
+    /// used with another branded arena.
+    ///
+    /// ```rust
+    /// # use silva::Arena;
+    /// let arena = Arena::new();
+    /// arena.branded(|ast| {
+    ///     let root = ast.push(None, "fn");
+    ///     let name = ast.push(Some(root), "main");
+    ///     assert_eq!(ast[name].parent().unwrap().value, "fn");
+    /// });
+    /// ```
+    ///
+    /// Indices from one brand can't be used with another:
+    ///
+    /// ```rust,compile_fail
+    /// # use silva::Arena;
+    /// let (ast, types) = (Arena::new(), Arena::new());
+    /// ast.branded(|ast| {
+    ///     types.branded(|types| {
+    ///         let root = ast.push(None, "fn");
+    ///         types.push(Some(root), "i32");
+    ///     });
+    /// });
+    /// ```
+    pub fn branded<R>(&self, f: impl for<'id> FnOnce(BrandedArena<'_, 'id, T>) -> R) -> R {
+        f(BrandedArena {
+            arena: self,
+            brand: PhantomData,
+        })
+    }
+}
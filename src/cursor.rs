@@ -0,0 +1,89 @@
+//! Navigating a tree one step at a time
+
+use std::ptr;
+
+use crate::{ChildOrder, Node};
+
+/// A position within a tree, moved between neighbouring nodes
+///
+/// Siblings are in the order of [`Node::children`], so by default the first
+/// child is the newest. Every move returns `false` & leaves the cursor where
+/// it was if there is no node to move to.
+#[derive(Debug)]
+pub struct Cursor<'a, T> {
+    node: &'a Node<T>,
+}
+
+impl<T> Clone for Cursor<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Cursor<'_, T> {}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Create a cursor at `node`
+    pub const fn new(node: &'a Node<T>) -> Self {
+        Self { node }
+    }
+
+    /// Get the node the cursor is at
+    pub const fn node(&self) -> &'a Node<T> {
+        self.node
+    }
+
+    /// Move to `node`, if there is one
+    fn goto(&mut self, node: Option<&'a Node<T>>) -> bool {
+        node.map(|node| self.node = node).is_some()
+    }
+
+    /// Move to the parent of the current node
+    pub fn goto_parent(&mut self) -> bool {
+        self.goto(self.node.parent())
+    }
+
+    /// Move to the root of the current node's tree
+    ///
+    /// Under an unattached orphan, that is the orphan. Returns `false` if the
+    /// cursor is already there.
+    pub fn goto_root(&mut self) -> bool {
+        self.goto(self.node.ancestors().last())
+    }
+
+    /// Move to the first child of the current node
+    pub fn goto_first_child(&mut self) -> bool {
+        self.goto(self.node.child())
+    }
+
+    /// Move to the last child of the current node
+    ///
+    /// This is `O(1)`, see [`Node::first_child`] & [`Node::last_child`].
+    pub fn goto_last_child(&mut self) -> bool {
+        let last = match self.node.child_order() {
+            ChildOrder::NewestFirst => self.node.first_child(),
+            ChildOrder::InsertionOrder => self.node.last_child(),
+        };
+        self.goto(last)
+    }
+
+    /// Move to the sibling after the current node
+    pub fn goto_next_sibling(&mut self) -> bool {
+        self.goto(self.node.next())
+    }
+
+    /// Move to the sibling before the current node
+    ///
+    /// Siblings are only linked forwards, so this walks the siblings before
+    /// the current node.
+    pub fn goto_prev_sibling(&mut self) -> bool {
+        let Some(parent) = self.node.parent() else {
+            return false;
+        };
+        let prev = parent
+            .children()
+            .take_while(|&sibling| !ptr::eq(sibling, self.node))
+            .last();
+        self.goto(prev)
+    }
+}
@@ -0,0 +1,376 @@
+//! The errors returned by fallible operations
+//!
+//! Every error implements [`std::error::Error`], so they can all be passed up
+//! with `?`. They also convert into [`Error`], for code that calls several
+//! fallible operations & wants a single error type.
+//!
+//! Every error is `#[non_exhaustive]`, so variants & fields can be added
+//! without breaking matches on them.
+
+use std::error::Error as StdError;
+use std::fmt;
+
+use crate::Index;
+#[cfg(doc)]
+use crate::{Arena, ArenaLayout, AsParent};
+
+/// A boxed error produced by a push check
+pub type CheckError = Box<dyn StdError + Send + Sync>;
+
+/// The error returned by [`Arena::attach`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AttachError {
+    /// The node is not an orphan, or has already been attached
+    NotOrphan(Index),
+    /// The parent is the orphan itself or one of its descendants
+    Cycle {
+        /// The node the orphan was to be attached to
+        parent: Index,
+        /// The orphan
+        orphan: Index,
+    },
+}
+
+impl fmt::Display for AttachError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotOrphan(index) => write!(f, "node {index} is not an unattached orphan"),
+            Self::Cycle { parent, orphan } => write!(
+                f,
+                "attaching orphan {orphan} to node {parent} would create a cycle"
+            ),
+        }
+    }
+}
+
+impl StdError for AttachError {}
+
+/// The error returned by [`Arena::reparent`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReparentError {
+    /// There is no node at the index, it was removed or never pushed
+    Missing(Index),
+    /// The new parent is the node itself or one of its descendants
+    Cycle {
+        /// The node being moved
+        node: Index,
+        /// The node it was to be moved under
+        parent: Index,
+    },
+}
+
+impl fmt::Display for ReparentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(index) => write!(f, "no node at index {index}"),
+            Self::Cycle { node, parent } => write!(
+                f,
+                "moving node {node} under node {parent} would create a cycle"
+            ),
+        }
+    }
+}
+
+impl StdError for ReparentError {}
+
+/// The error returned by [`Arena::swap_subtrees`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SwapError {
+    /// There is no node at the index, it was removed or never pushed
+    Missing(Index),
+    /// One node is in the other's subtree
+    Nested {
+        /// The node whose subtree holds the other
+        ancestor: Index,
+        /// The node in the other's subtree
+        descendant: Index,
+    },
+}
+
+impl fmt::Display for SwapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(index) => write!(f, "no node at index {index}"),
+            Self::Nested {
+                ancestor,
+                descendant,
+            } => write!(
+                f,
+                "can't swap node {ancestor} with its descendant {descendant}"
+            ),
+        }
+    }
+}
+
+impl StdError for SwapError {}
+
+/// The error returned by [`Arena::try_push`] & its variants, holding what was
+/// not pushed
+#[non_exhaustive]
+pub enum PushError<T> {
+    /// The arena has run out of indices
+    Full(T),
+    /// The value was rejected by the arena's push check
+    Rejected(CheckError, T),
+    /// The arena's storage could not be allocated
+    Alloc(T),
+    /// The parent does not belong to the arena
+    Foreign(ForeignNodeError, T),
+}
+
+impl<T> PushError<T> {
+    /// Get back the value that was not pushed
+    pub fn into_value(self) -> T {
+        match self {
+            Self::Full(value)
+            | Self::Rejected(_, value)
+            | Self::Alloc(value)
+            | Self::Foreign(_, value) => value,
+        }
+    }
+
+    /// Map the value that was not pushed, keeping the reason
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> PushError<U> {
+        match self {
+            Self::Full(value) => PushError::Full(f(value)),
+            Self::Rejected(e, value) => PushError::Rejected(e, f(value)),
+            Self::Alloc(value) => PushError::Alloc(f(value)),
+            Self::Foreign(e, value) => PushError::Foreign(e, f(value)),
+        }
+    }
+}
+
+impl<T> fmt::Debug for PushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full(_) => f.write_str("Full(..)"),
+            Self::Alloc(_) => f.write_str("Alloc(..)"),
+            Self::Rejected(e, _) => f.debug_tuple("Rejected").field(e).finish_non_exhaustive(),
+            Self::Foreign(e, _) => f.debug_tuple("Foreign").field(e).finish_non_exhaustive(),
+        }
+    }
+}
+
+impl<T> fmt::Display for PushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Full(_) => f.write_str("capacity overflow"),
+            Self::Alloc(_) => AllocError::Failed.fmt(f),
+            Self::Rejected(e, _) => write!(f, "value rejected: {e}"),
+            Self::Foreign(e, _) => write!(f, "invalid parent: {e}"),
+        }
+    }
+}
+
+impl<T> StdError for PushError<T> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::Full(_) | Self::Alloc(_) => None,
+            Self::Rejected(e, _) => Some(&**e),
+            Self::Foreign(e, _) => Some(e),
+        }
+    }
+}
+
+/// The error returned when an arena's storage could not be allocated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AllocError {
+    /// More capacity was requested than an arena can ever hold
+    CapacityOverflow,
+    /// The allocator failed
+    Failed,
+}
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::CapacityOverflow => "capacity overflow",
+            Self::Failed => "memory allocation failed",
+        })
+    }
+}
+
+impl StdError for AllocError {}
+
+/// The error returned by [`ArenaLayout::new`], holding the invalid parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LayoutError {
+    /// The first bucket's size is out of range or not a power of two
+    FirstBucket(usize),
+    /// The growth factor is out of range or not a power of two
+    Growth(usize),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FirstBucket(size) => write!(f, "invalid first bucket size {size}"),
+            Self::Growth(growth) => write!(f, "invalid growth factor {growth}"),
+        }
+    }
+}
+
+impl StdError for LayoutError {}
+
+/// The error returned when a parent does not belong to an arena
+///
+/// See [`AsParent::try_get`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ForeignNodeError {
+    index: Index,
+    pending: bool,
+}
+
+impl ForeignNodeError {
+    /// Construct an error for the node at `index`
+    pub(crate) const fn new(index: Index, pending: bool) -> Self {
+        Self { index, pending }
+    }
+
+    /// Get the index of the node that was not found
+    pub const fn index(&self) -> Index {
+        self.index
+    }
+
+    /// returns `true` if the index was handed out by the arena, but its node
+    /// is not written
+    ///
+    /// Either its push is still ongoing, or it failed.
+    pub const fn is_pending(&self) -> bool {
+        self.pending
+    }
+}
+
+impl fmt::Display for ForeignNodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pending {
+            write!(
+                f,
+                "node has not been written yet, its push is ongoing or failed (index {})",
+                self.index
+            )
+        } else {
+            write!(
+                f,
+                "node does not belong to this arena (index {})",
+                self.index
+            )
+        }
+    }
+}
+
+impl StdError for ForeignNodeError {}
+
+/// The error returned by [`Arena::from_depth_stream`] when an item is more
+/// than one level deeper than the one before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DepthError {
+    /// The item's position in the stream
+    pub position: usize,
+    /// The item's depth
+    pub depth: usize,
+    /// The deepest the item could have been, one below the previous item
+    pub max_depth: usize,
+}
+
+impl fmt::Display for DepthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "item {} is at depth {}, but can be at most {} deep",
+            self.position, self.depth, self.max_depth
+        )
+    }
+}
+
+impl StdError for DepthError {}
+
+/// Any of the errors in this module
+///
+/// Each error converts into this with `?`, a [`PushError`] dropping the value
+/// it held. Both [`Display`](fmt::Display) & [`source`](StdError::source)
+/// are forwarded to the inner error.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// See [`AttachError`]
+    Attach(AttachError),
+    /// See [`ReparentError`]
+    Reparent(ReparentError),
+    /// See [`SwapError`]
+    Swap(SwapError),
+    /// See [`PushError`], without the value that was not pushed
+    Push(PushError<()>),
+    /// See [`AllocError`]
+    Alloc(AllocError),
+    /// See [`LayoutError`]
+    Layout(LayoutError),
+    /// See [`ForeignNodeError`]
+    ForeignNode(ForeignNodeError),
+    /// See [`DepthError`]
+    Depth(DepthError),
+}
+
+impl Error {
+    /// Get the inner error
+    fn inner(&self) -> &(dyn StdError + 'static) {
+        match self {
+            Self::Attach(e) => e,
+            Self::Reparent(e) => e,
+            Self::Swap(e) => e,
+            Self::Push(e) => e,
+            Self::Alloc(e) => e,
+            Self::Layout(e) => e,
+            Self::ForeignNode(e) => e,
+            Self::Depth(e) => e,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.inner(), f)
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.inner().source()
+    }
+}
+
+impl<T> From<PushError<T>> for Error {
+    fn from(e: PushError<T>) -> Self {
+        Self::Push(e.map(drop))
+    }
+}
+
+/// Implement `From` for each error wrapped by [`Error`] as is
+macro_rules! from_errors {
+    ($($variant:ident($error:ty)),* $(,)?) => {
+        $(
+            impl From<$error> for Error {
+                fn from(e: $error) -> Self {
+                    Self::$variant(e)
+                }
+            }
+        )*
+    };
+}
+
+from_errors!(
+    Attach(AttachError),
+    Reparent(ReparentError),
+    Swap(SwapError),
+    Alloc(AllocError),
+    Layout(LayoutError),
+    ForeignNode(ForeignNodeError),
+    Depth(DepthError),
+);
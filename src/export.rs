@@ -0,0 +1,297 @@
+//! Writing trees out in other formats
+
+use std::fmt::{self, Display, Write};
+use std::io;
+
+use crate::{Arena, ChildOrder, Node};
+
+/// Write the subtree rooted at `root` as nested markup
+///
+/// `open` is called on a node before its children & `close` after them, so
+/// the calls nest like tags. Children are written in insertion order, using
+/// an explicit stack, so deep trees can't overflow the stack.
+///
+/// # Errors
+///
+/// Stops at the first error returned by `open` or `close`
+pub fn write_nested<T>(
+    root: &Node<T>,
+    w: &mut impl io::Write,
+    mut open: impl FnMut(&Node<T>, &mut dyn io::Write) -> io::Result<()>,
+    mut close: impl FnMut(&Node<T>, &mut dyn io::Write) -> io::Result<()>,
+) -> io::Result<()> {
+    /// Work left to do, in reverse
+    enum Step<'a, T> {
+        Open(&'a Node<T>),
+        Close(&'a Node<T>),
+    }
+
+    let mut stack = vec![Step::Open(root)];
+    while let Some(step) = stack.pop() {
+        match step {
+            Step::Open(node) => {
+                open(node, w)?;
+                stack.push(Step::Close(node));
+                // pushed newest first, so the oldest is popped first
+                stack.extend(node.children_in(ChildOrder::NewestFirst).map(Step::Open));
+            }
+            Step::Close(node) => close(node, w)?,
+        }
+    }
+    Ok(())
+}
+
+/// Write the subtree rooted at `root` as XML
+///
+/// Each node becomes a `<node value="...">` element holding its children,
+/// with the value's `&<>"'` escaped. No whitespace is added between elements.
+///
+/// # Errors
+///
+/// Fails if writing to `w` fails
+pub fn write_xml<T: Display>(root: &Node<T>, w: &mut impl io::Write) -> io::Result<()> {
+    write_nested(
+        root,
+        w,
+        |node, w| {
+            w.write_all(b"<node value=\"")?;
+            write_escaped(&node.value.to_string(), w)?;
+            w.write_all(b"\">")
+        },
+        |_, w| w.write_all(b"</node>"),
+    )
+}
+
+/// Write `s` with XML's special characters escaped
+fn write_escaped(s: &str, w: &mut dyn io::Write) -> io::Result<()> {
+    let mut rest = s.as_bytes();
+    while let Some(i) = rest.iter().position(|b| b"&<>\"'".contains(b)) {
+        let escaped: &[u8] = match rest[i] {
+            b'&' => b"&amp;",
+            b'<' => b"&lt;",
+            b'>' => b"&gt;",
+            b'"' => b"&quot;",
+            _ => b"&apos;",
+        };
+        w.write_all(&rest[..i])?;
+        w.write_all(escaped)?;
+        rest = &rest[i + 1..];
+    }
+    w.write_all(rest)
+}
+
+impl<T> Arena<T> {
+    /// Write every tree in this arena as a Graphviz DOT graph
+    ///
+    /// Nodes are named by their index & labeled using `label`, with `"`, `\`
+    /// & newlines escaped. Each node is followed by the edges to its
+    /// children, in insertion order, so unattached orphans & roots only have
+    /// edges going out.
+    ///
+    /// ```
+    /// # use silva::Arena;
+    /// let arena = Arena::new();
+    /// let root = arena.push(None, "root");
+    /// arena.push(root, "leaf");
+    /// assert_eq!(
+    ///     arena.to_dot(|node| node.value.to_string()),
+    ///     "digraph {\n    0 [label=\"root\"];\n    0 -> 1;\n    1 [label=\"leaf\"];\n}\n",
+    /// );
+    /// ```
+    pub fn to_dot(&self, label: impl Fn(&Node<T>) -> String) -> String {
+        let mut dot = String::from("digraph {\n");
+        for node in self.iter_range(..) {
+            let index = node.index().as_usize();
+            // writing to a string never fails
+            let _ = write!(dot, "    {index} [label=\"");
+            for c in label(node).chars() {
+                match c {
+                    '"' | '\\' => dot.extend(['\\', c]),
+                    '\n' => dot.push_str("\\n"),
+                    c => dot.push(c),
+                }
+            }
+            dot.push_str("\"];\n");
+            for child in node.children_in(ChildOrder::InsertionOrder) {
+                let _ = writeln!(dot, "    {index} -> {};", child.index().as_usize());
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// A subtree displayed as a Mermaid flowchart, see [`Node::mermaid`]
+pub struct Mermaid<'a, T, F> {
+    root: &'a Node<T>,
+    label: F,
+}
+
+impl<T, F: Fn(&Node<T>) -> String> Display for Mermaid<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("graph TD\n")?;
+        let mut stack = vec![self.root];
+        while let Some(node) = stack.pop() {
+            let index = node.index().as_usize();
+            write!(f, "    n{index}[\"")?;
+            for c in (self.label)(node).chars() {
+                match c {
+                    '"' => f.write_str("#quot;")?,
+                    '\n' => f.write_str("<br>")?,
+                    c => f.write_char(c)?,
+                }
+            }
+            f.write_str("\"]\n")?;
+            for child in node.children_in(ChildOrder::InsertionOrder) {
+                writeln!(f, "    n{index} --> n{}", child.index().as_usize())?;
+            }
+            // pushed newest first, so the oldest is popped first
+            stack.extend(node.children_in(ChildOrder::NewestFirst));
+        }
+        Ok(())
+    }
+}
+
+impl<T> Node<T> {
+    /// Display the subtree rooted at this node as a Mermaid `graph TD`
+    /// flowchart, for pasting into Markdown
+    ///
+    /// Nodes are named `n` followed by their index & labeled using `label`,
+    /// with `"` & newlines escaped. They are written in pre-order, each
+    /// followed by the edges to its children.
+    ///
+    /// ```
+    /// # use silva::Arena;
+    /// let arena = Arena::new();
+    /// let root = arena.push(None, "root");
+    /// arena.push(root, "leaf");
+    /// assert_eq!(
+    ///     root.mermaid(|node| node.value.to_string()).to_string(),
+    ///     "graph TD\n    n0[\"root\"]\n    n0 --> n1\n    n1[\"leaf\"]\n",
+    /// );
+    /// ```
+    pub const fn mermaid<F: Fn(&Self) -> String>(&self, label: F) -> Mermaid<'_, T, F> {
+        Mermaid { root: self, label }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl<T: serde::Serialize> Node<T> {
+    /// Convert the subtree rooted at this node to nested json
+    ///
+    /// Each node becomes a `{"value": ..., "children": [...]}` object, with
+    /// its children in insertion order. Nodes are walked using an explicit
+    /// stack, so deep trees can't overflow the stack.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a value can't be serialized
+    pub fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        use serde_json::{Map, Value};
+
+        /// Work left to do, in reverse
+        enum Step<'a, T> {
+            Open(&'a Node<T>),
+            Close(Value),
+        }
+
+        // the children of each open node
+        let mut levels = vec![Vec::new()];
+        let mut stack = vec![Step::Open(self)];
+        while let Some(step) = stack.pop() {
+            match step {
+                Step::Open(node) => {
+                    stack.push(Step::Close(serde_json::to_value(&node.value)?));
+                    levels.push(Vec::new());
+                    // pushed newest first, so the oldest is popped first
+                    stack.extend(node.children_in(ChildOrder::NewestFirst).map(Step::Open));
+                }
+                Step::Close(value) => {
+                    let children = levels.pop().unwrap_or_default();
+                    let mut object = Map::new();
+                    object.insert("value".into(), value);
+                    object.insert("children".into(), Value::Array(children));
+                    if let Some(parent) = levels.last_mut() {
+                        parent.push(Value::Object(object));
+                    }
+                }
+            }
+        }
+        Ok(levels
+            .pop()
+            .and_then(|mut root| root.pop())
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl<T: serde::Serialize> Arena<T> {
+    /// Convert every tree in this arena to a json array of nested trees
+    ///
+    /// Roots are taken in index order, each converted by [`Node::to_json`].
+    /// Unattached orphans are left out.
+    ///
+    /// ```
+    /// # use silva::Arena;
+    /// let arena = Arena::new();
+    /// let root = arena.push(None, "root");
+    /// arena.push(root, "leaf");
+    /// assert_eq!(
+    ///     arena.to_json().unwrap().to_string(),
+    ///     r#"[{"children":[{"children":[],"value":"leaf"}],"value":"root"}]"#,
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if a value can't be serialized
+    pub fn to_json(&self) -> serde_json::Result<serde_json::Value> {
+        self.roots().map(Node::to_json).collect()
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl<T: serde::de::DeserializeOwned> Arena<T> {
+    /// Build an arena from json made by [`Arena::to_json`]
+    ///
+    /// Takes either an array of trees or a single tree, each node being an
+    /// object holding its `"value"` & an optional array of `"children"`.
+    /// Nodes are pushed in pre-order, so the first root is at index `0`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the json is not shaped like a tree, or a value can't be
+    /// deserialized
+    ///
+    /// # Panics
+    ///
+    /// panics if the arena is full
+    pub fn from_json(json: &serde_json::Value) -> serde_json::Result<Self> {
+        use serde::de::Error;
+        use serde_json::Value;
+
+        let arena = Self::new();
+        let mut stack: Vec<_> = match json {
+            Value::Array(trees) => trees.iter().rev().map(|tree| (None, tree)).collect(),
+            tree => vec![(None, tree)],
+        };
+        while let Some((parent, tree)) = stack.pop() {
+            let Value::Object(object) = tree else {
+                return Err(Error::custom("expected a node object"));
+            };
+            let value = object
+                .get("value")
+                .ok_or_else(|| Error::missing_field("value"))?;
+            let node = arena.push(parent, T::deserialize(value)?).index();
+            match object.get("children") {
+                None | Some(Value::Null) => {}
+                Some(Value::Array(children)) => {
+                    // pushed in reverse, so the first is popped first
+                    stack.extend(children.iter().rev().map(|child| (Some(node), child)));
+                }
+                Some(_) => return Err(Error::custom("expected an array of children")),
+            }
+        }
+        Ok(arena)
+    }
+}
@@ -0,0 +1,217 @@
+//! A C API over arenas of pointer-sized values
+//!
+//! Enabled by the `ffi` feature, see `include/silva.h` for the matching
+//! header. Nodes are named by their dense index, see [`Index::as_usize`].
+//!
+//! # Thread safety
+//!
+//! An arena may be used from any number of threads at once, through every
+//! function but [`silva_arena_free`], which must not race with any other call
+//! on the same arena.
+//!
+//! # Panics
+//!
+//! No function unwinds into C. A panic is caught at the boundary & reported
+//! as an error instead.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{Arena, Index, Node};
+
+/// An arena of pointer-sized values, opaque to C
+#[expect(non_camel_case_types)]
+pub struct silva_arena_t {
+    arena: Arena<usize>,
+}
+
+/// The index given for a missing node, or used as the parent of a root
+pub const SILVA_NONE: u64 = u64::MAX;
+/// The index given when a call fails
+pub const SILVA_ERROR: u64 = u64::MAX - 1;
+
+/// The call succeeded
+pub const SILVA_OK: i32 = 0;
+/// The arena or the output pointer was null
+pub const SILVA_ERR_NULL: i32 = -1;
+/// The index is not a node in the arena
+pub const SILVA_ERR_NOT_FOUND: i32 = -2;
+/// The call panicked
+pub const SILVA_ERR_PANIC: i32 = -3;
+
+/// Run `f`, returning `error` if it panics
+fn guard<R>(error: R, f: impl FnOnce() -> R) -> R {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(error)
+}
+
+/// Get the node at the given C index
+fn node(arena: &Arena<usize>, index: u64) -> Option<&Node<usize>> {
+    arena.get_dense(usize::try_from(index).ok()?)
+}
+
+/// The C index of a node
+const fn c_index(index: Index) -> u64 {
+    index.as_usize() as u64
+}
+
+/// Get a link of the node at `index`
+///
+/// # Safety
+///
+/// `arena` must be null or come from [`silva_arena_new`]
+unsafe fn link(
+    arena: *const silva_arena_t,
+    index: u64,
+    f: fn(&Node<usize>) -> Option<&Node<usize>>,
+) -> u64 {
+    // SAFETY: upheld by caller
+    let Some(arena) = (unsafe { arena.as_ref() }) else {
+        return SILVA_ERROR;
+    };
+    guard(SILVA_ERROR, || {
+        node(&arena.arena, index).map_or(SILVA_ERROR, |node| {
+            f(node).map_or(SILVA_NONE, |n| c_index(n.index()))
+        })
+    })
+}
+
+/// Create a new, empty, arena
+///
+/// Returns null if it could not be created. It must be freed using
+/// [`silva_arena_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn silva_arena_new() -> *mut silva_arena_t {
+    guard(std::ptr::null_mut(), || {
+        Box::into_raw(Box::new(silva_arena_t {
+            arena: Arena::new(),
+        }))
+    })
+}
+
+/// Free an arena, does nothing if `arena` is null
+///
+/// # Safety
+///
+/// `arena` must be null or come from [`silva_arena_new`], & not have been
+/// freed already. No other call may use it concurrently or afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn silva_arena_free(arena: *mut silva_arena_t) {
+    if !arena.is_null() {
+        // SAFETY: upheld by caller
+        let arena = unsafe { Box::from_raw(arena) };
+        // values are plain integers, so dropping can't panic
+        drop(arena);
+    }
+}
+
+/// Add a node holding `value`, returning its index
+///
+/// `parent` is the parent's index, or [`SILVA_NONE`] to push a root. Returns
+/// [`SILVA_ERROR`] if `arena` is null, `parent` is not a node, or the arena
+/// is full.
+///
+/// # Safety
+///
+/// `arena` must be null or come from [`silva_arena_new`]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn silva_push(arena: *const silva_arena_t, parent: u64, value: usize) -> u64 {
+    // SAFETY: upheld by caller
+    let Some(arena) = (unsafe { arena.as_ref() }) else {
+        return SILVA_ERROR;
+    };
+    let arena = &arena.arena;
+    guard(SILVA_ERROR, || {
+        let parent = match parent {
+            SILVA_NONE => None,
+            parent => match node(arena, parent) {
+                Some(parent) => Some(parent.index()),
+                None => return SILVA_ERROR,
+            },
+        };
+        arena
+            .try_push(parent, value)
+            .map_or(SILVA_ERROR, |node| c_index(node.index()))
+    })
+}
+
+/// Read the value of the node at `index` into `out`
+///
+/// Returns [`SILVA_OK`] on success, leaving `out` untouched otherwise.
+///
+/// # Safety
+///
+/// `arena` must be null or come from [`silva_arena_new`], `out` must be null
+/// or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn silva_get(
+    arena: *const silva_arena_t,
+    index: u64,
+    out: *mut usize,
+) -> i32 {
+    // SAFETY: upheld by caller
+    let Some(arena) = (unsafe { arena.as_ref() }) else {
+        return SILVA_ERR_NULL;
+    };
+    if out.is_null() {
+        return SILVA_ERR_NULL;
+    }
+    guard(SILVA_ERR_PANIC, || {
+        node(&arena.arena, index).map_or(SILVA_ERR_NOT_FOUND, |node| {
+            // SAFETY: upheld by caller
+            unsafe { out.write(node.value) };
+            SILVA_OK
+        })
+    })
+}
+
+/// Get the index of the parent of the node at `index`
+///
+/// Returns [`SILVA_NONE`] for roots & orphans, or [`SILVA_ERROR`] if `arena`
+/// is null or `index` is not a node.
+///
+/// # Safety
+///
+/// `arena` must be null or come from [`silva_arena_new`]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn silva_parent(arena: *const silva_arena_t, index: u64) -> u64 {
+    // SAFETY: upheld by caller
+    unsafe { link(arena, index, Node::parent) }
+}
+
+/// Get the index of the latest child of the node at `index`
+///
+/// Returns [`SILVA_NONE`] for leaves, or [`SILVA_ERROR`] if `arena` is null
+/// or `index` is not a node.
+///
+/// # Safety
+///
+/// `arena` must be null or come from [`silva_arena_new`]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn silva_child(arena: *const silva_arena_t, index: u64) -> u64 {
+    // SAFETY: upheld by caller
+    unsafe { link(arena, index, Node::child) }
+}
+
+/// Get the index of the next(previously added) sibling of the node at `index`
+///
+/// Returns [`SILVA_NONE`] for the oldest sibling, or [`SILVA_ERROR`] if
+/// `arena` is null or `index` is not a node.
+///
+/// # Safety
+///
+/// `arena` must be null or come from [`silva_arena_new`]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn silva_next(arena: *const silva_arena_t, index: u64) -> u64 {
+    // SAFETY: upheld by caller
+    unsafe { link(arena, index, Node::next) }
+}
+
+/// Get the number of nodes in the arena, `0` if `arena` is null
+///
+/// # Safety
+///
+/// `arena` must be null or come from [`silva_arena_new`]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn silva_count(arena: *const silva_arena_t) -> u64 {
+    // SAFETY: upheld by caller
+    unsafe { arena.as_ref() }.map_or(0, |arena| arena.arena.count() as u64)
+}
@@ -1,15 +1,44 @@
+use std::collections::BTreeSet;
 use std::fmt::Display;
 use std::num::NonZero;
 
-use crate::{Arena, Node};
+use crate::error::ForeignNodeError;
+#[cfg(doc)]
+use crate::error::PushError;
+use crate::{Arena, Handle, Node};
+
+/// The integer an [`Index`] is stored as
+#[cfg(not(feature = "compact-index"))]
+type Repr = usize;
+/// The integer an [`Index`] is stored as
+#[cfg(feature = "compact-index")]
+type Repr = u32;
 
 /// A valid index into an arena
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Index(NonZero<usize>);
+///
+/// With the `compact-index` feature this is stored in a `u32`, making it 4
+/// bytes, & arenas hold at most `u32::MAX` nodes.
+///
+/// With the `generations` feature an index also carries its arena's
+/// `Index::generation`, so one handed out before [`Arena::clear`] finds
+/// nothing afterwards, rather than the new node at its position.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Index {
+    slot: NonZero<Repr>,
+    #[cfg(feature = "generations")]
+    generation: u32,
+}
 
 impl std::fmt::Debug for Index {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple("Index").field(&self.get()).finish()
+        let mut f = f.debug_tuple("Index");
+        f.field(&self.get());
+        // only shown once its arena has been cleared
+        #[cfg(feature = "generations")]
+        if self.generation != 0 {
+            f.field(&self.generation);
+        }
+        f.finish()
     }
 }
 
@@ -27,12 +56,87 @@ impl Index {
     /// index must be less than or equal to `MAX_INDEX`
     pub(crate) const unsafe fn new_unchecked(index: usize) -> Self {
         debug_assert!(index <= crate::arena::MAX_INDEX);
-        Self(unsafe { NonZero::new_unchecked(index + 1) })
+        // MAX_INDEX + 1 always fits in Repr
+        #[allow(clippy::cast_possible_truncation, clippy::unnecessary_cast)]
+        Self {
+            slot: unsafe { NonZero::new_unchecked(index as Repr + 1) },
+            #[cfg(feature = "generations")]
+            generation: 0,
+        }
+    }
+
+    /// Stamp this index with its arena's generation
+    #[cfg(feature = "generations")]
+    pub(crate) const fn with_generation(self, generation: u32) -> Self {
+        Self { generation, ..self }
+    }
+
+    /// Stamp this index with its arena's generation, a no-op without the
+    /// `generations` feature
+    #[cfg(not(feature = "generations"))]
+    pub(crate) const fn with_generation(self, _: u32) -> Self {
+        self
     }
 
     /// returns the index this arena is stored at
+    #[allow(clippy::unnecessary_cast)]
     pub(crate) const fn get(self) -> usize {
-        self.0.get() - 1
+        self.slot.get() as usize - 1
+    }
+
+    /// Get the dense position of this index
+    ///
+    /// Indices are handed out in push order starting from zero.
+    pub const fn as_usize(self) -> usize {
+        self.get()
+    }
+
+    /// Get the index at the dense position `index`, if `arena` has a node
+    /// there
+    ///
+    /// The counterpart of [`Index::as_usize`], for indices kept outside the
+    /// arena, such as those deserialized with the `serde` feature.
+    pub fn try_from_usize<T>(arena: &Arena<T>, index: usize) -> Option<Self> {
+        arena.get_dense(index).map(Node::index)
+    }
+
+    /// Get how many times this index's arena had been cleared when it was
+    /// handed out
+    ///
+    /// Wraps around after [`u32::MAX`] clears.
+    #[cfg(feature = "generations")]
+    pub const fn generation(self) -> u32 {
+        self.generation
+    }
+}
+
+/// Serialized as its dense position, a plain integer
+///
+/// Nothing ties the integer to an arena, so a deserialized index should be
+/// checked with [`Index::try_from_usize`] before it is used. With the
+/// `generations` feature the generation is not kept, & deserialized indices
+/// are of the first.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Index {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_usize().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Index {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{Error, Unexpected};
+
+        let index = usize::deserialize(deserializer)?;
+        if index > crate::arena::MAX_INDEX {
+            return Err(D::Error::invalid_value(
+                Unexpected::Unsigned(index as u64),
+                &"an index an arena can hold",
+            ));
+        }
+        // SAFETY: checked above
+        Ok(unsafe { Self::new_unchecked(index) })
     }
 }
 
@@ -43,38 +147,99 @@ impl Index {
 /// - [`Option<Index>`]
 /// - [`Index`]
 /// - [`Node<T>`]
+/// - [`Handle<T>`]
 ///
-/// Note that using a node from another arena here will cause a panic.
+/// Note that using a node from another arena here will cause a panic, see
+/// [`Arena::try_resolve_parent`] for a non-panicking alternative, or
+/// [`Arena::try_push`] which returns [`PushError::Foreign`]. So will an
+/// [`Index`] whose push has not finished writing its node, such as one sent
+/// out of a [`Arena::push_with`] closure. Nodes that are only being linked are
+/// waited on, but unwritten ones are not, as their push may have failed.
 pub trait AsParent<T>: as_parent::Sealed {
-    /// Optionally get an index
-    fn get(self, arena: &Arena<T>) -> Option<&Node<T>>;
+    /// Optionally get a node, failing if it does not belong to `arena`
+    ///
+    /// # Errors
+    ///
+    /// Fails if the node is not found in `arena`
+    fn try_get(self, arena: &Arena<T>) -> Result<Option<&Node<T>>, ForeignNodeError>;
+
+    /// Optionally get a node
+    ///
+    /// # Panics
+    ///
+    /// panics if the node does not belong to `arena`
+    fn get(self, arena: &Arena<T>) -> Option<&Node<T>>
+    where
+        Self: Sized,
+    {
+        self.try_get(arena).unwrap_or_else(|e| panic!("{e}"))
+    }
 }
 
 impl<T> AsParent<T> for Index {
-    fn get(self, arena: &Arena<T>) -> Option<&Node<T>> {
-        Some(&arena[self])
+    fn try_get(self, arena: &Arena<T>) -> Result<Option<&Node<T>>, ForeignNodeError> {
+        arena
+            .get(self)
+            .map(Some)
+            .ok_or_else(|| ForeignNodeError::new(self, self.as_usize() < arena.reserved()))
     }
 }
 
 impl<T> AsParent<T> for Option<Index> {
-    fn get(self, arena: &Arena<T>) -> Option<&Node<T>> {
-        Some(&arena[self?])
+    fn try_get(self, arena: &Arena<T>) -> Result<Option<&Node<T>>, ForeignNodeError> {
+        self.map_or(Ok(None), |index| index.try_get(arena))
     }
 }
 
 impl<T> AsParent<T> for &Node<T> {
-    fn get(self, arena: &Arena<T>) -> Option<&Node<T>> {
-        let node = &arena[self.index()];
-        assert!(std::ptr::eq(self, node), "invalid node used with arena");
-        Some(node)
+    fn try_get(self, arena: &Arena<T>) -> Result<Option<&Node<T>>, ForeignNodeError> {
+        arena
+            .get(self.index())
+            .filter(|node| std::ptr::eq(self, *node))
+            .map(Some)
+            .ok_or_else(|| ForeignNodeError::new(self.index(), false))
+    }
+}
+
+impl<T> AsParent<T> for &Handle<T> {
+    fn try_get(self, arena: &Arena<T>) -> Result<Option<&Node<T>>, ForeignNodeError> {
+        self.node().try_get(arena)
     }
 }
 
+/// Collects iterators of nodes into their indices
+///
+/// Implemented for every [`Iterator`] over `&Node<T>`, such as
+/// [`Node::children`] or [`Node::ancestors`].
+pub trait CollectIndices: Iterator + collect_indices::Sealed {
+    /// Collect the indices of the nodes, in iteration order
+    fn collect_indices(self) -> Vec<Index>;
+
+    /// Collect the indices of the nodes into a set
+    fn collect_index_set(self) -> BTreeSet<Index>;
+}
+
+impl<'a, T: 'a, I: Iterator<Item = &'a Node<T>>> CollectIndices for I {
+    fn collect_indices(self) -> Vec<Index> {
+        self.map(Node::index).collect()
+    }
+
+    fn collect_index_set(self) -> BTreeSet<Index> {
+        self.map(Node::index).collect()
+    }
+}
+
+mod collect_indices {
+    pub trait Sealed {}
+    impl<'a, T: 'a, I: Iterator<Item = &'a super::Node<T>>> Sealed for I {}
+}
+
 mod as_parent {
     pub trait Sealed {}
     impl Sealed for super::Index {}
     impl Sealed for Option<super::Index> {}
     impl<T> Sealed for &super::Node<T> {}
+    impl<T> Sealed for &super::Handle<T> {}
 }
 
 impl<T> From<&Node<T>> for Index {
@@ -0,0 +1,178 @@
+//! Conversions to & from other tree crates
+
+use std::collections::HashMap;
+
+use crate::{Arena, Index};
+#[cfg(feature = "ego-tree")]
+use crate::{ChildOrder, Node, SecondaryMap};
+
+#[cfg(feature = "indextree")]
+impl<T> Arena<T> {
+    /// Move every tree of an [`indextree::Arena`] into a new arena, giving
+    /// where each of its nodes ended up
+    ///
+    /// Roots are taken in storage order, each tree pushed in pre-order with
+    /// children in order, so values are moved without being cloned. Removed
+    /// nodes are left out.
+    ///
+    /// ```
+    /// # use silva::Arena;
+    /// let mut tree = indextree::Arena::new();
+    /// let root = tree.new_node("root");
+    /// let leaf = root.append_value("leaf", &mut tree);
+    ///
+    /// let (arena, indices) = Arena::from_indextree(tree);
+    /// let leaf = &arena[indices[&leaf]];
+    /// assert_eq!(leaf.parent().unwrap().index(), indices[&root]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// panics if the arena is full
+    pub fn from_indextree(tree: indextree::Arena<T>) -> (Self, HashMap<indextree::NodeId, Index>) {
+        // each node in pre-order, along with its parent
+        let mut order = Vec::with_capacity(tree.live_count());
+        for root in tree.roots() {
+            order.extend(root.descendants(&tree).map(|id| (id, tree[id].parent())));
+        }
+        let mut values: Vec<Option<T>> = tree.into_iter().map(indextree::Node::into_data).collect();
+
+        let arena = Self::with_capacity(order.len());
+        let mut indices = HashMap::with_capacity(order.len());
+        for (id, parent) in order {
+            let value = values[usize::from(id) - 1]
+                .take()
+                .expect("live nodes hold a value");
+            let parent = parent.map(|parent| indices[&parent]);
+            indices.insert(id, arena.push(parent, value).index());
+        }
+        (arena, indices)
+    }
+}
+
+#[cfg(feature = "indextree")]
+impl<T> From<indextree::Arena<T>> for Arena<T> {
+    /// Move every tree of an [`indextree::Arena`] into a new arena, see
+    /// [`Arena::from_indextree`]
+    fn from(tree: indextree::Arena<T>) -> Self {
+        Self::from_indextree(tree).0
+    }
+}
+
+#[cfg(feature = "ego-tree")]
+impl<T> Arena<T> {
+    /// Move an [`ego_tree::Tree`] into a new arena, giving where each of its
+    /// nodes ended up
+    ///
+    /// The root's tree is pushed first, in pre-order with children in order,
+    /// so the root is at index `0`. The tree's orphans follow in insertion
+    /// order, becoming orphans here along with their subtrees.
+    ///
+    /// ```
+    /// # use silva::Arena;
+    /// let mut tree = ego_tree::Tree::new("root");
+    /// let leaf = tree.root_mut().append("leaf").id();
+    ///
+    /// let (arena, indices) = Arena::from_ego_tree(tree);
+    /// assert_eq!(arena[indices[&leaf]].parent().unwrap().value, "root");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// panics if the arena is full
+    pub fn from_ego_tree(tree: ego_tree::Tree<T>) -> (Self, HashMap<ego_tree::NodeId, Index>) {
+        let root = tree.root();
+        let positions: HashMap<_, _> = (tree.nodes().enumerate())
+            .map(|(i, node)| (node.id(), i))
+            .collect();
+        // each node in pre-order, along with its parent
+        let tops = tree
+            .nodes()
+            .filter(|node| node.parent().is_none() && *node != root);
+        let order: Vec<_> = std::iter::once(root)
+            .chain(tops)
+            .flat_map(|top| top.descendants())
+            .map(|node| (node.id(), node.parent().map(|parent| parent.id())))
+            .collect();
+        let mut values: Vec<Option<T>> = tree.into_iter().map(Some).collect();
+
+        let arena = Self::with_capacity(order.len());
+        let mut indices = HashMap::with_capacity(order.len());
+        for (i, (id, parent)) in order.into_iter().enumerate() {
+            let value = values[positions[&id]]
+                .take()
+                .expect("each node is reached once");
+            let node = match parent {
+                Some(parent) => arena.push(indices[&parent], value),
+                None if i == 0 => arena.push(None, value),
+                None => arena.push_orphan(value),
+            };
+            indices.insert(id, node.index());
+        }
+        (arena, indices)
+    }
+
+    /// Move the subtree rooted at the node at `root` into a new
+    /// [`ego_tree::Tree`], giving where each of its nodes ended up
+    ///
+    /// Children are appended in insertion order. Every other node is dropped
+    /// along with the arena.
+    ///
+    /// ```
+    /// # use silva::Arena;
+    /// let arena = Arena::new();
+    /// let root = arena.push(None, "root").index();
+    /// let leaf = arena.push(root, "leaf").index();
+    ///
+    /// let (tree, ids) = arena.into_ego_tree(root);
+    /// assert_eq!(tree.get(ids[leaf]).unwrap().value(), &"leaf");
+    /// assert_eq!(tree.root().value(), &"root");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no node at `root`
+    pub fn into_ego_tree(self, root: Index) -> (ego_tree::Tree<T>, SecondaryMap<ego_tree::NodeId>) {
+        let top = self.get(root).expect("no node at index");
+        // each node below the root in pre-order, along with its parent
+        let mut order = Vec::new();
+        let mut stack = vec![top];
+        while let Some(node) = stack.pop() {
+            // pushed newest first, so the oldest is popped first
+            stack.extend(node.children_in(ChildOrder::NewestFirst));
+            if node.index() != root {
+                order.push((node.index(), node.parent().map(Node::index)));
+            }
+        }
+        let mut values: Vec<Option<T>> = (0..self.reserved()).map(|_| None).collect();
+        for (index, value) in self {
+            values[index.as_usize()] = Some(value);
+        }
+
+        let mut take = |index: Index| {
+            values[index.as_usize()]
+                .take()
+                .expect("subtree nodes are initialized")
+        };
+        let mut tree = ego_tree::Tree::with_capacity(take(root), order.len() + 1);
+        let mut ids = SecondaryMap::with_capacity(order.len() + 1);
+        ids.insert(root, tree.root().id());
+        for (index, parent) in order {
+            let mut parent = parent
+                .and_then(|parent| ids.get(parent).copied())
+                .and_then(|parent| tree.get_mut(parent))
+                .expect("parents come first");
+            ids.insert(index, parent.append(take(index)).id());
+        }
+        (tree, ids)
+    }
+}
+
+#[cfg(feature = "ego-tree")]
+impl<T> From<ego_tree::Tree<T>> for Arena<T> {
+    /// Move an [`ego_tree::Tree`] into a new arena, see
+    /// [`Arena::from_ego_tree`]
+    fn from(tree: ego_tree::Tree<T>) -> Self {
+        Self::from_ego_tree(tree).0
+    }
+}
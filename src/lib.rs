@@ -12,13 +12,53 @@
 #![allow(clippy::must_use_candidate)]
 #![allow(clippy::ref_as_ptr)]
 
+pub mod algo;
 mod arena;
+mod atomic;
+mod brand;
+mod cursor;
+pub mod error;
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 mod index;
+#[cfg(any(feature = "ego-tree", feature = "indextree"))]
+mod interop;
+mod locked;
 mod node;
+#[cfg(feature = "rayon")]
+mod par;
+pub mod prelude;
+mod query;
+#[cfg(feature = "registry")]
+pub mod registry;
+mod secondary;
+mod typed;
+mod view;
+pub mod visit;
 
-pub use arena::Arena;
-pub use index::{AsParent, Index};
+#[cfg(feature = "contention-stats")]
+pub use arena::ContentionStats;
+pub use arena::{
+    Arena, ArenaLayout, ArenaSnapshot, IntoIter, IterMut, Nodes, PushedNodes, Roots,
+    SnapshotChildren, SnapshotDescendants,
+};
+pub use atomic::{Atomic, AtomicValue};
+pub use brand::{BrandedArena, BrandedIndex};
+pub use cursor::Cursor;
+pub use error::{
+    AllocError, AttachError, CheckError, DepthError, ForeignNodeError, LayoutError, PushError,
+    ReparentError, SwapError,
+};
+pub use index::{AsParent, CollectIndices, Index};
+pub use locked::Locked;
 pub use node::*;
+#[cfg(feature = "rayon")]
+pub use par::{ParDescendants, ParNodes};
+pub use query::{Matches, Query};
+pub use secondary::{ConcurrentSecondaryMap, Entries, SecondaryMap};
+pub use typed::{IndexType, TypedArena};
+pub use view::{View, ViewDescendants, ViewIter};
 
 // /// example for calling cargo-asm
 // #[inline(never)]
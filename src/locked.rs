@@ -0,0 +1,89 @@
+//! Values that can be changed while their arena is shared
+
+use std::fmt;
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{Arena, AsParent, Index, Node};
+
+/// A value behind its own lock, so it can be changed after being pushed
+///
+/// Nodes are only ever given out as shared references, so an
+/// `Arena<Locked<T>>` lets concurrent readers & writers get at a node's value
+/// without locking the rest of the tree. A lock poisoned by a panicking
+/// writer is still handed out, as with [`Arena::attach`].
+///
+/// ```
+/// # use silva::{Arena, Locked};
+/// let arena = Arena::new();
+/// let root = arena.push_locked(None, 0);
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| *root.value.write() += 1);
+///     }
+/// });
+/// assert_eq!(*arena.read(root.index()).unwrap(), 4);
+/// ```
+#[derive(Default)]
+pub struct Locked<T>(RwLock<T>);
+
+impl<T: fmt::Debug> fmt::Debug for Locked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Locked").field(&*self.read()).finish()
+    }
+}
+
+impl<T> From<T> for Locked<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> Locked<T> {
+    /// Put `value` behind a lock
+    pub const fn new(value: T) -> Self {
+        Self(RwLock::new(value))
+    }
+
+    /// Lock the value for reading, blocking while it is being written
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Lock the value for writing, blocking while it is being read or written
+    pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Get the value without locking, as it is borrowed mutably
+    pub fn get_mut(&mut self) -> &mut T {
+        self.0.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Take the value out of the lock
+    pub fn into_inner(self) -> T {
+        self.0.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T> Arena<Locked<T>> {
+    /// Add a new node, putting `value` behind a lock
+    ///
+    /// # Panics
+    ///
+    /// panics if the arena is full or the value is rejected by its push check
+    pub fn push_locked(&self, parent: impl AsParent<Locked<T>>, value: T) -> &Node<Locked<T>> {
+        self.push(parent, Locked::new(value))
+    }
+
+    /// Lock the value of the node at `index` for reading, see
+    /// [`Locked::read`]
+    pub fn read(&self, index: Index) -> Option<RwLockReadGuard<'_, T>> {
+        self.get(index).map(|node| node.value.read())
+    }
+
+    /// Lock the value of the node at `index` for writing, see
+    /// [`Locked::write`]
+    pub fn write(&self, index: Index) -> Option<RwLockWriteGuard<'_, T>> {
+        self.get(index).map(|node| node.value.write())
+    }
+}
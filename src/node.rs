@@ -1,24 +1,68 @@
 //! The nodes within an arena
 
+use std::cmp::Ordering;
+use std::collections::VecDeque;
 use std::fmt;
+use std::iter::FusedIterator;
 use std::ptr::{self, NonNull};
-use std::sync::Arc;
-use std::sync::atomic::AtomicPtr;
 use std::sync::atomic::Ordering::AcqRel;
 use std::sync::atomic::Ordering::Acquire;
+use std::sync::atomic::Ordering::Relaxed;
+use std::sync::atomic::Ordering::Release;
+use std::sync::atomic::{AtomicPtr, AtomicUsize};
+use std::sync::{Arc, Weak};
+use std::vec;
 
-use crate::{Arena, Index};
+use crate::{Arena, AttachError, Cursor, Index};
+
+// # Synchronization
+//
+// Links are only ever dereferenced after an `Acquire` load, and every node is
+// fully written before it is linked anywhere:
+//
+// - `add_child` stores `next` & `position` `Relaxed` then publishes the child
+//   with an `AcqRel` CAS on `parent.child`. The previous head's `position` is
+//   read, so `parent.child` is loaded with `Acquire`, & the successful CAS
+//   keeps older siblings visible to readers that reach the child through its
+//   slot rather than through `parent.child`.
+// - `attach` links a node that is already visible through its slot, so a
+//   reader may load its `next` without passing through `parent.child`. There,
+//   `next` & `position` are stored with `Release` in both child orders,
+//   though appending in insertion order leaves `next` null & only stores
+//   `position`.
+// - In insertion order, a child is instead appended with an `AcqRel` CAS on
+//   the `next` of the node `tail` points to, or on `parent.child` for the
+//   first child. `tail` only ever moves forward onto a node that is already
+//   linked, & is stored with `Release` so a reader that loads it with
+//   `Acquire` may follow it. A stale `tail` is helped forward by whoever
+//   finds it behind.
+// - Newest first, `tail` is instead set once, with `Release`, to the tagged
+//   oldest child after it is linked.
+// - `parent` is set before the node is published, or by a `Release` CAS when
+//   attaching an orphan.
+// - `depth` is only ever set to its final value, so it is `Relaxed`, & a
+//   reader that misses it climbs to find it.
 
 /// A node within an arena
 pub struct Node<T> {
     /// This node's index, added for convenience
     index: Index,
-    /// This nodes's parent
-    parent: *const Self,
-    /// This nodes's last added child
+    /// This nodes's parent, [`Node::ORPHAN`] while unattached
+    parent: AtomicPtr<Self>,
+    /// This nodes's first child, see [`Node::children`]
     child: AtomicPtr<Self>,
     /// The node after this one
-    next: *const Self,
+    next: AtomicPtr<Self>,
+    /// This node's last child in insertion order, [`Node::NO_CHILDREN`] until
+    /// its first child is linked. When linking newest first, it is null until
+    /// then, & the oldest child tagged with [`Node::OLDEST`] after
+    tail: AtomicPtr<Self>,
+    /// This node's distance from its root, [`Node::UNKNOWN_DEPTH`] until it
+    /// can no longer change
+    depth: AtomicUsize,
+    /// One more than the number of siblings linked before this node, `0`
+    /// until it is linked
+    position: AtomicUsize,
     /// The node's data
     pub value: T,
 }
@@ -27,38 +71,136 @@ impl<T: fmt::Debug> fmt::Debug for Node<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Node")
             .field("index", &self.index)
-            .field("parent", &self.parent().map(Node::index))
-            .field("child", &self.child().map(Node::index))
-            .field("next", &self.next().map(Node::index))
+            .field("parent", &self.parent().map(Self::index))
             .field("value", &self.value)
-            .finish()
+            .finish_non_exhaustive()
     }
 }
 
-const fn map_ref<T>(v: Option<&T>) -> *const T {
-    if let Some(v) = v {
-        ptr::from_ref(v)
-    } else {
-        ptr::null()
+impl<T: fmt::Display> fmt::Display for Node<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)?;
+        write!(f, "@{}", self.index)
     }
 }
 
+/// The order [`Node::children`] gives a node's children in
+///
+/// Chosen per arena, see [`Arena::with_child_order`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChildOrder {
+    /// The latest child first, each linked with a single CAS
+    #[default]
+    NewestFirst,
+    /// The order children were linked in, at the cost of a tail pointer per
+    /// node, which appends still follow without locking
+    InsertionOrder,
+}
+
 impl<T> Node<T> {
     /// create a new node
     ///
     /// # Safety
     ///
-    /// The given `parent` should be located in the arena this node is to put in.
-    pub(crate) const unsafe fn new(index: Index, parent: Option<&Self>, value: T) -> Self {
+    /// The given `parent` should be null, or point to a node located in the
+    /// arena this node is to put in, taken from its slot.
+    pub(crate) unsafe fn new(index: Index, parent: *mut Self, order: ChildOrder, value: T) -> Self {
+        // SAFETY: upheld by caller
+        let depth =
+            unsafe { parent.as_ref() }.map_or(0, |parent| match parent.depth.load(Relaxed) {
+                Self::UNKNOWN_DEPTH => Self::UNKNOWN_DEPTH,
+                depth => depth + 1,
+            });
+        Self {
+            index,
+            parent: AtomicPtr::new(parent),
+            child: AtomicPtr::new(ptr::null_mut()),
+            next: AtomicPtr::new(ptr::null_mut()),
+            tail: AtomicPtr::new(Self::tail_for(order)),
+            depth: AtomicUsize::new(depth),
+            position: AtomicUsize::new(0),
+            value,
+        }
+    }
+
+    /// The depth of a node under an orphan, which may yet be attached
+    const UNKNOWN_DEPTH: usize = usize::MAX;
+
+    /// The parent of an orphan that is yet to be attached
+    ///
+    /// Misaligned, so it can never point to a real node.
+    const ORPHAN: *mut Self = ptr::without_provenance_mut(1);
+
+    /// The tail of a node yet to have children, when linking them in
+    /// insertion order
+    ///
+    /// Misaligned, so it can never point to a real node.
+    const NO_CHILDREN: *mut Self = ptr::without_provenance_mut(2);
+
+    /// Tags a `tail` pointing to the oldest child, when linking newest first
+    const OLDEST: usize = 1;
+
+    /// The initial tail of a node whose children are linked in `order`
+    const fn tail_for(order: ChildOrder) -> *mut Self {
+        match order {
+            ChildOrder::NewestFirst => ptr::null_mut(),
+            ChildOrder::InsertionOrder => Self::NO_CHILDREN,
+        }
+    }
+
+    /// create a new orphan, which can later be given a parent
+    pub(crate) const fn orphan(index: Index, order: ChildOrder, value: T) -> Self {
         Self {
             index,
-            parent: map_ref(parent),
+            parent: AtomicPtr::new(Self::ORPHAN),
             child: AtomicPtr::new(ptr::null_mut()),
-            next: ptr::null_mut(),
+            next: AtomicPtr::new(ptr::null_mut()),
+            tail: AtomicPtr::new(Self::tail_for(order)),
+            depth: AtomicUsize::new(Self::UNKNOWN_DEPTH),
+            position: AtomicUsize::new(0),
             value,
         }
     }
 
+    /// Map this node's value, pointing its links into another arena
+    ///
+    /// # Safety
+    ///
+    /// `relink` must give the counterpart of each node in the arena the new
+    /// node is to be put in.
+    pub(crate) unsafe fn map<U>(
+        self,
+        relink: impl Fn(*mut Self) -> *mut Node<U>,
+        f: impl FnOnce(T) -> U,
+    ) -> Node<U> {
+        let relink = |link: AtomicPtr<Self>| {
+            let link = link.into_inner();
+            if link.is_null() {
+                ptr::null_mut()
+            } else if link == Self::ORPHAN {
+                Node::ORPHAN
+            } else if link == Self::NO_CHILDREN {
+                Node::NO_CHILDREN
+            } else {
+                relink(link)
+            }
+        };
+        // keep the oldest child's tag through relinking
+        let tail = self.tail.into_inner();
+        let tag = tail.addr() & Self::OLDEST;
+        let tail = relink(AtomicPtr::new(tail.map_addr(|addr| addr & !tag)));
+        Node {
+            index: self.index,
+            parent: AtomicPtr::new(relink(self.parent)),
+            child: AtomicPtr::new(relink(self.child)),
+            next: AtomicPtr::new(relink(self.next)),
+            tail: AtomicPtr::new(tail.map_addr(|addr| addr | tag)),
+            depth: self.depth,
+            position: self.position,
+            value: f(self.value),
+        }
+    }
+
     /// turns this node into a handle using the given arena
     ///
     /// # Panics
@@ -74,8 +216,48 @@ impl<T> Node<T> {
     }
 
     /// A convenient wrapper for debugging a node
-    pub const fn debug(&self) -> DebugNode<T> {
-        DebugNode(self)
+    ///
+    /// This prints every descendant, see [`Node::debug_compact`] for a bounded
+    /// alternative.
+    pub const fn debug(&self) -> DebugNode<'_, T> {
+        DebugNode::new(self)
+    }
+
+    /// A wrapper for debugging a node, eliding large subtrees
+    ///
+    /// Prints up to 8 levels of descendants & 16 children per node.
+    pub const fn debug_compact(&self) -> DebugNode<'_, T> {
+        DebugNode::new(self).max_depth(8).max_children(16)
+    }
+
+    /// A wrapper for debugging the shape of this node's subtree
+    ///
+    /// Only indices are printed, as in `0 -> [7, 4 -> [6, 5], 1]`, so `T`
+    /// needn't be [`Debug`](fmt::Debug). Bounded like [`Node::debug_compact`].
+    pub const fn debug_structure(&self) -> DebugStructure<'_, T> {
+        DebugStructure(self.debug_compact())
+    }
+
+    /// A wrapper displaying this node's subtree as an indented tree
+    ///
+    /// ```
+    /// # use silva::Arena;
+    /// let arena = Arena::new();
+    /// let root = arena.push(None, "src");
+    /// let arena_rs = arena.push(root, "arena");
+    /// arena.push(arena_rs, "raw.rs");
+    /// arena.push(root, "lib.rs");
+    /// assert_eq!(
+    ///     root.display_tree().to_string(),
+    ///     "src\n├── arena\n│   └── raw.rs\n└── lib.rs",
+    /// );
+    /// ```
+    pub const fn display_tree(&self) -> DisplayTree<'_, T> {
+        DisplayTree {
+            node: self,
+            charset: TreeCharset::UNICODE,
+            max_depth: usize::MAX,
+        }
     }
 
     /// Get this node's index
@@ -83,16 +265,111 @@ impl<T> Node<T> {
         self.index
     }
 
+    /// Get this node's sequence number
+    ///
+    /// Indices are handed out in a single global order, so this is the
+    /// number of indices handed out before this node's. Pushes racing on
+    /// other threads may still finish in a different order.
+    pub const fn seq(&self) -> usize {
+        self.index.as_usize()
+    }
+
     /// Get this node's parent
     ///
-    /// If [`None`] this node is a root
-    #[expect(clippy::missing_const_for_fn)]
+    /// If [`None`] this node is a root or an unattached orphan
     pub fn parent(&self) -> Option<&Self> {
+        let parent = self.parent.load(Acquire);
+        if parent == Self::ORPHAN {
+            return None;
+        }
         // SAFETY: Node.parent is always sound
-        unsafe { self.parent.as_ref() }
+        unsafe { parent.as_ref() }
+    }
+
+    /// The order this node's children are linked in
+    pub fn child_order(&self) -> ChildOrder {
+        let tail = self.tail.load(Relaxed);
+        if tail.is_null() || tail.addr() & Self::OLDEST != 0 {
+            ChildOrder::NewestFirst
+        } else {
+            ChildOrder::InsertionOrder
+        }
+    }
+
+    /// The number of ancestors this node has
+    ///
+    /// Roots are at depth `0`, as are unattached orphans, whose subtrees are
+    /// measured from them. Depth is recorded as nodes are pushed, so this is
+    /// `O(1)`, except under orphans: once attached, the first call climbs to
+    /// the nearest node whose depth is known & records the way back.
+    pub fn depth(&self) -> usize {
+        self.resolve_depth().unwrap_or_else(|depth| depth)
+    }
+
+    /// Get this node's depth, or its depth below an unattached orphan as an
+    /// error, as that may still change
+    fn resolve_depth(&self) -> Result<usize, usize> {
+        let known = self.depth.load(Relaxed);
+        if known != Self::UNKNOWN_DEPTH {
+            return Ok(known);
+        }
+
+        let mut climbed = 0;
+        let mut up = self;
+        let base = loop {
+            let Some(parent) = up.parent() else {
+                // an unattached orphan, which may still be given a parent
+                return Err(climbed);
+            };
+            climbed += 1;
+            up = parent;
+            match up.depth.load(Relaxed) {
+                Self::UNKNOWN_DEPTH => {}
+                depth => break depth,
+            }
+        };
+
+        // the way up is now fixed, so record it for good
+        let path = std::iter::once(self).chain(self.ancestors());
+        for (node, depth) in path.zip((base + 1..=base + climbed).rev()) {
+            node.depth.store(depth, Relaxed);
+        }
+        Ok(base + climbed)
+    }
+
+    /// returns `true` if this node is a strict ancestor of `other`
+    ///
+    /// Climbs from `other` only as far as this node's depth, see
+    /// [`Node::depth`]. Under an unattached orphan, depths may change as it
+    /// is attached, so all of `other`'s ancestors are walked instead.
+    pub fn is_ancestor_of(&self, other: &Self) -> bool {
+        let (Ok(depth), Ok(other_depth)) = (self.resolve_depth(), other.resolve_depth()) else {
+            return other.ancestors().any(|node| ptr::eq(node, self));
+        };
+        other_depth
+            .checked_sub(depth + 1)
+            .and_then(|steps| other.ancestors().nth(steps))
+            .is_some_and(|node| ptr::eq(node, self))
+    }
+
+    /// returns `true` if this node is a strict descendant of `other`
+    ///
+    /// See [`Node::is_ancestor_of`]
+    pub fn is_descendant_of(&self, other: &Self) -> bool {
+        other.is_ancestor_of(self)
     }
 
-    /// Get this node's latest child
+    /// returns `true` if this node has no parent & is not an orphan
+    pub fn is_root(&self) -> bool {
+        self.parent.load(Relaxed).is_null()
+    }
+
+    /// returns `true` if this node is an orphan that is yet to be attached
+    pub fn is_orphan(&self) -> bool {
+        self.parent.load(Relaxed) == Self::ORPHAN
+    }
+
+    /// Get this node's first child, see [`Node::children`]
     ///
     /// If [`None`] this node is a leaf
     pub fn child(&self) -> Option<&Self> {
@@ -100,27 +377,106 @@ impl<T> Node<T> {
         unsafe { self.child.load(Acquire).as_ref() }
     }
 
-    /// Get this node's next sibling
-    #[expect(clippy::missing_const_for_fn)]
+    /// Get this node's oldest child, whatever order its children are in
+    ///
+    /// If [`None`] this node is a leaf
+    pub fn first_child(&self) -> Option<&Self> {
+        let tail = self.tail.load(Acquire);
+        if tail.addr() & Self::OLDEST != 0 {
+            // SAFETY: a tagged tail is the oldest child, which is linked
+            unsafe { tail.map_addr(|addr| addr & !Self::OLDEST).as_ref() }
+        } else if tail.is_null() {
+            // no children, or the first is linked but yet to be recorded
+            self.children().last()
+        } else {
+            self.child()
+        }
+    }
+
+    /// Get this node's newest child, whatever order its children are in
+    ///
+    /// If [`None`] this node is a leaf
+    pub fn last_child(&self) -> Option<&Self> {
+        let tail = self.tail.load(Acquire);
+        if tail.is_null() || tail.addr() & Self::OLDEST != 0 {
+            self.child()
+        } else {
+            let curr = if tail == Self::NO_CHILDREN {
+                self.child()
+            } else {
+                // SAFETY: tail only ever points to a linked child
+                Some(unsafe { &*tail })
+            };
+            // the tail may lag behind the latest appends
+            let mut last = curr?;
+            while let Some(next) = last.next() {
+                last = next;
+            }
+            Some(last)
+        }
+    }
+
+    /// One more than the number of siblings linked before this node, or `0`
+    /// if it isn't linked to a parent
+    pub(crate) fn position(&self) -> usize {
+        self.position.load(Relaxed)
+    }
+
+    /// Get this node's next sibling, see [`Node::children`]
     pub fn next(&self) -> Option<&Self> {
         // SAFETY: Node.next is always sound
-        unsafe { self.next.as_ref() }
+        unsafe { self.next.load(Acquire).as_ref() }
     }
 
     /// Add a child to this node
     ///
     /// # Safety
     ///
-    /// The given `node` must belong to the same arena as this one. The ptr to
-    /// the `node` must be valid.
+    /// The given `child` must point to a node in the same arena as this one,
+    /// taken from its slot, & must not have been added to any node before.
     pub(crate) unsafe fn add_child(&self, child: *mut Self) {
         // SAFETY: upheld by caller
-        debug_assert!(unsafe { ptr::eq((*child).parent, self) });
+        unsafe { self.link_child::<false>(child) }
+    }
+
+    /// Link a child into this node's children
+    ///
+    /// `PUBLISHED` should be `true` if the child is already visible to other
+    /// threads.
+    ///
+    /// # Safety
+    ///
+    /// See [`Node::add_child`]
+    #[inline]
+    unsafe fn link_child<const PUBLISHED: bool>(&self, child: *mut Self) {
+        if self.child_order() == ChildOrder::NewestFirst {
+            // SAFETY: upheld by caller
+            unsafe { self.push_front::<PUBLISHED>(child) }
+        } else {
+            // SAFETY: upheld by caller
+            unsafe { self.push_back::<PUBLISHED>(child) }
+        }
+    }
+
+    /// Link a child ahead of this node's other children
+    ///
+    /// # Safety
+    ///
+    /// See [`Node::add_child`]
+    #[inline]
+    unsafe fn push_front<const PUBLISHED: bool>(&self, child: *mut Self) {
+        let store = if PUBLISHED { Release } else { Relaxed };
+        // SAFETY: upheld by caller
+        debug_assert!(ptr::eq(unsafe { (*child).parent.load(Relaxed) }, self));
 
         let mut prev = self.child.load(Acquire);
         loop {
-            // SAFETY: upheld by caller
-            unsafe { (*child).next = prev };
+            // SAFETY: upheld by caller, prev is either null or a linked child
+            unsafe {
+                let position = prev.as_ref().map_or(0, Self::position) + 1;
+                (*child).position.store(position, store);
+                (*child).next.store(prev, store);
+            }
 
             match self
                 .child
@@ -130,160 +486,1619 @@ impl<T> Node<T> {
                 Ok(_) => break,
             }
         }
+        // only one child is ever linked onto an empty list
+        if prev.is_null() {
+            let oldest = child.map_addr(|addr| addr | Self::OLDEST);
+            self.tail.store(oldest, Release);
+        }
     }
 
-    /// Iterate over the ancestors of this node
+    /// Link a child after this node's other children
     ///
-    /// Iterator starts from this node's parent
-    pub fn ancestors(&self) -> Ancestors<'_, T> {
-        Ancestors {
-            curr: self.parent(),
-        }
-    }
+    /// # Safety
+    ///
+    /// See [`Node::add_child`], the child must also have no `next`
+    unsafe fn push_back<const PUBLISHED: bool>(&self, child: *mut Self) {
+        let store = if PUBLISHED { Release } else { Relaxed };
+        // SAFETY: upheld by caller
+        debug_assert!(unsafe { ptr::eq((*child).parent.load(Relaxed), self) });
+        // SAFETY: upheld by caller
+        debug_assert!(unsafe { (*child).next.load(Relaxed).is_null() });
 
-    /// Iterate over the children of this node
-    pub fn children(&self) -> Next<'_, T> {
-        Next { curr: self.child() }
+        loop {
+            let tail = self.tail.load(Acquire);
+            // the first child is linked from this node, the rest from their
+            // previous sibling
+            let (link, position) = if tail == Self::NO_CHILDREN {
+                (&self.child, 1)
+            } else {
+                // SAFETY: tail only ever points to a linked child
+                let tail = unsafe { &*tail };
+                (&tail.next, tail.position() + 1)
+            };
+            // SAFETY: upheld by caller
+            unsafe { (*child).position.store(position, store) };
+            match link.compare_exchange(ptr::null_mut(), child, AcqRel, Acquire) {
+                Ok(_) => {
+                    // a failure means another append already helped it along
+                    let _ = self.tail.compare_exchange(tail, child, Release, Relaxed);
+                    return;
+                }
+                Err(next) => {
+                    // tail is behind, help it forward before retrying
+                    let _ = self.tail.compare_exchange(tail, next, Release, Relaxed);
+                }
+            }
+        }
     }
 
-    /// Iterate over the next(previously added) nodes
+    /// Give an orphan a parent
     ///
-    /// Skips this node
-    pub fn iter_next(&self) -> Next<'_, T> {
-        Next { curr: self.next() }
-    }
-}
-
-/// Iterates over nodes using [`Node::next`]
-#[derive(Debug)]
-pub struct Next<'a, T> {
-    curr: Option<&'a Node<T>>,
-}
+    /// # Safety
+    ///
+    /// Both nodes must belong to the same arena, & be pointed to from their
+    /// slots. Calls to this must not race, or cycles may be created.
+    pub(crate) unsafe fn attach(parent: *mut Self, orphan: *mut Self) -> Result<(), AttachError> {
+        // SAFETY: upheld by caller
+        let (parent_node, orphan_node) = unsafe { (&*parent, &*orphan) };
+        if !orphan_node.is_orphan() {
+            return Err(AttachError::NotOrphan(orphan_node.index));
+        }
+        if ptr::eq(parent, orphan)
+            || parent_node
+                .ancestors()
+                .any(|node| ptr::eq(node, orphan_node))
+        {
+            return Err(AttachError::Cycle {
+                parent: parent_node.index,
+                orphan: orphan_node.index,
+            });
+        }
 
-impl<T> Clone for Next<'_, T> {
-    fn clone(&self) -> Self {
-        Self { curr: self.curr }
+        orphan_node
+            .parent
+            .compare_exchange(Self::ORPHAN, parent, Release, Relaxed)
+            .map_err(|_| AttachError::NotOrphan(orphan_node.index))?;
+        // SAFETY: upheld by caller, orphans are never added as a child
+        unsafe { parent_node.link_child::<true>(orphan) };
+        Ok(())
     }
-}
 
-impl<'a, T> Iterator for Next<'a, T> {
-    type Item = &'a Node<T>;
+    /// Remove this node from its parent's children, if it has a parent
+    ///
+    /// Later siblings move up a position. This node's own links are left as
+    /// they are.
+    ///
+    /// # Safety
+    ///
+    /// Nothing else may access this node's siblings or parent while this
+    /// runs.
+    pub(crate) unsafe fn unlink(&self) {
+        let Some(parent) = self.parent() else {
+            return;
+        };
+        let tail = parent.tail.load(Relaxed);
+        let position = self.position();
+        // the sibling linked just before this node, taken from its link so
+        // it can be stored again
+        let mut prev = ptr::null_mut();
+        let mut found = false;
+        let mut link = &parent.child;
+        loop {
+            let curr = link.load(Relaxed);
+            // SAFETY: links only ever point to live nodes
+            let Some(node) = (unsafe { curr.as_ref() }) else {
+                break;
+            };
+            if ptr::eq(node, self) {
+                link.store(self.next.load(Relaxed), Relaxed);
+                found = true;
+                continue;
+            }
+            if node.position() > position {
+                node.position.store(node.position() - 1, Relaxed);
+            }
+            if !found {
+                prev = curr;
+            }
+            link = &node.next;
+        }
+        debug_assert!(found);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let node = self.curr.take()?;
-        self.curr = node.next();
-        Some(node)
+        if ptr::eq(tail, self) {
+            // the last child, appended after
+            let tail = if prev.is_null() {
+                Self::NO_CHILDREN
+            } else {
+                prev
+            };
+            parent.tail.store(tail, Relaxed);
+        } else if ptr::eq(tail.map_addr(|addr| addr & !Self::OLDEST), self) {
+            // the oldest child, newest first
+            parent
+                .tail
+                .store(prev.map_addr(|addr| addr | Self::OLDEST), Relaxed);
+        }
     }
-}
-
-/// Iterates over nodes using [`Node::parent`]
-#[derive(Debug)]
-pub struct Ancestors<'a, T> {
-    curr: Option<&'a Node<T>>,
-}
 
-impl<T> Clone for Ancestors<'_, T> {
-    fn clone(&self) -> Self {
-        Self { curr: self.curr }
+    /// Move a node under a new parent, linking it as a new child would be
+    ///
+    /// Depths are updated through the node's subtree.
+    ///
+    /// # Safety
+    ///
+    /// Both nodes must belong to the same arena, & be pointed to from their
+    /// slots. `parent` must not be in `node`'s subtree, & nothing else may
+    /// access the tree while this runs.
+    pub(crate) unsafe fn move_to(node: *mut Self, parent: *mut Self) {
+        // SAFETY: upheld by caller
+        let (node_ref, parent_ref) = unsafe { (&*node, &*parent) };
+        // SAFETY: upheld by caller
+        unsafe { node_ref.unlink() };
+        node_ref.next.store(ptr::null_mut(), Relaxed);
+        node_ref.position.store(0, Relaxed);
+        node_ref.parent.store(parent, Relaxed);
+        // SAFETY: upheld by caller, the node was just unlinked
+        unsafe { parent_ref.link_child::<true>(node) };
+        node_ref.reset_depths();
     }
-}
 
-impl<'a, T> Iterator for Ancestors<'a, T> {
-    type Item = &'a Node<T>;
+    /// Exchange two nodes' places in the tree, each taking the other's
+    /// parent & spot among its siblings
+    ///
+    /// Depths are updated through both subtrees.
+    ///
+    /// # Safety
+    ///
+    /// Both nodes must belong to the same arena, & be pointed to from their
+    /// slots. Neither may be in the other's subtree, & nothing else may
+    /// access the tree while this runs.
+    pub(crate) unsafe fn swap(a: *mut Self, b: *mut Self) {
+        // SAFETY: upheld by caller
+        let (a_ref, b_ref) = unsafe { (&*a, &*b) };
+        let (a_parent, b_parent) = (a_ref.parent(), b_ref.parent());
+        let shared = a_parent.is_some_and(|p| b_parent.is_some_and(|q| ptr::eq(p, q)));
+        let parents = [a_parent, b_parent.filter(|_| !shared)];
+        for parent in parents.into_iter().flatten() {
+            let mut children = parent.child_links();
+            for child in &mut children {
+                if *child == a {
+                    *child = b;
+                } else if *child == b {
+                    *child = a;
+                }
+            }
+            // SAFETY: upheld by caller
+            unsafe { parent.relink_children(&children) };
+        }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let node = self.curr.take()?;
-        self.curr = node.parent();
-        Some(node)
+        let (a_up, b_up) = (a_ref.parent.load(Relaxed), b_ref.parent.load(Relaxed));
+        a_ref.parent.store(b_up, Relaxed);
+        b_ref.parent.store(a_up, Relaxed);
+        for node in [a_ref, b_ref] {
+            if node.parent().is_none() {
+                node.next.store(ptr::null_mut(), Relaxed);
+                node.position.store(0, Relaxed);
+            }
+            node.reset_depths();
+        }
     }
-}
-
-/// A non-owning handle to a node
-///
-/// Uses an [`Arc`] to an [`Arena`] to safely forego a lifetime
-pub struct Handle<T> {
-    node: NonNull<Node<T>>,
-    arena: Arc<Arena<T>>,
-}
 
-impl<T: fmt::Debug> fmt::Debug for Handle<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.node().fmt(f)
+    /// Store the depths through this node's subtree, after it has moved
+    fn reset_depths(&self) {
+        let base = if self.is_root() {
+            Some(0)
+        } else {
+            let parent = self.parent().and_then(|parent| parent.resolve_depth().ok());
+            parent.map(|depth| depth + 1)
+        };
+        for (depth, node) in self.descendants_with_depth() {
+            let depth = base.map_or(Self::UNKNOWN_DEPTH, |base| base + depth);
+            node.depth.store(depth, Relaxed);
+        }
     }
-}
 
-// SAFETY: mirrors Arc
-unsafe impl<T: Send + Sync> Send for Handle<T> {}
-unsafe impl<T: Send + Sync> Sync for Handle<T> {}
+    /// Relink this node's children so they are given in sorted order
+    ///
+    /// The sort is stable, & positions are renumbered to match the new order.
+    ///
+    /// # Safety
+    ///
+    /// Nothing else may access this node or its children while this runs.
+    pub(crate) unsafe fn sort_children_by(&self, mut cmp: impl FnMut(&Self, &Self) -> Ordering) {
+        let mut children = self.child_links();
+        // SAFETY: links only ever point to live nodes
+        children.sort_by(|&a, &b| unsafe { cmp(&*a, &*b) });
+        // SAFETY: upheld by caller
+        unsafe { self.relink_children(&children) };
+    }
 
-impl<T> Clone for Handle<T> {
-    fn clone(&self) -> Self {
-        Self {
-            node: self.node,
-            arena: self.arena.clone(),
+    /// Collect this node's children, taken from their links so they can be
+    /// stored again
+    fn child_links(&self) -> Vec<*mut Self> {
+        let mut children = Vec::new();
+        let mut curr = self.child.load(Relaxed);
+        while !curr.is_null() {
+            children.push(curr);
+            // SAFETY: links only ever point to live nodes
+            curr = unsafe { (*curr).next.load(Relaxed) };
         }
+        children
     }
-}
 
-impl<T> Handle<T> {
-    /// create a new handle
+    /// Relink this node's children to be given in the order of `children`,
+    /// renumbering their positions
     ///
     /// # Safety
     ///
-    /// The given node should be obtained from the given [`Arena`]
-    pub(crate) unsafe fn new(node: &Node<T>, arena: &Arc<Arena<T>>) -> Self {
-        debug_assert!(
-            arena.contains(node),
-            "given node does not belong to this arena"
-        );
-        Self {
-            node: NonNull::from(node),
-            arena: arena.clone(),
+    /// `children` must be pointed to from their slots & have this node as
+    /// their parent, or be about to. Nothing else may access this node or
+    /// its children while this runs.
+    unsafe fn relink_children(&self, children: &[*mut Self]) {
+        if children.is_empty() {
+            return;
         }
+        let order = self.child_order();
+        let len = children.len();
+        let mut link = &self.child;
+        for (i, &child) in children.iter().enumerate() {
+            link.store(child, Relaxed);
+            // SAFETY: links only ever point to live nodes
+            let child = unsafe { &*child };
+            let position = match order {
+                ChildOrder::NewestFirst => len - i,
+                ChildOrder::InsertionOrder => i + 1,
+            };
+            child.position.store(position, Relaxed);
+            link = &child.next;
+        }
+        link.store(ptr::null_mut(), Relaxed);
+
+        let last = children[len - 1];
+        let tail = match order {
+            ChildOrder::NewestFirst => last.map_addr(|addr| addr | Self::OLDEST),
+            ChildOrder::InsertionOrder => last,
+        };
+        self.tail.store(tail, Relaxed);
     }
 
-    /// Get the underlying arena
-    pub const fn arena(&self) -> &Arc<Arena<T>> {
-        &self.arena
+    /// Iterate over the ancestors of this node
+    ///
+    /// Iterator starts from this node's parent
+    pub fn ancestors(&self) -> Ancestors<'_, T> {
+        Ancestors {
+            curr: self.parent(),
+        }
     }
 
-    /// Get this handle's node
-    pub const fn node(&self) -> &Node<T> {
-        // SAFETY: when correctly created(according to Handle::new)
-        // this should always be valid
-        unsafe { self.node.as_ref() }
+    /// Collect the path from this node's root down to this node
+    ///
+    /// The reverse of [`Node::ancestors`], with this node last. An unattached
+    /// orphan's subtree is measured from the orphan.
+    pub fn path(&self) -> Vec<&Self> {
+        let mut path = Vec::with_capacity(self.depth() + 1);
+        path.push(self);
+        path.extend(self.ancestors());
+        path.reverse();
+        path
     }
-}
 
-impl<T> std::ops::Deref for Handle<T> {
-    type Target = Node<T>;
+    /// Iterate over this node's subtree in pre-order, starting with this node
+    ///
+    /// Children are visited in the order of [`Node::children`]. Only the
+    /// links are followed, so nothing is allocated. Each node's first child
+    /// is read as the walk moves past it, see [`Node::descendants_snapshot`],
+    /// & subtrees can be left out with [`Descendants::skip_subtree`].
+    pub const fn descendants(&self) -> Descendants<'_, T> {
+        Descendants {
+            root: self,
+            last: None,
+            depth: 0,
+            skip: false,
+            done: false,
+        }
+    }
 
-    fn deref(&self) -> &Self::Target {
-        self.node()
+    /// Find the first node in this node's subtree that matches `pred`
+    ///
+    /// Nodes are checked in the order of [`Node::descendants`], starting with
+    /// this node.
+    pub fn find_descendant(&self, mut pred: impl FnMut(&Self) -> bool) -> Option<&Self> {
+        self.descendants().find(|node| pred(node))
     }
-}
+
+    /// Iterate over this node's subtree in pre-order, along with each node's
+    /// depth below this one
+    ///
+    /// This node is given first, at depth `0`. Depths are tracked as the
+    /// links are followed, see [`Node::descendants`].
+    pub const fn descendants_with_depth(&self) -> DescendantsWithDepth<'_, T> {
+        DescendantsWithDepth(self.descendants())
+    }
+
+    /// Walk this node's subtree depth first, opening & closing each node
+    ///
+    /// Every node is opened before its children & closed after them, so the
+    /// edges nest like tags. Children are visited in the order of
+    /// [`Node::children`], & only the links are followed, so nothing is
+    /// allocated.
+    pub const fn traverse(&self) -> Traverse<'_, T> {
+        Traverse {
+            root: self,
+            edge: None,
+            done: false,
+        }
+    }
+
+    /// Iterate over the nodes after this one in document order, leaving out
+    /// its subtree
+    ///
+    /// Document order is the pre-order of [`Node::descendants`] over the
+    /// whole tree, so this gives the later siblings of this node & of each
+    /// of its ancestors, each followed by their subtree.
+    pub fn following(&self) -> Following<'_, T> {
+        Following {
+            curr: Self::next_outside(self),
+        }
+    }
+
+    /// Iterate over the nodes before this one in document order, leaving out
+    /// its ancestors
+    ///
+    /// Nodes are given nearest first, so in reverse document order. Siblings
+    /// are only linked forwards, so each step back to an earlier sibling
+    /// walks the siblings before it, see [`Cursor::goto_prev_sibling`].
+    pub const fn preceding(&self) -> Preceding<'_, T> {
+        Preceding {
+            curr: Some(self),
+            ancestor: self,
+        }
+    }
+
+    /// The first node after `node`'s subtree in document order
+    fn next_outside(mut node: &Self) -> Option<&Self> {
+        loop {
+            if let Some(next) = node.next() {
+                return Some(next);
+            }
+            node = node.parent()?;
+        }
+    }
+
+    /// Iterate over this node's subtree level by level, starting with this
+    /// node
+    ///
+    /// Each level is given in the order its parents were, with siblings
+    /// in the order of [`Node::children`]. Holds a queue of the levels'
+    /// remaining sibling lists.
+    pub fn breadth_first(&self) -> BreadthFirst<'_, T> {
+        BreadthFirst {
+            queue: VecDeque::from([Next::new(Some(self), 1)]),
+        }
+    }
+
+    /// Iterate over this node's subtree in post-order, ending with this node
+    ///
+    /// Each node is given after all of its children, which are visited in
+    /// insertion order, as in [`Node::fold_post_order`]. The subtree is walked
+    /// using an explicit stack.
+    pub fn post_order(&self) -> PostOrder<'_, T> {
+        PostOrder {
+            stack: vec![(self, false)],
+        }
+    }
+
+    /// Count the nodes in this node's subtree, including this node
+    ///
+    /// Walks the subtree, see [`Arena::subtree_len`] for an O(1) count.
+    pub fn subtree_len(&self) -> usize {
+        self.descendants().count()
+    }
+
+    /// Iterate over the children of this node
+    ///
+    /// Children are given in the arena's [`ChildOrder`], newest first by
+    /// default. Use [`Iterator::rev`] for the other order. Only the children
+    /// linked when this is called are given, which are counted in `O(1)`.
+    pub fn children(&self) -> Next<'_, T> {
+        match self.child_order() {
+            ChildOrder::NewestFirst => Next::newest_first(self.child()),
+            ChildOrder::InsertionOrder => {
+                // read the count first, children are only ever appended
+                let len = self.last_child().map_or(0, Self::position);
+                Next::new(self.child(), len)
+            }
+        }
+    }
+
+    /// Count the children of this node in `O(1)`
+    ///
+    /// Children are counted from the position each records as it is linked,
+    /// see [`Node::children`].
+    pub fn child_count(&self) -> usize {
+        self.children().len()
+    }
+
+    /// Iterate over the children of this node in the given order
+    ///
+    /// Children linked in the other order are collected first.
+    pub(crate) fn children_in(&self, order: ChildOrder) -> impl Iterator<Item = &Self> {
+        let (linked, reversed) = if self.child_order() == order {
+            (Some(self.children()), None)
+        } else {
+            (None, Some(self.children().rev()))
+        };
+        linked
+            .into_iter()
+            .flatten()
+            .chain(reversed.into_iter().flatten())
+    }
+
+    /// Get the children of this node in insertion order, if it has exactly `N`
+    pub fn children_array<const N: usize>(&self) -> Option<[&Self; N]> {
+        let mut children = self.children();
+        let mut array = [self; N];
+        match self.child_order() {
+            ChildOrder::NewestFirst => {
+                for slot in array.iter_mut().rev() {
+                    *slot = children.next()?;
+                }
+            }
+            ChildOrder::InsertionOrder => {
+                for slot in &mut array {
+                    *slot = children.next()?;
+                }
+            }
+        }
+        children.next().is_none().then_some(array)
+    }
+
+    /// Get the only child of this node
+    ///
+    /// Returns `None` if this node has no children or more than one.
+    pub fn single_child(&self) -> Option<&Self> {
+        self.children_array().map(|[child]| child)
+    }
+
+    /// Iterate over the values of this node's children
+    ///
+    /// See [`Node::children`]
+    pub fn child_values(&self) -> impl FusedIterator<Item = &T> + Clone {
+        self.children().map(|node| &node.value)
+    }
+
+    /// Collect the children of this node
+    ///
+    /// The first child is read once, so children pushed after this is called
+    /// are excluded. See [`Arena::snapshot`] for a view of the whole arena.
+    pub fn children_snapshot(&self) -> Vec<&Self> {
+        self.children().collect()
+    }
+
+    /// Collect this node's subtree in pre-order, starting with this node
+    ///
+    /// Each node's first child is read once, as it is visited. Nodes pushed
+    /// under already visited nodes are excluded, while those pushed under
+    /// nodes yet to be visited may be included. See [`Arena::snapshot`] for a
+    /// consistent view.
+    pub fn descendants_snapshot(&self) -> Vec<&Self> {
+        let mut nodes = Vec::new();
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            nodes.push(node);
+            let start = stack.len();
+            stack.extend(node.children());
+            // visit children in the order they are linked in
+            stack[start..].reverse();
+        }
+        nodes
+    }
+
+    /// Iterate over the values of this node's subtree, starting with this node
+    ///
+    /// See [`Node::descendants`]
+    pub fn descendant_values(&self) -> impl FusedIterator<Item = &T> + Clone {
+        self.descendants().map(|node| &node.value)
+    }
+
+    /// Iterate over the siblings after this one, see [`Node::children`]
+    ///
+    /// Skips this node
+    pub fn iter_next(&self) -> Next<'_, T> {
+        match self.child_order() {
+            ChildOrder::NewestFirst => Next::newest_first(self.next()),
+            ChildOrder::InsertionOrder => {
+                let last = self.parent().and_then(Self::last_child);
+                let len = last.map_or(0, |last| last.position() - self.position());
+                // unlinked if it has no next, & so it has no position yet
+                let curr = self.next();
+                Next::new(curr, curr.map_or(0, |_| len))
+            }
+        }
+    }
+
+    /// Aggregate this node's subtree bottom-up
+    ///
+    /// `f` is called once per node, after its children, with the results of
+    /// its children in insertion order. The subtree is walked using an
+    /// explicit stack, so deep trees can't overflow the stack.
+    pub fn fold_post_order<'a, A>(
+        &'a self,
+        mut f: impl FnMut(&'a Self, vec::Drain<'_, A>) -> A,
+    ) -> A {
+        // each node & its unvisited children, along with how many results
+        // its children have pushed to `results`
+        let mut stack = vec![(self, self.children(), 0)];
+        let mut results = Vec::new();
+        while let Some((node, children, pushed)) = stack.last_mut() {
+            if let Some(child) = children.next() {
+                *pushed += 1;
+                stack.push((child, child.children(), 0));
+                continue;
+            }
+
+            let (node, start) = (*node, results.len() - *pushed);
+            stack.pop();
+            if node.child_order() == ChildOrder::NewestFirst {
+                results[start..].reverse();
+            }
+            let result = f(node, results.drain(start..));
+            results.push(result);
+        }
+        let Some(result) = results.pop() else {
+            unreachable!("the root always pushes a result")
+        };
+        result
+    }
+
+    /// Aggregate this node's children, in insertion order
+    pub fn reduce_children<A>(&self, init: A, f: impl FnMut(A, &Self) -> A) -> A {
+        self.children_in(ChildOrder::InsertionOrder).fold(init, f)
+    }
+}
+
+/// Iterates over nodes using [`Node::next`]
+///
+/// Nodes are only singly linked, so iterating from the back first collects
+/// the remaining nodes.
+#[derive(Debug)]
+pub struct Next<'a, T> {
+    curr: Option<&'a Node<T>>,
+    /// The number of nodes left to give from `curr`
+    remaining: usize,
+    /// The remaining nodes, once iterated from the back
+    collected: Option<VecDeque<&'a Node<T>>>,
+}
+
+impl<T> Clone for Next<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            curr: self.curr,
+            remaining: self.remaining,
+            collected: self.collected.clone(),
+        }
+    }
+}
+
+impl<'a, T> Next<'a, T> {
+    /// Give `remaining` nodes, starting from `curr`
+    const fn new(curr: Option<&'a Node<T>>, remaining: usize) -> Self {
+        Self {
+            curr,
+            remaining,
+            collected: None,
+        }
+    }
+
+    /// Give every node after `curr`, which can't grow when linking newest
+    /// first
+    fn newest_first(curr: Option<&'a Node<T>>) -> Self {
+        Self::new(curr, curr.map_or(0, Node::position))
+    }
+}
+
+impl<'a, T> Iterator for Next<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(collected) = &mut self.collected {
+            return collected.pop_front();
+        }
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = self.curr.take()?;
+        self.remaining -= 1;
+        self.curr = node.next();
+        Some(node)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Next<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.collected
+            .get_or_insert_with(|| {
+                let remaining = std::mem::take(&mut self.remaining);
+                Next::new(self.curr.take(), remaining).collect()
+            })
+            .pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Next<'_, T> {
+    fn len(&self) -> usize {
+        self.collected
+            .as_ref()
+            .map_or(self.remaining, VecDeque::len)
+    }
+}
+
+impl<T> FusedIterator for Next<'_, T> {}
+
+/// Iterates over nodes using [`Node::parent`]
+#[derive(Debug)]
+pub struct Ancestors<'a, T> {
+    curr: Option<&'a Node<T>>,
+}
+
+impl<T> Clone for Ancestors<'_, T> {
+    fn clone(&self) -> Self {
+        Self { curr: self.curr }
+    }
+}
+
+impl<'a, T> Iterator for Ancestors<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.curr.take()?;
+        self.curr = node.parent();
+        Some(node)
+    }
+}
+
+impl<T> FusedIterator for Ancestors<'_, T> {}
+
+/// Iterates over a subtree in pre-order
+///
+/// Created by [`Node::descendants`]
+#[derive(Debug)]
+pub struct Descendants<'a, T> {
+    root: &'a Node<T>,
+    /// The last node given, [`None`] before the first
+    last: Option<&'a Node<T>>,
+    /// The depth of `last` below `root`
+    depth: usize,
+    /// Whether to skip the children of `last`
+    skip: bool,
+    done: bool,
+}
+
+impl<T> Clone for Descendants<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root,
+            last: self.last,
+            depth: self.depth,
+            skip: self.skip,
+            done: self.done,
+        }
+    }
+}
+
+impl<'a, T> Descendants<'a, T> {
+    /// Skip the subtree of the node last given
+    ///
+    /// Its children are not visited, & the walk moves on to its next sibling
+    /// or further up. Does nothing before the first node is given.
+    ///
+    /// ```
+    /// # use silva::Arena;
+    /// let arena = Arena::new();
+    /// let root = arena.push(None, 0);
+    /// let skipped = arena.push(root, 1);
+    /// arena.push(skipped, 2);
+    /// arena.push(root, 3);
+    ///
+    /// let mut walk = root.descendants();
+    /// let mut visited = Vec::new();
+    /// while let Some(node) = walk.next() {
+    ///     visited.push(node.value);
+    ///     if node.value == 1 {
+    ///         walk.skip_subtree();
+    ///     }
+    /// }
+    /// assert_eq!(visited, [0, 3, 1]);
+    /// ```
+    pub const fn skip_subtree(&mut self) {
+        self.skip = self.last.is_some();
+    }
+
+    /// Step to the next node, giving it along with its depth
+    fn step(&mut self) -> Option<(usize, &'a Node<T>)> {
+        if self.done {
+            return None;
+        }
+        let Some(last) = self.last else {
+            self.last = Some(self.root);
+            return Some((0, self.root));
+        };
+        let child = if self.skip { None } else { last.child() };
+        self.skip = false;
+        if let Some(child) = child {
+            self.last = Some(child);
+            self.depth += 1;
+            return Some((self.depth, child));
+        }
+        // climb until a node with a sibling left, stopping at the root
+        let mut up = last;
+        while !ptr::eq(up, self.root) {
+            if let Some(next) = up.next() {
+                self.last = Some(next);
+                return Some((self.depth, next));
+            }
+            let Some(parent) = up.parent() else {
+                break;
+            };
+            up = parent;
+            self.depth -= 1;
+        }
+        self.done = true;
+        None
+    }
+}
+
+impl<'a, T> Iterator for Descendants<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step().map(|(_, node)| node)
+    }
+}
+
+impl<T> FusedIterator for Descendants<'_, T> {}
+
+/// Iterates over a subtree in pre-order, along with each node's depth
+///
+/// Created by [`Node::descendants_with_depth`]
+#[derive(Debug)]
+pub struct DescendantsWithDepth<'a, T>(Descendants<'a, T>);
+
+impl<T> Clone for DescendantsWithDepth<'_, T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> DescendantsWithDepth<'_, T> {
+    /// Skip the subtree of the node last given
+    ///
+    /// See [`Descendants::skip_subtree`]
+    pub const fn skip_subtree(&mut self) {
+        self.0.skip_subtree();
+    }
+}
+
+impl<'a, T> Iterator for DescendantsWithDepth<'a, T> {
+    type Item = (usize, &'a Node<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.step()
+    }
+}
+
+impl<T> FusedIterator for DescendantsWithDepth<'_, T> {}
+
+/// An event of a depth first walk
+///
+/// Given by [`Traverse`]
+#[derive(Debug)]
+pub enum Edge<'a, T> {
+    /// The walk enters a node, before any of its children
+    Open(&'a Node<T>),
+    /// The walk leaves a node, after all of its children
+    Close(&'a Node<T>),
+}
+
+impl<T> Clone for Edge<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Edge<'_, T> {}
+
+/// Walks a subtree depth first, opening & closing each node
+///
+/// Created by [`Node::traverse`]
+#[derive(Debug)]
+pub struct Traverse<'a, T> {
+    root: &'a Node<T>,
+    /// The last edge given
+    edge: Option<Edge<'a, T>>,
+    done: bool,
+}
+
+impl<T> Clone for Traverse<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root,
+            edge: self.edge,
+            done: self.done,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Traverse<'a, T> {
+    type Item = Edge<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let edge = match self.edge {
+            None => Edge::Open(self.root),
+            Some(Edge::Open(node)) => node.child().map_or(Edge::Close(node), Edge::Open),
+            Some(Edge::Close(node)) if ptr::eq(node, self.root) => {
+                self.done = true;
+                return None;
+            }
+            Some(Edge::Close(node)) => match (node.next(), node.parent()) {
+                (Some(next), _) => Edge::Open(next),
+                (None, Some(parent)) => Edge::Close(parent),
+                (None, None) => unreachable!("only the root has no parent within the walk"),
+            },
+        };
+        self.edge = Some(edge);
+        Some(edge)
+    }
+}
+
+impl<T> FusedIterator for Traverse<'_, T> {}
+
+/// Iterates over the nodes after a node in document order
+///
+/// Created by [`Node::following`]
+#[derive(Debug)]
+pub struct Following<'a, T> {
+    curr: Option<&'a Node<T>>,
+}
+
+impl<T> Clone for Following<'_, T> {
+    fn clone(&self) -> Self {
+        Self { curr: self.curr }
+    }
+}
+
+impl<'a, T> Iterator for Following<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.curr.take()?;
+        self.curr = node.child().or_else(|| Node::next_outside(node));
+        Some(node)
+    }
+}
+
+impl<T> FusedIterator for Following<'_, T> {}
+
+/// Iterates over the nodes before a node in reverse document order
+///
+/// Created by [`Node::preceding`]
+#[derive(Debug)]
+pub struct Preceding<'a, T> {
+    /// The last node given, or the starting node
+    curr: Option<&'a Node<T>>,
+    /// The highest of the starting node & its ancestors reached so far
+    ancestor: &'a Node<T>,
+}
+
+impl<T> Clone for Preceding<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            curr: self.curr,
+            ancestor: self.ancestor,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Preceding<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let curr = self.curr?;
+            let mut cursor = Cursor::new(curr);
+            if cursor.goto_prev_sibling() {
+                // the last node of the earlier sibling's subtree
+                while cursor.goto_last_child() {}
+                self.curr = Some(cursor.node());
+                return self.curr;
+            }
+            self.curr = curr.parent();
+            let parent = self.curr?;
+            // ancestors are left out
+            if !self.ancestor.parent().is_some_and(|a| ptr::eq(a, parent)) {
+                return self.curr;
+            }
+            self.ancestor = parent;
+        }
+    }
+}
+
+impl<T> FusedIterator for Preceding<'_, T> {}
+
+/// Iterates over a subtree level by level
+///
+/// Created by [`Node::breadth_first`]
+#[derive(Debug)]
+pub struct BreadthFirst<'a, T> {
+    /// The sibling lists left to visit, in order
+    queue: VecDeque<Next<'a, T>>,
+}
+
+impl<T> Clone for BreadthFirst<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for BreadthFirst<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let siblings = self.queue.front_mut()?;
+            let Some(node) = siblings.next() else {
+                self.queue.pop_front();
+                continue;
+            };
+            if node.child().is_some() {
+                self.queue.push_back(node.children());
+            }
+            return Some(node);
+        }
+    }
+}
+
+impl<T> FusedIterator for BreadthFirst<'_, T> {}
+
+/// Iterates over a subtree in post-order
+///
+/// Created by [`Node::post_order`]
+#[derive(Debug)]
+pub struct PostOrder<'a, T> {
+    /// Nodes left to visit, & whether their children have been pushed
+    stack: Vec<(&'a Node<T>, bool)>,
+}
+
+impl<T> Clone for PostOrder<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            stack: self.stack.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for PostOrder<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, expanded) = self.stack.pop()?;
+            if expanded || node.child().is_none() {
+                return Some(node);
+            }
+            self.stack.push((node, true));
+            // pushed newest first, so the oldest is popped first
+            self.stack.extend(
+                node.children_in(ChildOrder::NewestFirst)
+                    .map(|child| (child, false)),
+            );
+        }
+    }
+}
+
+impl<T> FusedIterator for PostOrder<'_, T> {}
+
+/// A non-owning handle to a node
+///
+/// Uses an [`Arc`] to an [`Arena`] to safely forego a lifetime
+pub struct Handle<T> {
+    node: NonNull<Node<T>>,
+    arena: Arc<Arena<T>>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.node().fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.node().fmt(f)
+    }
+}
+
+// SAFETY: mirrors Arc
+unsafe impl<T: Send + Sync> Send for Handle<T> {}
+unsafe impl<T: Send + Sync> Sync for Handle<T> {}
+
+/// Handles are equal if they point to the same node, whatever its value
+///
+/// They hash by the node's address too, so can be used as keys, though clippy
+/// warns of the arena's interior mutability.
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> std::hash::Hash for Handle<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.node.hash(state);
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node,
+            arena: self.arena.clone(),
+        }
+    }
+}
+
+impl<T> Handle<T> {
+    /// create a new handle
+    ///
+    /// # Safety
+    ///
+    /// The given node should be obtained from the given [`Arena`]
+    pub(crate) unsafe fn new(node: &Node<T>, arena: &Arc<Arena<T>>) -> Self {
+        debug_assert!(
+            arena.contains(node),
+            "given node does not belong to this arena"
+        );
+        Self {
+            node: NonNull::from(node),
+            arena: arena.clone(),
+        }
+    }
+
+    /// Get the underlying arena
+    pub const fn arena(&self) -> &Arc<Arena<T>> {
+        &self.arena
+    }
+
+    /// Get this handle's node
+    pub const fn node(&self) -> &Node<T> {
+        // SAFETY: when correctly created(according to Handle::new)
+        // this should always be valid
+        unsafe { self.node.as_ref() }
+    }
+
+    /// returns `true` if both handles point into the same arena
+    pub fn same_arena(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.arena, &other.arena)
+    }
+
+    /// Get a pointer to the underlying arena
+    ///
+    /// This is only meant to identify the arena, such as for use as a map
+    /// key, and is the same for every handle into it.
+    pub fn arena_ptr(&self) -> *const Arena<T> {
+        Arc::as_ptr(&self.arena)
+    }
+
+    /// Get the number of strong references to the underlying arena
+    ///
+    /// This counts every handle into the arena, along with any other
+    /// [`Arc`]s to it.
+    pub fn strong_count(&self) -> usize {
+        Arc::strong_count(&self.arena)
+    }
+
+    /// Create a [`WeakHandle`] to this handle's node
+    pub fn downgrade(&self) -> WeakHandle<T> {
+        WeakHandle {
+            node: self.node,
+            arena: Arc::downgrade(&self.arena),
+        }
+    }
+
+    /// Get a handle to a node linked to this one
+    fn linked(&self, node: &Node<T>) -> Self {
+        // SAFETY: links only ever point to nodes of the same arena
+        unsafe { Self::new(node, &self.arena) }
+    }
+
+    /// Get a handle to this node's parent, see [`Node::parent`]
+    pub fn parent_handle(&self) -> Option<Self> {
+        self.node().parent().map(|node| self.linked(node))
+    }
+
+    /// Get a handle to this node's first child, see [`Node::first_child`]
+    pub fn first_child_handle(&self) -> Option<Self> {
+        self.node().first_child().map(|node| self.linked(node))
+    }
+
+    /// Get a handle to this node's last child, see [`Node::last_child`]
+    pub fn last_child_handle(&self) -> Option<Self> {
+        self.node().last_child().map(|node| self.linked(node))
+    }
+
+    /// Get a handle to this node's next sibling, see [`Node::next`]
+    pub fn next_handle(&self) -> Option<Self> {
+        self.node().next().map(|node| self.linked(node))
+    }
+
+    /// Iterate over handles to this node's children, see [`Node::children`]
+    pub fn children_handles(&self) -> Handles<'_, T, Next<'_, T>> {
+        Handles {
+            nodes: self.node().children(),
+            arena: &self.arena,
+        }
+    }
+
+    /// Iterate over handles to this node's ancestors, see
+    /// [`Node::ancestors`]
+    pub fn ancestor_handles(&self) -> Handles<'_, T, Ancestors<'_, T>> {
+        Handles {
+            nodes: self.node().ancestors(),
+            arena: &self.arena,
+        }
+    }
+}
+
+/// Turns an iterator over a handle's linked nodes into one over their
+/// handles
+///
+/// Created by [`Handle::children_handles`] & [`Handle::ancestor_handles`]
+pub struct Handles<'a, T, I> {
+    nodes: I,
+    arena: &'a Arc<Arena<T>>,
+}
+
+impl<T, I: fmt::Debug> fmt::Debug for Handles<'_, T, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Handles")
+            .field("nodes", &self.nodes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, I: Clone> Clone for Handles<'_, T, I> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            arena: self.arena,
+        }
+    }
+}
+
+impl<'a, T: 'a, I: Iterator<Item = &'a Node<T>>> Iterator for Handles<'a, T, I> {
+    type Item = Handle<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: the nodes are linked to a node of this arena
+        self.nodes
+            .next()
+            .map(|node| unsafe { Handle::new(node, self.arena) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<'a, T: 'a, I: DoubleEndedIterator<Item = &'a Node<T>>> DoubleEndedIterator
+    for Handles<'a, T, I>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        // SAFETY: the nodes are linked to a node of this arena
+        self.nodes
+            .next_back()
+            .map(|node| unsafe { Handle::new(node, self.arena) })
+    }
+}
+
+impl<'a, T: 'a, I: ExactSizeIterator<Item = &'a Node<T>>> ExactSizeIterator for Handles<'a, T, I> {}
+
+impl<'a, T: 'a, I: FusedIterator<Item = &'a Node<T>>> FusedIterator for Handles<'a, T, I> {}
+
+/// A handle to a node that doesn't keep its [`Arena`] alive
+///
+/// Created by [`Handle::downgrade`], & upgraded back into a [`Handle`] for
+/// as long as the arena is alive.
+pub struct WeakHandle<T> {
+    node: NonNull<Node<T>>,
+    arena: Weak<Arena<T>>,
+}
+
+impl<T> fmt::Debug for WeakHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakHandle")
+            .field("alive", &(self.strong_count() != 0))
+            .finish_non_exhaustive()
+    }
+}
+
+// SAFETY: mirrors Weak
+unsafe impl<T: Send + Sync> Send for WeakHandle<T> {}
+unsafe impl<T: Send + Sync> Sync for WeakHandle<T> {}
+
+impl<T> Clone for WeakHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node,
+            arena: self.arena.clone(),
+        }
+    }
+}
+
+impl<T> WeakHandle<T> {
+    /// Get a [`Handle`] to the node, if its arena is still alive
+    pub fn upgrade(&self) -> Option<Handle<T>> {
+        let arena = self.arena.upgrade()?;
+        // Arc::get_mut fails while a Weak is around, so the arena is only
+        // mutated once moved out of its Arc, after which upgrading fails
+        Some(Handle {
+            node: self.node,
+            arena,
+        })
+    }
+
+    /// Get the number of strong references to the underlying arena
+    ///
+    /// This is `0` once the arena has been dropped.
+    pub fn strong_count(&self) -> usize {
+        self.arena.strong_count()
+    }
+}
+
+impl<T> std::borrow::Borrow<Node<T>> for Handle<T> {
+    fn borrow(&self) -> &Node<T> {
+        self.node()
+    }
+}
+
+impl<T> std::ops::Deref for Handle<T> {
+    type Target = Node<T>;
+
+    fn deref(&self) -> &Self::Target {
+        self.node()
+    }
+}
 
 /// A recursively printing wrapper over a node
-pub struct DebugNode<'a, T>(pub &'a Node<T>);
+///
+/// The alternate flag (`{:#?}`) prints an outline instead, one node per line
+/// with children indented below their parent.
+///
+/// Nodes are walked using an explicit stack, so deep trees can't overflow the
+/// stack. Children beyond [`DebugNode::max_children`], or below
+/// [`DebugNode::max_depth`], are elided with a `… +N more` entry.
+pub struct DebugNode<'a, T> {
+    node: &'a Node<T>,
+    max_depth: usize,
+    max_children: usize,
+}
+
+impl<'a, T> DebugNode<'a, T> {
+    const fn new(node: &'a Node<T>) -> Self {
+        Self {
+            node,
+            max_depth: usize::MAX,
+            max_children: usize::MAX,
+        }
+    }
+
+    /// Only print nodes up to `depth` levels below this one
+    #[must_use]
+    pub const fn max_depth(self, depth: usize) -> Self {
+        Self {
+            max_depth: depth,
+            ..self
+        }
+    }
+
+    /// Only print the first `children` children of each node
+    #[must_use]
+    pub const fn max_children(self, children: usize) -> Self {
+        Self {
+            max_children: children,
+            ..self
+        }
+    }
+}
+
+/// How a [`DebugNode`] writes each node
+struct Style<T> {
+    /// Write a node before its children, given whether it has any
+    open: fn(&Node<T>, bool, &mut fmt::Formatter<'_>) -> fmt::Result,
+    /// Write the end of a node, given whether it has children
+    close: fn(bool, &mut fmt::Formatter<'_>) -> fmt::Result,
+}
+
+impl<T> DebugNode<'_, T> {
+    /// Write a node, returning its children if they are to be written next
+    fn open<'a>(
+        &self,
+        style: &Style<T>,
+        node: &'a Node<T>,
+        depth: usize,
+        f: &mut fmt::Formatter<'_>,
+    ) -> Result<Option<Next<'a, T>>, fmt::Error> {
+        if f.alternate() {
+            if depth != 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{:indent$}", "", indent = depth * 4)?;
+        }
+
+        let parent = node.child().is_some();
+        (style.open)(node, parent, f)?;
+        if !parent {
+            (style.close)(false, f)?;
+            return Ok(None);
+        }
+        if depth >= self.max_depth {
+            Self::elide(node.children().count(), depth + 1, true, f)?;
+            (style.close)(true, f)?;
+            return Ok(None);
+        }
+        Ok(Some(node.children()))
+    }
+
+    /// Write the entry standing in for `n` elided nodes
+    fn elide(n: usize, depth: usize, first: bool, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "\n{:indent$}… +{n} more", "", indent = depth * 4)
+        } else if first {
+            write!(f, "… +{n} more")
+        } else {
+            write!(f, ", … +{n} more")
+        }
+    }
+
+    /// Write every node, using an explicit stack
+    fn write(&self, style: &Style<T>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the children left to print at each level, & how many were printed
+        let mut stack: Vec<(Next<'_, T>, usize)> = Vec::new();
+        if let Some(children) = self.open(style, self.node, 0, f)? {
+            stack.push((children, 0));
+        }
+
+        loop {
+            let depth = stack.len();
+            let Some((children, shown)) = stack.last_mut() else {
+                break;
+            };
+            match children.next() {
+                Some(child) if *shown < self.max_children => {
+                    if *shown != 0 && !f.alternate() {
+                        f.write_str(", ")?;
+                    }
+                    *shown += 1;
+                    if let Some(children) = self.open(style, child, depth, f)? {
+                        stack.push((children, 0));
+                    }
+                }
+                Some(_) => {
+                    Self::elide(children.count() + 1, depth, *shown == 0, f)?;
+                    stack.pop();
+                    (style.close)(true, f)?;
+                }
+                None => {
+                    stack.pop();
+                    (style.close)(true, f)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
 
 impl<T: fmt::Debug> fmt::Debug for DebugNode<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let Self(node) = self;
+        self.write(
+            &Style {
+                open: |node, _, f| {
+                    if f.alternate() {
+                        write!(f, "{}: {:?}", node.index, node.value)
+                    } else {
+                        write!(
+                            f,
+                            "Node {{ index: {:?}, value: {:?}, children: [",
+                            node.index, node.value
+                        )
+                    }
+                },
+                close: |_, f| {
+                    if f.alternate() {
+                        Ok(())
+                    } else {
+                        f.write_str("] }")
+                    }
+                },
+            },
+            f,
+        )
+    }
+}
 
-        f.debug_struct("Node")
-            .field("index", &node.index)
-            .field("value", &node.value)
-            .field("children", &DebugChildren(node.children()))
-            .finish()
+/// A wrapper printing only the indices & shape of a node's subtree
+///
+/// Created by [`Node::debug_structure`], it is otherwise a [`DebugNode`]
+/// without the `T: Debug` bound. The alternate flag (`{:#?}`) prints an
+/// outline, one index per line.
+pub struct DebugStructure<'a, T>(DebugNode<'a, T>);
+
+impl<T> DebugStructure<'_, T> {
+    /// Only print nodes up to `depth` levels below this one
+    #[must_use]
+    pub const fn max_depth(self, depth: usize) -> Self {
+        Self(self.0.max_depth(depth))
+    }
+
+    /// Only print the first `children` children of each node
+    #[must_use]
+    pub const fn max_children(self, children: usize) -> Self {
+        Self(self.0.max_children(children))
+    }
+}
+
+impl<T> fmt::Debug for DebugStructure<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write(
+            &Style {
+                open: |node, parent, f| {
+                    write!(f, "{}", node.index)?;
+                    if parent && !f.alternate() {
+                        f.write_str(" -> [")?;
+                    }
+                    Ok(())
+                },
+                close: |parent, f| {
+                    if parent && !f.alternate() {
+                        f.write_str("]")
+                    } else {
+                        Ok(())
+                    }
+                },
+            },
+            f,
+        )
     }
 }
 
-struct DebugChildren<'a, T>(Next<'a, T>);
+/// The glyphs a [`DisplayTree`] draws its branches with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeCharset {
+    /// Put before a child with siblings after it
+    pub branch: &'static str,
+    /// Put before a parent's last child
+    pub last: &'static str,
+    /// Put below a child with siblings after it, ahead of its descendants
+    pub vertical: &'static str,
+    /// Put below a parent's last child, ahead of its descendants
+    pub blank: &'static str,
+    /// Put before the count of elided nodes
+    pub ellipsis: &'static str,
+}
+
+impl TreeCharset {
+    /// Box-drawing characters, as in `├──` & `└──`
+    pub const UNICODE: Self = Self {
+        branch: "├── ",
+        last: "└── ",
+        vertical: "│   ",
+        blank: "    ",
+        ellipsis: "…",
+    };
+
+    /// Plain ASCII, as in `|--` & `` `-- ``
+    pub const ASCII: Self = Self {
+        branch: "|-- ",
+        last: "`-- ",
+        vertical: "|   ",
+        blank: "    ",
+        ellipsis: "...",
+    };
+}
+
+/// A wrapper displaying a node's subtree, one value per line
+///
+/// Created by [`Node::display_tree`], children are written in insertion
+/// order below their parent, joined by the glyphs of a [`TreeCharset`].
+/// Nodes are walked using an explicit stack, so deep trees can't overflow
+/// the stack. Children below [`DisplayTree::max_depth`] are elided with a
+/// `… +N more` line.
+pub struct DisplayTree<'a, T> {
+    node: &'a Node<T>,
+    charset: TreeCharset,
+    max_depth: usize,
+}
+
+impl<T> DisplayTree<'_, T> {
+    /// Draw branches using the given glyphs
+    #[must_use]
+    pub const fn charset(self, charset: TreeCharset) -> Self {
+        Self { charset, ..self }
+    }
+
+    /// Draw branches using only ASCII, see [`TreeCharset::ASCII`]
+    #[must_use]
+    pub const fn ascii(self) -> Self {
+        self.charset(TreeCharset::ASCII)
+    }
+
+    /// Only print nodes up to `depth` levels below this one
+    #[must_use]
+    pub const fn max_depth(self, depth: usize) -> Self {
+        Self {
+            max_depth: depth,
+            ..self
+        }
+    }
+}
 
-impl<T: fmt::Debug> fmt::Debug for DebugChildren<'_, T> {
+impl<'a, T: fmt::Display> fmt::Display for DisplayTree<'a, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_list()
-            .entries(self.0.clone().map(DebugNode))
-            .finish()
+        let TreeCharset {
+            branch,
+            last,
+            vertical,
+            blank,
+            ellipsis,
+        } = self.charset;
+        let children = |node: &'a Node<T>| node.children_in(ChildOrder::InsertionOrder).peekable();
+
+        write!(f, "{}", self.node.value)?;
+        let mut prefix = String::new();
+        // the children left to print at each level, & the prefix before it
+        let mut stack = Vec::new();
+        if self.node.child().is_some() {
+            if self.max_depth == 0 {
+                let n = self.node.children().count();
+                return write!(f, "\n{last}{ellipsis} +{n} more");
+            }
+            stack.push((children(self.node), 0));
+        }
+
+        while let Some((siblings, len)) = stack.last_mut() {
+            let Some(child) = siblings.next() else {
+                prefix.truncate(*len);
+                stack.pop();
+                continue;
+            };
+            let is_last = siblings.peek().is_none();
+            let glyph = if is_last { last } else { branch };
+            write!(f, "\n{prefix}{glyph}{}", child.value)?;
+            if child.child().is_none() {
+                continue;
+            }
+            let len = prefix.len();
+            prefix.push_str(if is_last { blank } else { vertical });
+            if stack.len() >= self.max_depth {
+                let n = child.children().count();
+                write!(f, "\n{prefix}{last}{ellipsis} +{n} more")?;
+                prefix.truncate(len);
+            } else {
+                stack.push((children(child), len));
+            }
+        }
+        Ok(())
     }
 }
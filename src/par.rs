@@ -0,0 +1,181 @@
+//! Parallel iterators, using rayon
+
+use rayon::iter::ParallelIterator;
+use rayon::iter::plumbing::{Folder, UnindexedConsumer, UnindexedProducer, bridge_unindexed};
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::{Arena, Node};
+
+/// Iterates over a subtree in parallel, in no particular order
+///
+/// Created by [`Node::par_descendants`]
+#[derive(Debug)]
+pub struct ParDescendants<'a, T> {
+    root: &'a Node<T>,
+}
+
+impl<T> Clone for ParDescendants<'_, T> {
+    fn clone(&self) -> Self {
+        Self { root: self.root }
+    }
+}
+
+/// Iterates over every node in an arena in parallel, in no particular order
+///
+/// Created by [`Arena::par_iter`]
+pub struct ParNodes<'a, T> {
+    arena: &'a Arena<T>,
+    range: Range<usize>,
+}
+
+impl<T> fmt::Debug for ParNodes<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParNodes")
+            .field("range", &self.range)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Clone for ParNodes<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            arena: self.arena,
+            range: self.range.clone(),
+        }
+    }
+}
+
+impl<T> Arena<T> {
+    /// Iterate over the initialized nodes in parallel
+    ///
+    /// The indices handed out when this is called are split across threads,
+    /// cutting at bucket boundaries where possible. As with
+    /// [`Arena::iter_range`], nodes pushed afterwards are excluded and nodes
+    /// still being initialized are skipped.
+    pub fn par_iter(&self) -> ParNodes<'_, T> {
+        ParNodes {
+            arena: self,
+            range: 0..self.reserved(),
+        }
+    }
+}
+
+impl<'a, T: Send + Sync> ParallelIterator for ParNodes<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge_unindexed(self, consumer)
+    }
+}
+
+impl<'a, T: Send + Sync> UnindexedProducer for ParNodes<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn split(self) -> (Self, Option<Self>) {
+        let Range { start, end } = self.range;
+        if end - start < 2 {
+            return (self, None);
+        }
+        let mid = start + (end - start) / 2;
+        // SAFETY: mid < end <= the watermark, which is at most MAX_INDEX + 1
+        let entry = unsafe { self.arena.layout().locate_unchecked(mid) }.entry;
+        // cut where mid's bucket starts, unless that's the whole range
+        let at = if mid - entry > start {
+            mid - entry
+        } else {
+            mid
+        };
+        let right = Self {
+            arena: self.arena,
+            range: at..end,
+        };
+        let left = Self {
+            arena: self.arena,
+            range: start..at,
+        };
+        (left, Some(right))
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        folder.consume_iter(self.arena.iter_range(self.range))
+    }
+}
+
+impl<T> Node<T> {
+    /// Iterate over this node's subtree in parallel, including this node
+    ///
+    /// Work is split by handing out whole subtrees, so wide trees split well
+    /// while a long chain is walked by one thread. Nodes pushed while this
+    /// runs may or may not be given, as in [`Node::descendants`].
+    pub const fn par_descendants(&self) -> ParDescendants<'_, T> {
+        ParDescendants { root: self }
+    }
+}
+
+impl<'a, T: Sync> ParallelIterator for ParDescendants<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        let producer = SubtreeProducer {
+            nodes: Vec::new(),
+            subtrees: vec![self.root],
+        };
+        bridge_unindexed(producer, consumer)
+    }
+}
+
+/// Gives some lone nodes, then some whole subtrees
+struct SubtreeProducer<'a, T> {
+    /// Nodes whose children have been handed to `subtrees`
+    nodes: Vec<&'a Node<T>>,
+    subtrees: Vec<&'a Node<T>>,
+}
+
+impl<'a, T: Sync> UnindexedProducer for SubtreeProducer<'a, T> {
+    type Item = &'a Node<T>;
+
+    fn split(mut self) -> (Self, Option<Self>) {
+        // open up a lone subtree to find more to split
+        while let [root] = self.subtrees[..] {
+            if root.child().is_none() {
+                break;
+            }
+            self.nodes.push(root);
+            self.subtrees.clear();
+            self.subtrees.extend(root.children());
+        }
+        if self.subtrees.len() < 2 {
+            return (self, None);
+        }
+        let right = Self {
+            nodes: Vec::new(),
+            subtrees: self.subtrees.split_off(self.subtrees.len() / 2),
+        };
+        (self, Some(right))
+    }
+
+    fn fold_with<F>(self, mut folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        folder = folder.consume_iter(self.nodes);
+        for root in self.subtrees {
+            if folder.full() {
+                break;
+            }
+            folder = folder.consume_iter(root.descendants());
+        }
+        folder
+    }
+}
@@ -0,0 +1,12 @@
+//! The commonly used traits & types
+//!
+//! ```rust
+//! use silva::prelude::*;
+//!
+//! let arena = Arena::new();
+//! let root = arena.push(None, "root");
+//! arena.push(root, "leaf");
+//! assert_eq!(root.children().collect_indices().len(), 1);
+//! ```
+
+pub use crate::{Arena, AsParent, CollectIndices, Index, Node};
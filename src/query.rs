@@ -0,0 +1,225 @@
+//! Selecting nodes by their path from a starting node
+
+use std::fmt;
+use std::iter::FusedIterator;
+
+use crate::{Arena, DescendantsWithDepth, Node};
+
+/// A path of steps, each matching nodes by a predicate, selecting the nodes
+/// at its end
+///
+/// Every match is found in one pre-order walk from the starting node, keeping
+/// for each node on the path down which steps it has reached. Subtrees that
+/// can't reach the end are skipped, so a query made only of
+/// [`child`](Query::child) steps never looks deeper than it has steps.
+///
+/// ```
+/// # use silva::{Arena, Query};
+/// let arena = Arena::new();
+/// let html = arena.push(None, "html");
+/// let body = arena.push(html, "body");
+/// let div = arena.push(body, "div");
+/// arena.push(div, "p");
+/// arena.push(body, "p");
+/// arena.push(html, "p");
+///
+/// let query = Query::new()
+///     .child(|n| n.value == "body")
+///     .descendant(|n| n.value == "p");
+/// assert_eq!(query.select(html).len(), 2);
+/// assert!(query.select(body).is_empty());
+/// assert_eq!(query.root().select(div).len(), 2);
+/// ```
+pub struct Query<'p, T> {
+    from_root: bool,
+    steps: Vec<Step<'p, T>>,
+}
+
+/// Decides whether a node matches a step
+type Pred<'p, T> = Box<dyn Fn(&Node<T>) -> bool + 'p>;
+
+/// A single step of a [`Query`]
+struct Step<'p, T> {
+    /// Whether the step may skip over nodes to match deeper down
+    descendant: bool,
+    pred: Pred<'p, T>,
+}
+
+impl<T> fmt::Debug for Query<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let steps: Vec<_> = self
+            .steps
+            .iter()
+            .map(|step| {
+                if step.descendant {
+                    "descendant"
+                } else {
+                    "child"
+                }
+            })
+            .collect();
+        f.debug_struct("Query")
+            .field("from_root", &self.from_root)
+            .field("steps", &steps)
+            .finish()
+    }
+}
+
+impl<T> Default for Query<'_, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'p, T> Query<'p, T> {
+    /// Create a query with no steps, selecting the starting node
+    pub const fn new() -> Self {
+        Self {
+            from_root: false,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Start from the root of the starting node's tree
+    ///
+    /// Under an unattached orphan, that is the orphan. This applies to the
+    /// whole query, wherever in the chain it is called.
+    #[must_use]
+    pub const fn root(mut self) -> Self {
+        self.from_root = true;
+        self
+    }
+
+    /// Step to the children that match `pred`
+    #[must_use]
+    pub fn child(mut self, pred: impl Fn(&Node<T>) -> bool + 'p) -> Self {
+        self.steps.push(Step {
+            descendant: false,
+            pred: Box::new(pred),
+        });
+        self
+    }
+
+    /// Step to the descendants that match `pred`, at any depth below
+    #[must_use]
+    pub fn descendant(mut self, pred: impl Fn(&Node<T>) -> bool + 'p) -> Self {
+        self.steps.push(Step {
+            descendant: true,
+            pred: Box::new(pred),
+        });
+        self
+    }
+
+    /// Iterate over the nodes selected from `node`, in the order of
+    /// [`Node::descendants`]
+    ///
+    /// Each node is given once, however many ways it is reached.
+    pub fn matches<'q, 'a>(&'q self, node: &'a Node<T>) -> Matches<'q, 'a, T> {
+        let start = if self.from_root {
+            node.ancestors().last().unwrap_or(node)
+        } else {
+            node
+        };
+        Matches {
+            steps: &self.steps,
+            walk: start.descendants_with_depth(),
+            states: Vec::new(),
+            levels: Vec::new(),
+        }
+    }
+
+    /// Collect the nodes selected from `node`, see [`Query::matches`]
+    pub fn select<'a>(&self, node: &'a Node<T>) -> Vec<&'a Node<T>> {
+        self.matches(node).collect()
+    }
+
+    /// Find the first node selected from `node`, see [`Query::matches`]
+    pub fn first<'a>(&self, node: &'a Node<T>) -> Option<&'a Node<T>> {
+        self.matches(node).next()
+    }
+}
+
+impl<T> Arena<T> {
+    /// Iterate over the nodes selected from each root, see [`Query::matches`]
+    ///
+    /// Roots are taken in index order, see [`Arena::roots`].
+    pub fn query<'a>(&'a self, query: &'a Query<'_, T>) -> impl FusedIterator<Item = &'a Node<T>> {
+        self.roots().flat_map(move |root| query.matches(root))
+    }
+}
+
+/// Iterates over the nodes selected by a [`Query`]
+///
+/// Created by [`Query::matches`]
+pub struct Matches<'q, 'a, T> {
+    steps: &'q [Step<'q, T>],
+    walk: DescendantsWithDepth<'a, T>,
+    /// The steps reached by each node on the path to the last node given,
+    /// sorted within each node
+    states: Vec<usize>,
+    /// Where each node's steps start in `states`
+    levels: Vec<usize>,
+}
+
+impl<T> fmt::Debug for Matches<'_, '_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Matches")
+            .field("states", &self.states)
+            .field("levels", &self.levels)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Clone for Matches<'_, '_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            steps: self.steps,
+            walk: self.walk.clone(),
+            states: self.states.clone(),
+            levels: self.levels.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Matches<'_, 'a, T> {
+    type Item = &'a Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (depth, node) = self.walk.next()?;
+            // drop the nodes no longer on the path
+            if let Some(&end) = self.levels.get(depth) {
+                self.states.truncate(end);
+            }
+            self.levels.truncate(depth);
+            let start = self.states.len();
+            if depth == 0 {
+                self.states.push(0);
+            } else {
+                for at in self.levels[depth - 1]..start {
+                    let i = self.states[at];
+                    let Some(step) = self.steps.get(i) else {
+                        continue;
+                    };
+                    if step.descendant && self.states[start..].last() != Some(&i) {
+                        self.states.push(i);
+                    }
+                    if (step.pred)(node) {
+                        self.states.push(i + 1);
+                    }
+                }
+            }
+            self.levels.push(start);
+
+            let reached = &self.states[start..];
+            if reached.first().is_none_or(|&i| i >= self.steps.len()) {
+                self.walk.skip_subtree();
+            }
+            if reached.last() == Some(&self.steps.len()) {
+                return Some(node);
+            }
+        }
+    }
+}
+
+impl<T> FusedIterator for Matches<'_, '_, T> {}
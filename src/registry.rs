@@ -0,0 +1,178 @@
+//! Resolving nodes by plain numbers, while their arena is alive
+//!
+//! Enabled by the `registry` feature. Arenas are only tracked once
+//! registered, so arenas that aren't pay nothing.
+//!
+//! ```rust
+//! use std::sync::Arc;
+//!
+//! use silva::Arena;
+//! use silva::registry::Registry;
+//!
+//! static EVENTS: Registry<&str> = Registry::new();
+//!
+//! let arena = Arc::new(Arena::new());
+//! let id = EVENTS.register(&arena);
+//! let index = arena.push(None, "start").index();
+//!
+//! assert_eq!(EVENTS.resolve(id, index).unwrap().value, "start");
+//! drop(arena);
+//! assert!(EVENTS.resolve(id, index).is_none());
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::num::NonZero;
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, Weak};
+
+use crate::{Arena, Handle, Index};
+
+/// The number of separately locked parts of a registry
+const SHARDS: usize = 8;
+
+/// Identifies an arena registered in a [`Registry`]
+///
+/// Ids are never reused, even across registries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArenaId(NonZero<u64>);
+
+impl ArenaId {
+    /// Hand out a new id
+    fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+        let id = NEXT.fetch_add(1, Relaxed);
+        Self(NonZero::new(id).expect("arena ids overflowed"))
+    }
+
+    /// Get the id as a plain number
+    pub const fn get(self) -> u64 {
+        self.0.get()
+    }
+
+    /// Get an id back from a plain number, `None` if it is zero
+    pub const fn from_u64(id: u64) -> Option<Self> {
+        match NonZero::new(id) {
+            Some(id) => Some(Self(id)),
+            None => None,
+        }
+    }
+
+    /// The shard this id lives in
+    #[expect(
+        clippy::cast_possible_truncation,
+        reason = "the remainder is below SHARDS"
+    )]
+    const fn shard(self) -> usize {
+        (self.0.get() % SHARDS as u64) as usize
+    }
+}
+
+impl fmt::Display for ArenaId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+type Shard<T> = Mutex<BTreeMap<ArenaId, Weak<Arena<T>>>>;
+
+/// Weak references to arenas of `T`, keyed by [`ArenaId`]
+///
+/// A registry doesn't keep its arenas alive. Entries of dropped arenas are
+/// removed as they are resolved, & swept whenever another arena is
+/// registered. Registries can be put in a `static`, giving a global registry
+/// per value type.
+pub struct Registry<T> {
+    shards: [Shard<T>; SHARDS],
+}
+
+impl<T> fmt::Debug for Registry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registry").finish_non_exhaustive()
+    }
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Registry<T> {
+    /// Construct a new, empty, registry
+    pub const fn new() -> Self {
+        Self {
+            shards: [const { Mutex::new(BTreeMap::new()) }; SHARDS],
+        }
+    }
+
+    /// Lock the shard of `id`
+    fn shard(&self, id: ArenaId) -> MutexGuard<'_, BTreeMap<ArenaId, Weak<Arena<T>>>> {
+        // entries are only inserted or removed, so a panic can't break them
+        self.shards[id.shard()]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Register an arena, returning its new id
+    ///
+    /// Each call hands out a new id, even for an arena already registered.
+    pub fn register(&self, arena: &Arc<Arena<T>>) -> ArenaId {
+        let id = ArenaId::next();
+        let mut shard = self.shard(id);
+        shard.retain(|_, arena| arena.strong_count() > 0);
+        shard.insert(id, Arc::downgrade(arena));
+        id
+    }
+
+    /// Get a handle to the node at `index` of the arena registered as `id`
+    ///
+    /// Returns `None` if the arena has been dropped, or has no such node.
+    pub fn resolve(&self, id: ArenaId, index: Index) -> Option<Handle<T>> {
+        let mut shard = self.shard(id);
+        let Some(arena) = shard.get(&id)?.upgrade() else {
+            shard.remove(&id);
+            return None;
+        };
+        drop(shard);
+        arena.get(index).map(|node| node.handle(&arena))
+    }
+
+    /// Get the arena registered as `id`, if it is alive
+    pub fn arena(&self, id: ArenaId) -> Option<Arc<Arena<T>>> {
+        self.shard(id).get(&id)?.upgrade()
+    }
+
+    /// Remove the arena registered as `id`, returning `true` if it was there
+    pub fn unregister(&self, id: ArenaId) -> bool {
+        self.shard(id).remove(&id).is_some()
+    }
+
+    /// Get the number of entries, including those of dropped arenas yet to be
+    /// removed
+    pub fn len(&self) -> usize {
+        (0..SHARDS)
+            .map(|i| {
+                self.shards[i]
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .len()
+            })
+            .sum()
+    }
+
+    /// returns `true` if there are no entries
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove the entries of every dropped arena
+    pub fn prune(&self) {
+        for shard in &self.shards {
+            shard
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .retain(|_, arena| arena.strong_count() > 0);
+        }
+    }
+}
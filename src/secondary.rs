@@ -0,0 +1,267 @@
+//! Values associated with an arena's nodes
+
+use std::fmt;
+use std::iter::FusedIterator;
+
+#[cfg(doc)]
+use crate::Arena;
+use crate::Index;
+use crate::arena::{Column, OnceEntry};
+
+/// Values associated with an arena's nodes, stored apart from the arena
+///
+/// Entries are keyed by [`Index`], so any number of maps can be attached to
+/// an arena after it is built. Storage grows to the largest index inserted.
+/// An entry is only found by the index it was inserted with, so with the
+/// `generations` feature an index from before [`Arena::clear`] doesn't find
+/// the entry of one handed out afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecondaryMap<V> {
+    values: Vec<Option<(Index, V)>>,
+    len: usize,
+}
+
+impl<V> Default for SecondaryMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> std::ops::Index<Index> for SecondaryMap<V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, index: Index) -> &Self::Output {
+        self.get(index).expect("no entry for index")
+    }
+}
+
+impl<V> std::ops::IndexMut<Index> for SecondaryMap<V> {
+    #[inline]
+    fn index_mut(&mut self, index: Index) -> &mut Self::Output {
+        self.get_mut(index).expect("no entry for index")
+    }
+}
+
+impl<V> SecondaryMap<V> {
+    /// Construct a new, empty, map
+    pub const fn new() -> Self {
+        Self {
+            values: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Create a map with room for indices below `capacity`
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: Vec::with_capacity(capacity),
+            len: 0,
+        }
+    }
+
+    /// Insert a value, returning the one it replaced
+    pub fn insert(&mut self, index: Index, value: V) -> Option<V> {
+        let i = index.as_usize();
+        if i >= self.values.len() {
+            self.values.resize_with(i + 1, || None);
+        }
+        // an entry of another generation is replaced, but not given back
+        let prev = self.values[i].replace((index, value));
+        if prev.is_none() {
+            self.len += 1;
+        }
+        prev.filter(|&(key, _)| key == index)
+            .map(|(_, value)| value)
+    }
+
+    /// Remove the value at `index`, returning it
+    pub fn remove(&mut self, index: Index) -> Option<V> {
+        let entry = self.values.get_mut(index.as_usize())?;
+        let (_, value) = entry.take_if(|(key, _)| *key == index)?;
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Get the value at `index`
+    pub fn get(&self, index: Index) -> Option<&V> {
+        match self.values.get(index.as_usize())? {
+            Some((key, value)) if *key == index => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the value at `index` mutably
+    pub fn get_mut(&mut self, index: Index) -> Option<&mut V> {
+        match self.values.get_mut(index.as_usize())? {
+            Some((key, value)) if *key == index => Some(value),
+            _ => None,
+        }
+    }
+
+    /// returns `true` if there is a value at `index`
+    pub fn contains_key(&self, index: Index) -> bool {
+        self.get(index).is_some()
+    }
+
+    /// Get the number of values
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// returns `true` if there are no values
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Iterate over the values, in index order
+    pub fn iter(&self) -> Entries<'_, V> {
+        Entries {
+            values: self.values.iter(),
+            len: self.len,
+        }
+    }
+}
+
+impl<V> FromIterator<(Index, V)> for SecondaryMap<V> {
+    fn from_iter<I: IntoIterator<Item = (Index, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<V> Extend<(Index, V)> for SecondaryMap<V> {
+    fn extend<I: IntoIterator<Item = (Index, V)>>(&mut self, iter: I) {
+        for (index, value) in iter {
+            self.insert(index, value);
+        }
+    }
+}
+
+impl<'a, V> IntoIterator for &'a SecondaryMap<V> {
+    type Item = (Index, &'a V);
+    type IntoIter = Entries<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterates over a [`SecondaryMap`]'s values in index order
+///
+/// Created by [`SecondaryMap::iter`]
+#[derive(Debug)]
+pub struct Entries<'a, V> {
+    values: std::slice::Iter<'a, Option<(Index, V)>>,
+    len: usize,
+}
+
+impl<V> Clone for Entries<'_, V> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<'a, V> Iterator for Entries<'a, V> {
+    type Item = (Index, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.values.find_map(|entry| {
+            let (index, value) = entry.as_ref()?;
+            self.len -= 1;
+            Some((*index, value))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<V> ExactSizeIterator for Entries<'_, V> {}
+
+impl<V> FusedIterator for Entries<'_, V> {}
+
+/// Values computed lazily for an arena's nodes, from any number of threads
+///
+/// Each entry is set at most once, the first writer wins & every reader sees
+/// its value. Storage grows in buckets like an arena's, so entries never move
+/// & references to them stay valid while the map is shared. As with
+/// [`SecondaryMap`], an entry is only found by the index it was set with.
+///
+/// ```
+/// # use silva::{Arena, ConcurrentSecondaryMap};
+/// let arena = Arena::new();
+/// let root = arena.push(None, 2);
+/// let squares = ConcurrentSecondaryMap::with_capacity(arena.capacity());
+/// assert_eq!(*squares.get_or_compute(root.index(), || root.value * root.value), 4);
+/// assert_eq!(squares.get(root.index()), Some(&4));
+/// ```
+pub struct ConcurrentSecondaryMap<V> {
+    entries: Column<OnceEntry<V>>,
+}
+
+// SAFETY: the map owns its values
+#[allow(clippy::non_send_fields_in_send_ty)]
+unsafe impl<V: Send> Send for ConcurrentSecondaryMap<V> {}
+// SAFETY: values are only set through a `OnceLock`, & shared afterwards
+unsafe impl<V: Send + Sync> Sync for ConcurrentSecondaryMap<V> {}
+
+impl<V> Default for ConcurrentSecondaryMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> fmt::Debug for ConcurrentSecondaryMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrentSecondaryMap")
+            .finish_non_exhaustive()
+    }
+}
+
+impl<V> ConcurrentSecondaryMap<V> {
+    /// Construct a new, empty, map
+    pub const fn new() -> Self {
+        Self {
+            entries: Column::new(),
+        }
+    }
+
+    /// Create a map with room for indices below `capacity`
+    ///
+    /// Pass [`Arena::capacity`] to allocate upfront for every node the arena
+    /// holds, & most it will hold without growing.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let map = Self::new();
+        map.entries.reserve(capacity);
+        map
+    }
+
+    /// Get the value at `index`, if it has been set
+    pub fn get(&self, index: Index) -> Option<&V> {
+        self.entries.get(index)?.get(index)
+    }
+
+    /// Set the value at `index`, giving it back if one is already set
+    ///
+    /// # Errors
+    ///
+    /// Fails if the entry was already set, or is being computed
+    pub fn insert(&self, index: Index, value: V) -> Result<(), V> {
+        self.entries.acquire(index).cell(index).set(value)
+    }
+
+    /// Get the value at `index`, computing it with `f` if it isn't set
+    ///
+    /// Concurrent calls for the same index block until the first finishes, so
+    /// `f` runs at most once per index. If `f` panics the entry is left
+    /// unset, & the next call computes it again.
+    pub fn get_or_compute(&self, index: Index, f: impl FnOnce() -> V) -> &V {
+        self.entries.acquire(index).cell(index).get_or_init(f)
+    }
+}
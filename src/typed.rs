@@ -0,0 +1,162 @@
+//! Arenas indexed by a dedicated index type
+
+use std::marker::PhantomData;
+
+use crate::{Arena, Index, Node};
+
+/// An [`Index`] wrapper made by [`new_index_type!`](crate::new_index_type)
+///
+/// Each wrapper can only be used with a [`TypedArena`] of the same type.
+pub trait IndexType: Copy + From<Index> + Into<Index> {}
+
+/// Generate a wrapper over [`Index`] for use with a [`TypedArena`]
+///
+/// The wrapper implements [`IndexType`], the usual derives, and converts to
+/// & from [`Index`].
+///
+/// # Examples
+///
+/// ```rust
+/// silva::new_index_type!(
+///     /// Indexes the syntax tree
+///     pub struct AstIndex;
+/// );
+///
+/// let ast = silva::TypedArena::<_, AstIndex>::new();
+/// let root: AstIndex = ast.push(None, "fn");
+/// let name = ast.push(root, "main");
+/// assert_eq!(ast[name].parent().unwrap().value, "fn");
+/// ```
+///
+/// Indices of one type can't be used with another's arena:
+///
+/// ```rust,compile_fail
+/// silva::new_index_type!(struct AstIndex;);
+/// silva::new_index_type!(struct TypeIndex;);
+///
+/// let ast = silva::TypedArena::<_, AstIndex>::new();
+/// let types = silva::TypedArena::<_, TypeIndex>::new();
+/// let root = ast.push(None, "fn");
+/// types.push(root, "i32");
+/// ```
+#[macro_export]
+macro_rules! new_index_type {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident;) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[repr(transparent)]
+        $vis struct $name($crate::Index);
+
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_tuple(::core::stringify!($name))
+                    .field(&self.0.as_usize())
+                    .finish()
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl ::core::convert::From<$crate::Index> for $name {
+            fn from(index: $crate::Index) -> Self {
+                Self(index)
+            }
+        }
+
+        impl ::core::convert::From<$name> for $crate::Index {
+            fn from(index: $name) -> Self {
+                index.0
+            }
+        }
+
+        impl $crate::IndexType for $name {}
+    };
+}
+
+/// An [`Arena`] whose indices are of the type `I`
+///
+/// This only delegates to the inner arena, but keeps indices from different
+/// arenas from being mixed up. See [`new_index_type!`](crate::new_index_type).
+pub struct TypedArena<T, I> {
+    arena: Arena<T>,
+    index: PhantomData<fn() -> I>,
+}
+
+impl<T, I: IndexType> Default for TypedArena<T, I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, I: IndexType> From<Arena<T>> for TypedArena<T, I> {
+    fn from(arena: Arena<T>) -> Self {
+        Self {
+            arena,
+            index: PhantomData,
+        }
+    }
+}
+
+impl<T, I: IndexType> std::ops::Index<I> for TypedArena<T, I> {
+    type Output = Node<T>;
+
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        &self.arena[index.into()]
+    }
+}
+
+impl<T, I: IndexType> TypedArena<T, I> {
+    /// Construct a new, empty, tree.
+    pub const fn new() -> Self {
+        Self {
+            arena: Arena::new(),
+            index: PhantomData,
+        }
+    }
+
+    /// Create a tree with atleast the given capacity
+    pub fn with_capacity(capacity: usize) -> Self {
+        Arena::with_capacity(capacity).into()
+    }
+
+    /// Add a new node, returning its index
+    ///
+    /// `parent` can be an `I`, `Option<I>`, or `None`.
+    ///
+    /// # Panics
+    ///
+    /// See [`Arena::push`]
+    pub fn push(&self, parent: impl Into<Option<I>>, value: T) -> I {
+        let parent = parent.into().map(Into::into);
+        self.arena.push(parent, value).index().into()
+    }
+
+    /// Get the node of the given index
+    ///
+    /// See [`Arena::get`]
+    pub fn get(&self, index: I) -> Option<&Node<T>> {
+        self.arena.get(index.into())
+    }
+
+    /// Get the index of a node in this arena
+    ///
+    /// Returns `None` if `node` does not belong to this arena.
+    pub fn index_of(&self, node: &Node<T>) -> Option<I> {
+        self.arena.contains(node).then(|| node.index().into())
+    }
+
+    /// Get the underlying arena
+    pub const fn arena(&self) -> &Arena<T> {
+        &self.arena
+    }
+
+    /// Take the underlying arena
+    pub fn into_inner(self) -> Arena<T> {
+        self.arena
+    }
+}
@@ -0,0 +1,178 @@
+//! An arena viewed alongside a column of values
+
+use std::fmt;
+use std::iter::FusedIterator;
+
+use crate::{Arena, Entries, Index, Next, Node, SecondaryMap};
+
+/// An arena's structure, zipped with a column of values
+///
+/// Views are cheap to copy, one can be made for each column of an arena while
+/// borrowing both for the same lifetime.
+pub struct View<'a, T, C> {
+    arena: &'a Arena<T>,
+    column: &'a SecondaryMap<C>,
+}
+
+impl<T, C> fmt::Debug for View<'_, T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("View")
+            .field("count", &self.arena.count())
+            .field("entries", &self.column.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, C> Clone for View<'_, T, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, C> Copy for View<'_, T, C> {}
+
+impl<'a, T, C> View<'a, T, C> {
+    /// View `arena` alongside `column`
+    pub const fn new(arena: &'a Arena<T>, column: &'a SecondaryMap<C>) -> Self {
+        Self { arena, column }
+    }
+
+    /// Get the underlying arena
+    pub const fn arena(&self) -> &'a Arena<T> {
+        self.arena
+    }
+
+    /// Get the underlying column
+    pub const fn column(&self) -> &'a SecondaryMap<C> {
+        self.column
+    }
+
+    /// Get the node at `index` along with its column value
+    ///
+    /// Returns `None` if either is missing.
+    pub fn get(&self, index: Index) -> Option<(&'a Node<T>, &'a C)> {
+        Some((self.arena.get(index)?, self.column.get(index)?))
+    }
+
+    /// Iterate over the nodes that have a column value, in index order
+    pub fn iter(&self) -> ViewIter<'a, T, C> {
+        ViewIter {
+            entries: self.column.iter(),
+            arena: self.arena,
+        }
+    }
+
+    /// Iterate over the subtree rooted at `root` in pre-order, starting with
+    /// `root`
+    ///
+    /// Unlike [`View::iter`] every node is yielded, with `None` for those
+    /// missing a column value, so the shape of the subtree is kept.
+    ///
+    /// # Panics
+    ///
+    /// panics if `root` is not in this view's arena
+    pub fn descendants(&self, root: Index) -> ViewDescendants<'a, T, C> {
+        let root = &self.arena[root];
+        ViewDescendants {
+            root: Some(root),
+            stack: Vec::new(),
+            column: self.column,
+        }
+    }
+}
+
+impl<'a, T, C> IntoIterator for &View<'a, T, C> {
+    type Item = (&'a Node<T>, &'a C);
+    type IntoIter = ViewIter<'a, T, C>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterates over the nodes of a [`View`] that have a column value
+///
+/// Created by [`View::iter`]
+pub struct ViewIter<'a, T, C> {
+    entries: Entries<'a, C>,
+    arena: &'a Arena<T>,
+}
+
+impl<T, C> fmt::Debug for ViewIter<'_, T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ViewIter").finish_non_exhaustive()
+    }
+}
+
+impl<T, C> Clone for ViewIter<'_, T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            arena: self.arena,
+        }
+    }
+}
+
+impl<'a, T, C> Iterator for ViewIter<'a, T, C> {
+    type Item = (&'a Node<T>, &'a C);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries
+            .find_map(|(index, value)| Some((self.arena.get(index)?, value)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.entries.size_hint().1)
+    }
+}
+
+impl<T, C> FusedIterator for ViewIter<'_, T, C> {}
+
+/// Iterates over a subtree of a [`View`] in pre-order
+///
+/// Created by [`View::descendants`]
+pub struct ViewDescendants<'a, T, C> {
+    root: Option<&'a Node<T>>,
+    /// The children left to visit at each level
+    stack: Vec<Next<'a, T>>,
+    column: &'a SecondaryMap<C>,
+}
+
+impl<T, C> fmt::Debug for ViewDescendants<'_, T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ViewDescendants")
+            .field("depth", &self.stack.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, C> Clone for ViewDescendants<'_, T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root,
+            stack: self.stack.clone(),
+            column: self.column,
+        }
+    }
+}
+
+impl<'a, T, C> Iterator for ViewDescendants<'a, T, C> {
+    type Item = (&'a Node<T>, Option<&'a C>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = match self.root.take() {
+            Some(root) => root,
+            None => loop {
+                let children = self.stack.last_mut()?;
+                if let Some(node) = children.next() {
+                    break node;
+                }
+                self.stack.pop();
+            },
+        };
+        self.stack.push(node.children());
+        Some((node, self.column.get(node.index())))
+    }
+}
+
+impl<T, C> FusedIterator for ViewDescendants<'_, T, C> {}
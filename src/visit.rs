@@ -0,0 +1,106 @@
+//! Visiting the nodes of a tree
+
+use std::fmt;
+
+use crate::Node;
+
+/// What [`walk`] should do after entering a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Flow {
+    /// Visit the node's children
+    Continue,
+    /// Skip the node's children, leaving it right away
+    SkipSubtree,
+    /// Stop walking, without leaving any node
+    Break,
+}
+
+/// Visits nodes as they are entered & left by [`walk`]
+pub trait Visitor<'a, T> {
+    /// Called on a node before its children
+    fn enter(&mut self, node: &'a Node<T>) -> Flow;
+
+    /// Called on a node after its children
+    fn leave(&mut self, node: &'a Node<T>) {
+        let _ = node;
+    }
+}
+
+/// Walk the subtree rooted at `root`, calling `visitor` on every node
+///
+/// Each node is entered, then its children are walked in the order of
+/// [`Node::children`], then it is left. Every entered node is left, unless
+/// the walk is broken. Nodes are walked using an explicit stack, so deep
+/// trees can't overflow the stack.
+///
+/// Returns `false` if the walk was stopped by [`Flow::Break`].
+pub fn walk<'a, T, V: Visitor<'a, T> + ?Sized>(root: &'a Node<T>, visitor: &mut V) -> bool {
+    match visitor.enter(root) {
+        Flow::Continue => {}
+        Flow::SkipSubtree => {
+            visitor.leave(root);
+            return true;
+        }
+        Flow::Break => return false,
+    }
+
+    let mut stack = vec![(root, root.children())];
+    while let Some((node, children)) = stack.last_mut() {
+        if let Some(child) = children.next() {
+            match visitor.enter(child) {
+                Flow::Continue => stack.push((child, child.children())),
+                Flow::SkipSubtree => visitor.leave(child),
+                Flow::Break => return false,
+            }
+        } else {
+            let node = *node;
+            stack.pop();
+            visitor.leave(node);
+        }
+    }
+    true
+}
+
+/// A visitor collecting the nodes matching a predicate, in the order they
+/// are entered
+pub struct Collector<'a, T, P> {
+    predicate: P,
+    nodes: Vec<&'a Node<T>>,
+}
+
+impl<T, P> fmt::Debug for Collector<'_, T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Collector")
+            .field("nodes", &self.nodes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T, P: FnMut(&Node<T>) -> bool> Collector<'a, T, P> {
+    /// Create a collector of the nodes matching `predicate`
+    pub const fn new(predicate: P) -> Self {
+        Self {
+            predicate,
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Get the nodes collected so far
+    pub fn nodes(&self) -> &[&'a Node<T>] {
+        &self.nodes
+    }
+
+    /// Take the nodes collected
+    pub fn into_nodes(self) -> Vec<&'a Node<T>> {
+        self.nodes
+    }
+}
+
+impl<'a, T, P: FnMut(&Node<T>) -> bool> Visitor<'a, T> for Collector<'a, T, P> {
+    fn enter(&mut self, node: &'a Node<T>) -> Flow {
+        if (self.predicate)(node) {
+            self.nodes.push(node);
+        }
+        Flow::Continue
+    }
+}
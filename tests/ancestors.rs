@@ -0,0 +1,156 @@
+use silva::algo::AncestorIndex;
+use silva::{Arena, Index, Node};
+
+/// splitmix64, enough randomness without a dependency
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// A forest of roots, orphans & attached orphans
+fn random_arena(seed: u64, len: usize) -> Arena<usize> {
+    let mut rng = Rng(seed);
+    let arena = Arena::new();
+    arena.push(None, 0);
+    for i in 1..len {
+        match rng.below(16) {
+            0 => {
+                arena.push(None, i);
+            }
+            1 => {
+                arena.push_orphan(i);
+            }
+            2 => {
+                let parent = arena.get_dense(rng.below(i)).unwrap();
+                let orphan = arena.get_dense(rng.below(i)).unwrap();
+                // cycles & non-orphans are rejected, which is fine here
+                let _ = arena.attach(parent, orphan);
+                arena.push(None, i);
+            }
+            // favour deep chains now & then
+            3..=5 => {
+                arena.push(arena.get_dense(i - 1).unwrap(), i);
+            }
+            _ => {
+                arena.push(arena.get_dense(rng.below(i)).unwrap(), i);
+            }
+        }
+    }
+    arena
+}
+
+fn naive_lca<T>(a: &Node<T>, b: &Node<T>) -> Option<Index> {
+    let path: Vec<_> = std::iter::once(a).chain(a.ancestors()).collect();
+    std::iter::once(b)
+        .chain(b.ancestors())
+        .find(|n| path.iter().any(|p| std::ptr::eq(*p, *n)))
+        .map(Node::index)
+}
+
+#[test]
+fn matches_naive() {
+    let (seeds, len, pairs) = if cfg!(miri) {
+        (1, 64, 64)
+    } else {
+        (8, 500, 2_000)
+    };
+    for seed in 0..seeds {
+        let arena = random_arena(seed, len);
+        let index = AncestorIndex::build(&arena);
+        assert_eq!(index.len(), len, "seed {seed}");
+
+        for node in arena.iter_range(..) {
+            let depth = node.ancestors().count();
+            assert_eq!(index.depth(node.index()), Some(depth), "seed {seed}");
+        }
+
+        let mut rng = Rng(seed);
+        for _ in 0..pairs {
+            let a = arena.get_dense(rng.below(len)).unwrap();
+            let b = arena.get_dense(rng.below(len)).unwrap();
+            let ctx = format!("seed {seed}, {} & {}", a.index(), b.index());
+            assert_eq!(index.lca(a.index(), b.index()), naive_lca(a, b), "{ctx}");
+            let is_ancestor = b.ancestors().any(|n| std::ptr::eq(n, a));
+            assert_eq!(
+                index.is_ancestor(a.index(), b.index()),
+                Some(is_ancestor),
+                "{ctx}"
+            );
+        }
+    }
+}
+
+#[test]
+fn small_tree() {
+    let arena = Arena::new();
+    let root = arena.push(None, "root");
+    let a = arena.push(root, "a");
+    let a1 = arena.push(a, "a1");
+    let b = arena.push(root, "b");
+    let other = arena.push(None, "other");
+    let index = AncestorIndex::build(&arena);
+
+    assert_eq!(index.depth(a1.index()), Some(2));
+    assert_eq!(index.lca(a1.index(), b.index()), Some(root.index()));
+    assert_eq!(index.lca(a1.index(), a.index()), Some(a.index()));
+    assert_eq!(index.lca(b.index(), b.index()), Some(b.index()));
+    assert_eq!(index.lca(a1.index(), other.index()), None);
+    assert_eq!(index.is_ancestor(root.index(), a1.index()), Some(true));
+    assert_eq!(index.is_ancestor(a1.index(), root.index()), Some(false));
+    assert_eq!(index.is_ancestor(a.index(), a.index()), Some(false));
+    assert_eq!(index.is_ancestor(a.index(), b.index()), Some(false));
+}
+
+#[test]
+fn stale_after_build() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let orphan = arena.push_orphan(1);
+    let index = AncestorIndex::build(&arena);
+
+    let late = arena.push(root, 2);
+    assert_eq!(index.depth(late.index()), None);
+    assert_eq!(index.lca(root.index(), late.index()), None);
+    assert_eq!(index.is_ancestor(root.index(), late.index()), None);
+
+    // attached after the build, so still its own tree
+    arena.attach(root, orphan).unwrap();
+    assert_eq!(index.depth(orphan.index()), Some(0));
+    assert_eq!(index.lca(root.index(), orphan.index()), None);
+    assert_eq!(AncestorIndex::build(&arena).depth(orphan.index()), Some(1));
+}
+
+#[test]
+fn deep_chain() {
+    let depth = if cfg!(miri) { 1_000 } else { 100_000 };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut leaf = root;
+    for i in 1..depth {
+        leaf = arena.push(leaf, i);
+    }
+    let index = AncestorIndex::build(&arena);
+
+    assert_eq!(index.depth(leaf.index()), Some(depth - 1));
+    let mid = arena.get_dense(depth / 2).unwrap().index();
+    assert_eq!(index.lca(leaf.index(), mid), Some(mid));
+    assert_eq!(index.is_ancestor(root.index(), leaf.index()), Some(true));
+}
+
+#[test]
+fn empty() {
+    let arena = Arena::<()>::new();
+    let index = AncestorIndex::build(&arena);
+    assert!(index.is_empty());
+}
@@ -0,0 +1,67 @@
+use silva::Arena;
+
+#[test]
+fn strict() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    let a1 = arena.push(a, 2);
+    let b = arena.push(root, 3);
+    let other = arena.push(None, 4);
+
+    assert!(root.is_ancestor_of(a1));
+    assert!(a.is_ancestor_of(a1));
+    assert!(a1.is_descendant_of(root));
+    assert!(!a1.is_ancestor_of(a));
+    assert!(!a.is_ancestor_of(a));
+    assert!(!a.is_descendant_of(a));
+    assert!(!b.is_ancestor_of(a1));
+    assert!(!a.is_ancestor_of(b));
+    assert!(!root.is_ancestor_of(other));
+    assert!(!other.is_descendant_of(root));
+}
+
+#[test]
+fn orphans() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let orphan = arena.push_orphan(1);
+    let leaf = arena.push(orphan, 2);
+
+    assert!(orphan.is_ancestor_of(leaf));
+    assert!(!root.is_ancestor_of(leaf));
+    assert!(!leaf.is_ancestor_of(orphan));
+
+    arena.attach(root, orphan).unwrap();
+    assert!(root.is_ancestor_of(leaf));
+    assert!(orphan.is_ancestor_of(leaf));
+    assert!(leaf.is_descendant_of(root));
+}
+
+#[test]
+fn matches_ancestors() {
+    let len = if cfg!(miri) { 100 } else { 1_000 };
+    let arena = Arena::new();
+    arena.push(None, 0);
+    for i in 1..len {
+        if i % 9 == 0 {
+            arena.push_orphan(i);
+        } else {
+            arena.push(arena.get_dense((i * 37 + 11) % i).unwrap(), i);
+        }
+        // cycles & non-orphans are rejected, which is fine here
+        if i % 4 == 0 {
+            let (parent, orphan) = (arena.get_dense(i / 2), arena.get_dense(i - 4));
+            let _ = arena.attach(parent.unwrap(), orphan.unwrap());
+        }
+    }
+
+    let step = if cfg!(miri) { 7 } else { 1 };
+    for a in arena.iter_range(..).step_by(step) {
+        for b in arena.iter_range(..).step_by(step) {
+            let expected = b.ancestors().any(|n| std::ptr::eq(n, a));
+            assert_eq!(a.is_ancestor_of(b), expected, "{a} & {b}");
+            assert_eq!(b.is_descendant_of(a), expected, "{a} & {b}");
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::rc::Rc;
+
+use silva::{Arena, ChildOrder};
+
+#[test]
+fn grafts_roots() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let mut arena = Arena::with_child_order(order);
+        let root = arena.push(None, "root").index();
+        arena.push(root, "x");
+
+        let other = Arena::with_child_order(order);
+        let a = other.push(None, "a");
+        other.push(a, "a1");
+        other.push(a, "a2");
+        let b = other.push(None, "b");
+        other.push(b, "b1");
+        let before: Vec<_> = [a, b]
+            .iter()
+            .flat_map(|n| n.descendants_with_depth())
+            .map(|(depth, n)| (depth + 1, n.value))
+            .collect();
+        let (a, b) = (a.index(), b.index());
+
+        let map = arena.append(other, root);
+        assert_eq!(arena.len(), 7);
+        let root = &arena[root];
+        let after: Vec<_> = root
+            .descendants_with_depth()
+            .skip(1)
+            .filter(|(_, n)| n.value != "x")
+            .map(|(depth, n)| (depth, n.value))
+            .collect();
+        let mut expected = before.clone();
+        if order == ChildOrder::NewestFirst {
+            // b was pushed under root after a, so comes first
+            expected.rotate_left(3);
+        }
+        assert_eq!(after, expected, "{order:?}");
+        assert_eq!(arena[map[a]].value, "a");
+        assert_eq!(arena[map[b]].parent().unwrap().index(), root.index());
+    }
+}
+
+#[test]
+fn as_roots() {
+    let mut arena = Arena::new();
+    arena.push(None, 0);
+    let other = Arena::new();
+    let a = other.push(None, 1);
+    other.push(a, 2);
+    let map = arena.append(other, None);
+    assert_eq!(map.len(), 2);
+    assert!(arena.roots().map(|n| n.value).eq([0, 1]));
+}
+
+#[test]
+fn keeps_orphans() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    let other = Arena::new();
+    let orphan = other.push_orphan(1);
+    other.push(orphan, 2);
+    let attached = other.push_orphan(3);
+    let top = other.push(None, 4);
+    other.attach(top, attached).unwrap();
+    let (orphan, attached) = (orphan.index(), attached.index());
+
+    let map = arena.append(other, root);
+    assert!(arena[map[orphan]].is_orphan());
+    assert!(arena[map[orphan]].child_values().eq(&[2]));
+    assert_eq!(arena[map[attached]].parent().unwrap().value, 4);
+    assert!(arena[root].child_values().eq(&[4]));
+}
+
+#[test]
+fn no_clones() {
+    let shared = Rc::new(());
+    let mut arena = Arena::new();
+    let other = Arena::new();
+    let a = other.push(None, Rc::clone(&shared));
+    other.push(a, Rc::clone(&shared));
+    arena.append(other, None);
+    assert_eq!(Rc::strong_count(&shared), 3);
+    drop(arena);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
+
+#[test]
+fn removed_nodes() {
+    let mut arena = Arena::new();
+    let mut other = Arena::new();
+    let a = other.push(None, 0).index();
+    let b = other.push(a, 1).index();
+    other.push(a, 2);
+    other.remove_subtree(b);
+    let map = arena.append(other, None);
+    assert_eq!(map.len(), 2);
+    assert!(!map.contains_key(b));
+    assert_eq!(arena.len(), 2);
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 use std::thread;
 
-use silva::Arena;
+use silva::{Arena, ArenaLayout, LayoutError};
 
 macro_rules! assert_ptr_eq {
     ($left:expr, $right:expr $(,)?) => {
@@ -182,7 +182,7 @@ fn tree_macro() {
     assert_eq!(root2.value, "root2");
     assert_eq!(one.value, "one");
 
-    for (child, name) in one.children().zip(names[1..].into_iter().rev()) {
+    for (child, name) in one.children().zip(names[1..].iter().rev()) {
         assert_eq!(*name, child.value);
     }
 }
@@ -207,18 +207,155 @@ fn iter() {
 #[test]
 fn capacity_reserve() {
     let arena = Arena::<()>::with_capacity(0);
-    for i in 0..SLOTS {
+    arena.reserve(0);
+    assert_eq!(arena.capacity(), 0);
+    for i in 1..=SLOTS {
         arena.reserve(i);
         assert_eq!(arena.capacity(), SLOTS);
     }
-    for i in SLOTS..SLOTS * 3 {
+    for i in SLOTS + 1..=SLOTS * 3 {
         arena.reserve(i);
         assert_eq!(arena.capacity(), SLOTS * 3);
     }
-    for i in SLOTS * 3..SLOTS * 7 {
+    for i in SLOTS * 3 + 1..=SLOTS * 7 {
         arena.reserve(i);
         assert_eq!(arena.capacity(), SLOTS * 7);
     }
+
+    // a request filling whole buckets takes no more
+    let arena = Arena::<()>::new();
+    arena.try_reserve(SLOTS).unwrap();
+    assert_eq!(arena.capacity(), SLOTS);
+}
+
+/// Reserve one node at a time, checking the capacity covers whole buckets
+fn check_reserve(layout: ArenaLayout, buckets: usize) {
+    let arena = Arena::<()>::with_layout(layout);
+    arena.reserve(0);
+    assert_eq!(arena.capacity(), 0);
+    let (mut capacity, mut len) = (0, layout.first_bucket());
+    for _ in 0..buckets {
+        for i in capacity + 1..=capacity + len {
+            arena.reserve(i);
+            assert_eq!(arena.capacity(), capacity + len, "{layout:?}, {i}");
+        }
+        capacity += len;
+        len *= layout.growth();
+    }
+}
+
+#[test]
+fn capacity_reserve_layouts() {
+    check_reserve(ArenaLayout::default(), 3);
+    check_reserve(ArenaLayout::new(SLOTS, 4).unwrap(), 3);
+    check_reserve(ArenaLayout::new(SLOTS * 4, 2).unwrap(), 3);
+    check_reserve(ArenaLayout::new(SLOTS * 2, 8).unwrap(), 2);
+}
+
+#[test]
+fn push_layouts() {
+    for (layout, capacity) in [
+        (ArenaLayout::new(SLOTS, 4).unwrap(), SLOTS * 21),
+        (ArenaLayout::new(SLOTS * 4, 2).unwrap(), SLOTS * 12),
+    ] {
+        let arena = Arena::with_layout(layout);
+        let root = arena.push(None, 0);
+        for i in 1..SLOTS * 8 {
+            arena.push(root, i);
+        }
+        assert_eq!(arena.capacity(), capacity, "{layout:?}");
+        for i in 0..SLOTS * 8 {
+            assert_eq!(arena.get_dense(i).unwrap().value, i, "{layout:?}");
+        }
+
+        let arena = arena.map(|i| i * 2);
+        assert_eq!(arena.layout(), layout);
+        assert_eq!(
+            arena.get_dense(SLOTS * 8 - 1).unwrap().value,
+            SLOTS * 16 - 2
+        );
+        let root = arena.get_dense(0).unwrap();
+        assert_eq!(root.children().count(), SLOTS * 8 - 1);
+    }
+}
+
+#[test]
+fn layout_errors() {
+    assert_eq!(ArenaLayout::new(SLOTS, 2), Ok(ArenaLayout::default()));
+    assert_eq!(
+        ArenaLayout::new(SLOTS * 3, 2),
+        Err(LayoutError::FirstBucket(SLOTS * 3))
+    );
+    assert_eq!(ArenaLayout::new(SLOTS, 1), Err(LayoutError::Growth(1)));
+    assert_eq!(
+        LayoutError::Growth(3).to_string(),
+        "invalid growth factor 3"
+    );
+}
+
+#[test]
+fn with_capacity_bounds() {
+    const MAX_INDEX: usize = isize::MAX as usize - SLOTS;
+
+    assert_eq!(Arena::<()>::with_capacity(1).capacity(), SLOTS);
+    assert_eq!(Arena::<()>::with_capacity(SLOTS).capacity(), SLOTS);
+    assert_eq!(Arena::<()>::with_capacity(SLOTS + 1).capacity(), SLOTS * 3);
+    assert_eq!(Arena::<()>::try_with_capacity(0).unwrap().capacity(), 0);
+    assert_eq!(
+        Arena::<()>::try_with_capacity(SLOTS).unwrap().capacity(),
+        SLOTS
+    );
+
+    for capacity in [MAX_INDEX + 2, usize::MAX] {
+        let err = Arena::<()>::try_with_capacity(capacity).err();
+        assert_eq!(err, Some(silva::AllocError::CapacityOverflow));
+    }
+}
+
+#[test]
+fn try_reserve() {
+    let arena = Arena::<usize>::new();
+    assert_eq!(
+        arena.try_reserve(usize::MAX),
+        Err(silva::AllocError::CapacityOverflow)
+    );
+    assert_eq!(arena.capacity(), 0);
+    arena.try_reserve(SLOTS + 1).unwrap();
+    assert_eq!(arena.capacity(), SLOTS * 3);
+
+    // side tables are reserved too, so nothing is left to allocate on push
+    let arena = Arena::with_subtree_counts();
+    arena.try_reserve(SLOTS).unwrap();
+    let root = arena.push_within_capacity(None, 0).unwrap();
+    for i in 1..SLOTS {
+        arena.push_within_capacity(root, i).unwrap();
+    }
+    assert_eq!(arena.subtree_len(root), SLOTS);
+}
+
+#[test]
+fn reserve_after_remove() {
+    let reserves: [fn(&Arena<usize>, usize); 2] = [
+        |arena, n| arena.try_reserve(n).unwrap(),
+        |arena, n| arena.reserve(n),
+    ];
+    for reserve in reserves {
+        let mut arena = Arena::with_subtree_counts();
+        let root = arena.push(None, 0).index();
+        for i in 1..100 {
+            arena.push(root, i);
+        }
+        arena.remove_subtree(root);
+        assert_eq!(arena.count(), 0);
+
+        // new nodes go after the removed ones, so reserving counts from there
+        reserve(&arena, 150);
+        let root = arena.push_within_capacity(None, 0).unwrap();
+        for i in 1..150 {
+            arena.push_within_capacity(root, i).unwrap();
+        }
+        assert_eq!(arena.count(), 150);
+    }
 }
 
 #[test]
@@ -264,16 +401,223 @@ fn push_all() {
     let arena = Arena::new();
     let root = arena.push(None, 0);
 
-    for (i, node) in arena.push_all(root, [1, 2, 3, 4, 5]).enumerate() {
+    let pushed = arena.push_all(root, [1, 2, 3, 4, 5]);
+    assert_eq!(pushed.len(), 5);
+    assert_eq!(pushed.index_range(), 1..6);
+    // every node is written up front
+    assert_eq!(arena.count(), 6);
+    assert_ptr_eq!(root.child(), pushed.clone().last());
+
+    for (i, node) in pushed.enumerate() {
         assert_eq!(
             node.iter_next().map(|n| n.value).collect::<Vec<_>>(),
             (1..=i).rev().collect::<Vec<_>>()
         );
         assert_eq!(node.value, i + 1);
         assert_ptr_eq!(root, node.parent());
-        assert_ptr_eq!(root.child(), node);
     }
 }
 
+#[test]
+fn push_all_rev() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+
+    let mut pushed = arena.push_all(root, 1..SLOTS * 2 + 1);
+    assert_eq!(pushed.next().unwrap().value, 1);
+    assert_eq!(pushed.index_range(), 2..SLOTS * 2 + 1);
+    assert!(pushed.rev().map(|n| n.value).eq((2..=SLOTS * 2).rev()));
+
+    // writes happen in iteration order regardless of how the nodes are read
+    assert!(root.children().map(|n| n.value).eq((1..=SLOTS * 2).rev()));
+    for i in 1..=SLOTS * 2 {
+        assert_eq!(arena.get_dense(i).unwrap().value, i);
+    }
+    assert!(arena.get_dense(SLOTS * 2 + 1).is_none());
+}
+
+#[test]
+fn push_all_unconsumed() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let range = arena.push_all(root, [1, 2, 3]).index_range();
+    assert_eq!(arena.count(), 4);
+    assert!(
+        range
+            .map(|i| arena.get_dense(i).unwrap().value)
+            .eq([1, 2, 3])
+    );
+}
+
 // taken from arena::raw
 pub const SLOTS: usize = usize::BITS as usize;
+
+#[test]
+fn iter_range() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    for i in 1..SLOTS * 3 {
+        arena.push(root, i);
+    }
+
+    let spanning = SLOTS - 4..SLOTS * 2 + 4;
+    assert!(
+        arena
+            .iter_range(spanning.clone())
+            .map(|n| n.value)
+            .eq(spanning)
+    );
+    assert!(
+        arena
+            .iter_range(..=3)
+            .map(|n| n.index().as_usize())
+            .eq(0..=3)
+    );
+    assert!(
+        arena
+            .iter_from(SLOTS * 3 - 2)
+            .map(|n| n.value)
+            .eq([SLOTS * 3 - 2, SLOTS * 3 - 1])
+    );
+
+    assert_eq!(arena.iter_range(5..5).count(), 0);
+    assert_eq!(arena.iter_range(SLOTS * 3..SLOTS * 10).count(), 0);
+    assert_eq!(arena.iter_range(SLOTS * 3 - 1..).count(), 1);
+
+    assert_eq!(arena.count_initialized_below(SLOTS + 1), SLOTS + 1);
+    assert_eq!(arena.count_initialized_below(usize::MAX), SLOTS * 3);
+    assert_eq!(arena.count_initialized_below(0), 0);
+}
+
+#[test]
+fn link_race() {
+    let threads = if cfg!(miri) { 3 } else { 8 };
+    let per_thread = if cfg!(miri) { 4 } else { 1_000 };
+
+    let arena = Arena::new();
+    let root = arena.push(None, usize::MAX).index();
+    let orphan = arena.push_orphan(usize::MAX).index();
+
+    thread::scope(|s| {
+        let arena = &arena;
+        for t in 0..threads {
+            s.spawn(move || {
+                for i in 0..per_thread {
+                    arena.push(root, t * per_thread + i);
+                }
+            });
+        }
+        s.spawn(move || arena.attach(&arena[root], &arena[orphan]).unwrap());
+        // readers reaching nodes through both the parent & the slots
+        s.spawn(move || {
+            for _ in 0..threads {
+                assert!(arena[root].children().all(|n| n.parent().is_some()));
+            }
+        });
+        s.spawn(move || {
+            for _ in 0..threads {
+                for node in arena.iter_range(..) {
+                    let _ = node.next().map(|n| n.value);
+                }
+            }
+        });
+    });
+
+    assert_eq!(arena[root].children().count(), threads * per_thread + 1);
+}
+
+#[test]
+fn map() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    for i in 1..SLOTS * 3 {
+        arena.push(arena.get_dense(i / 2).unwrap(), i);
+    }
+
+    let mut order = Vec::new();
+    let mapped = arena.map(|i| {
+        order.push(i);
+        i.to_string()
+    });
+    assert!(order.into_iter().eq(0..SLOTS * 3));
+    assert_eq!(mapped.count(), SLOTS * 3);
+
+    for node in mapped.iter_range(1..) {
+        let i = node.index().as_usize();
+        assert_eq!(node.value, i.to_string());
+        assert_eq!(node.parent().unwrap().index().as_usize(), i / 2);
+        assert!(mapped.contains(node.parent().unwrap()));
+    }
+    assert!(mapped[root].children().map(|n| n.value.as_str()).eq(["1"]));
+    assert_eq!(
+        mapped.push(root, String::new()).index().as_usize(),
+        SLOTS * 3
+    );
+}
+
+#[test]
+fn len_acquire() {
+    let n = thread::available_parallelism().unwrap().get();
+    let total = if cfg!(miri) { n * 2 } else { n.pow(2) * 8 };
+    let step = total / n;
+
+    let arena = Arena::new();
+    assert!(arena.is_empty());
+    let root = arena.push(None, usize::MAX).index();
+
+    thread::scope(|s| {
+        let arena = &arena;
+        for t in 0..n {
+            s.spawn(move || {
+                for i in t * step..(t + 1) * step {
+                    arena.push(root, i);
+                }
+            });
+        }
+
+        s.spawn(move || {
+            while arena.len_acquire() < total + 1 {
+                std::hint::spin_loop();
+            }
+            assert_eq!(arena.reserved(), total + 1);
+            for i in 0..=total {
+                assert!(arena.get_dense(i).is_some());
+            }
+        });
+    });
+
+    assert_eq!(arena.len(), total + 1);
+    assert_eq!(arena.len(), arena.count());
+    assert!(!arena.is_empty());
+}
+
+#[test]
+fn push_with_ordinal() {
+    let threads = if cfg!(miri) { 3 } else { 8 };
+    let per_thread = if cfg!(miri) { 4 } else { 500 };
+
+    let arena = Arena::new();
+    let (root, ordinal) = arena.push_with_ordinal(None, usize::MAX);
+    assert_eq!(ordinal, 0);
+    let root = root.index();
+
+    let mut ordinals: Vec<usize> = thread::scope(|s| {
+        let arena = &arena;
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                s.spawn(move || {
+                    (0..per_thread)
+                        .map(|i| arena.push_with_ordinal(root, t * per_thread + i).1)
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect()
+    });
+
+    ordinals.sort_unstable();
+    assert!(ordinals.into_iter().eq(0..threads * per_thread));
+}
@@ -0,0 +1,77 @@
+use silva::{Arena, Node};
+
+#[test]
+fn exact_arity() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let lhs = arena.push(root, 1);
+    let rhs = arena.push(root, 2);
+
+    let [a, b] = root.children_array().unwrap();
+    assert!(std::ptr::eq(a, lhs));
+    assert!(std::ptr::eq(b, rhs));
+
+    assert!(root.children_array::<0>().is_none());
+    assert!(root.children_array::<1>().is_none());
+    assert!(root.children_array::<3>().is_none());
+    assert!(lhs.children_array::<0>().is_some());
+}
+
+#[test]
+fn single_child() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    assert!(root.single_child().is_none());
+
+    let only = arena.push(root, 1);
+    assert!(std::ptr::eq(root.single_child().unwrap(), only));
+
+    arena.push(root, 2);
+    assert!(root.single_child().is_none());
+}
+
+#[derive(Debug)]
+enum Expr {
+    Num(i64),
+    Neg,
+    Add,
+    Sub,
+    Div,
+}
+
+fn eval(node: &Node<Expr>) -> Option<i64> {
+    match node.value {
+        Expr::Num(n) => node.children_array::<0>().map(|[]| n),
+        Expr::Neg => eval(node.single_child()?).map(|n| -n),
+        Expr::Add | Expr::Sub | Expr::Div => {
+            let [lhs, rhs] = node.children_array()?;
+            let (lhs, rhs) = (eval(lhs)?, eval(rhs)?);
+            match node.value {
+                Expr::Add => Some(lhs + rhs),
+                Expr::Sub => Some(lhs - rhs),
+                _ => lhs.checked_div(rhs),
+            }
+        }
+    }
+}
+
+#[test]
+fn evaluator() {
+    let arena = Arena::new();
+    // (10 - 4) / -(2 + 1)
+    let div = arena.push(None, Expr::Div);
+    let sub = arena.push(div, Expr::Sub);
+    arena.push(sub, Expr::Num(10));
+    arena.push(sub, Expr::Num(4));
+    let neg = arena.push(div, Expr::Neg);
+    let add = arena.push(neg, Expr::Add);
+    arena.push(add, Expr::Num(2));
+    arena.push(add, Expr::Num(1));
+
+    assert_eq!(eval(div), Some(-2));
+    assert_eq!(eval(sub), Some(6));
+
+    // malformed, a third operand
+    arena.push(add, Expr::Num(7));
+    assert_eq!(eval(div), None);
+}
@@ -0,0 +1,50 @@
+use std::thread;
+
+use silva::{Arena, Atomic};
+
+#[test]
+fn counts_while_pushing() {
+    let arena = Arena::new();
+    let root = arena.push_atomic(None, 0usize);
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..25 {
+                    let child = arena.push_atomic(root, 0);
+                    child.store(1);
+                    root.fetch_update(|n| Some(n + child.load())).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(root.load(), 100);
+    assert_eq!(root.children().map(|c| c.load()).sum::<usize>(), 100);
+}
+
+#[test]
+fn fetch_update_stops() {
+    let arena = Arena::new();
+    let flag = arena.push_atomic(None, false);
+    assert_eq!(flag.fetch_update(|set| (!set).then_some(true)), Ok(false));
+    assert_eq!(flag.fetch_update(|set| (!set).then_some(true)), Err(true));
+    assert!(flag.load());
+}
+
+#[test]
+fn floats() {
+    let arena = Arena::new();
+    let node = arena.push_atomic(None, 1.5f64);
+    assert_eq!(node.value.swap(2.0), 1.5);
+    node.fetch_update(|x| Some(x * 2.0)).unwrap();
+    assert_eq!(node.load(), 4.0);
+    assert_eq!(format!("{:?}", node.value), "Atomic(4.0)");
+}
+
+#[test]
+fn into_inner() {
+    let arena = Arena::new();
+    arena.push(None, Atomic::from(-3i32));
+    arena.push(None, Atomic::default());
+    let values: Vec<_> = arena.into_values().map(Atomic::into_inner).collect();
+    assert_eq!(values, [-3, 0]);
+}
@@ -0,0 +1,93 @@
+use std::sync::mpsc;
+use std::thread;
+
+use silva::{Arena, AttachError};
+
+#[test]
+fn bottom_up() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|s| {
+        for i in 1..=4 {
+            let tx = tx.clone();
+            let arena = &arena;
+            s.spawn(move || {
+                let leaf = arena.push_orphan(i * 10);
+                arena.push(leaf, i * 10 + 1);
+                arena.push(leaf, i * 10 + 2);
+                tx.send(leaf.index()).unwrap();
+            });
+        }
+        drop(tx);
+
+        for leaf in rx {
+            assert!(arena[leaf].is_orphan());
+            assert_eq!(arena.roots().count(), 1);
+            arena.attach(root, &arena[leaf]).unwrap();
+        }
+    });
+
+    assert!(arena.roots().map(|n| n.value).eq([0]));
+    let mut leaves = root.children().map(|n| n.value).collect::<Vec<_>>();
+    leaves.sort_unstable();
+    assert_eq!(leaves, [10, 20, 30, 40]);
+    for leaf in root.children() {
+        assert!(!leaf.is_orphan());
+        assert!(std::ptr::eq(leaf.parent().unwrap(), root));
+        assert!(
+            leaf.children()
+                .map(|n| n.value)
+                .eq([leaf.value + 2, leaf.value + 1])
+        );
+    }
+}
+
+#[test]
+fn double_attach() {
+    let arena = Arena::new();
+    let a = arena.push(None, "a");
+    let b = arena.push(None, "b");
+    let orphan = arena.push_orphan("orphan");
+
+    assert_eq!(orphan.parent().map(|n| n.value), None);
+    arena.attach(a, orphan).unwrap();
+    assert_eq!(
+        arena.attach(b, orphan),
+        Err(AttachError::NotOrphan(orphan.index()))
+    );
+    assert_eq!(arena.attach(a, b), Err(AttachError::NotOrphan(b.index())));
+    assert_eq!(orphan.parent().map(|n| n.value), Some("a"));
+    assert!(b.child().is_none());
+}
+
+#[test]
+fn attach_cycle() {
+    let arena = Arena::new();
+    let orphan = arena.push_orphan(0);
+    let child = arena.push(orphan, 1);
+    let grandchild = arena.push(child, 2);
+
+    for parent in [orphan, child, grandchild] {
+        assert_eq!(
+            arena.attach(parent, orphan),
+            Err(AttachError::Cycle {
+                parent: parent.index(),
+                orphan: orphan.index()
+            })
+        );
+    }
+    assert!(orphan.is_orphan());
+    assert_eq!(arena.roots().count(), 0);
+}
+
+#[test]
+#[should_panic = "node does not belong to this arena"]
+fn attach_foreign() {
+    let arena = Arena::new();
+    let other = Arena::new();
+    let parent = arena.push(None, 0);
+    let orphan = other.push_orphan(1);
+    let _ = arena.attach(parent, orphan);
+}
@@ -0,0 +1,71 @@
+//! Arenas of borrowed values must work with the whole API, so nothing should
+//! require `T: 'static`.
+
+use std::sync::Arc;
+
+use silva::{Arena, Index};
+
+fn read_api<'src>(arena: &Arena<&'src str>, root: Index) -> Vec<&'src str> {
+    let node = &arena[root];
+    let _ = arena.get(root).unwrap().debug();
+    let _ = format!("{node} {:?} {:#?}", node.debug(), node.debug());
+    let _ = arena.try_resolve_parent(node).unwrap();
+    let _ = arena.timestamp(root);
+    assert!(arena.contains(node));
+    assert!(node.ancestors().all(|n| arena.contains(n)));
+    assert!(node.iter_next().all(|n| arena.contains(n)));
+    assert_eq!(
+        arena.roots().count() + arena.iter_from(1).count(),
+        arena.count()
+    );
+
+    let mut values: Vec<&'src str> = node.children().map(|n| n.value).collect();
+    values.extend(arena.iter_range(..).map(|n| n.value));
+    values
+}
+
+#[test]
+fn read_write_api() {
+    let source = String::from("root a b c d e");
+    let words: Vec<&str> = source.split(' ').collect();
+
+    let arena = Arc::new(Arena::new());
+    let root = arena.push(None, words[0]);
+    arena.push_all(root, words[1..3].iter().copied());
+    arena.try_push(root.index(), words[3]).unwrap();
+    arena.push_with(Some(root.index()), |_| words[4]);
+    let orphan = arena.push_orphan(words[5]);
+    arena.attach(root, orphan).unwrap();
+
+    let handle = root.handle(&arena);
+    assert_eq!(arena.push(&handle, "f").parent().unwrap().value, "root");
+    let values = read_api(&arena, handle.index());
+    assert_eq!(values[..6], ["f", "e", "d", "c", "b", "a"]);
+}
+
+#[test]
+fn into_owned() {
+    let source = String::from("root a b c");
+    let arena = Arena::with_clock(|| 7);
+    let root = arena.push(None, &source[..4]).index();
+    let orphan = arena.push_orphan(&source[5..6]).index();
+    let b = arena.push(root, &source[7..8]).index();
+    arena.push(b, &source[9..]);
+    arena.attach(&arena[root], &arena[orphan]).unwrap();
+    let unattached = arena.push_orphan(&source[..1]).index();
+
+    let owned: Arena<String> = arena.map_into_owned();
+    drop(source);
+
+    assert_eq!(owned.count(), 5);
+    assert_eq!(owned[root].value, "root");
+    assert!(owned[root].children().map(|n| n.index()).eq([orphan, b]));
+    assert_eq!(owned[b].child().unwrap().value, "c");
+    assert_eq!(owned[orphan].parent().unwrap().index(), root);
+    assert!(owned[unattached].is_orphan());
+    assert!(owned.roots().map(|n| n.index()).eq([root]));
+    assert_eq!(owned.timestamp(b), Some(7));
+
+    let b = &owned[b];
+    assert!(std::ptr::eq(owned.push(b, "d".into()).parent().unwrap(), b));
+}
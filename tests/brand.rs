@@ -0,0 +1,30 @@
+use silva::{Arena, Index};
+
+#[test]
+fn round_trip() {
+    let arena = Arena::new();
+    let other = Arena::new();
+    let outside = other.push(None, "other");
+
+    let raw: Index = arena.branded(|ast| {
+        let item = ast.push(None, "fn");
+        let name = ast.push(Some(item), "main");
+
+        assert_eq!(ast[name].value, "main");
+        assert_eq!(ast.get(name).parent().unwrap().value, "fn");
+        assert_eq!(ast.index_of(&ast[name]), Some(name));
+        assert_eq!(ast.index_of(outside), None);
+        assert_eq!(ast.index(name.index()), Some(name));
+        assert_eq!(ast.index(Index::from(name)).unwrap(), name);
+        assert_eq!(format!("{name:?} {name}"), "BrandedIndex(1) 1");
+        name.into()
+    });
+    assert_eq!(arena[raw].value, "main");
+
+    // nodes pushed through the arena itself can be branded too
+    arena.branded(|ast| {
+        let late = ast.arena().push(None, "late").index();
+        assert_eq!(ast[ast.index(late).unwrap()].value, "late");
+        assert!(other.branded(|other| other.index(late).is_none()));
+    });
+}
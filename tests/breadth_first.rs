@@ -0,0 +1,73 @@
+use silva::Arena;
+
+#[test]
+fn level_order() {
+    let arena = Arena::new();
+    let root;
+    silva::tree![
+        &arena,
+        root = ("root") = [
+            ("a") = [("a1") = [("a11")], ("a2")],
+            ("b"),
+            ("c") = [("c1")]
+        ]
+    ];
+
+    assert!(
+        root.breadth_first()
+            .map(|n| n.value)
+            .eq(["root", "c", "b", "a", "c1", "a2", "a1", "a11"])
+    );
+    let a = root.children().last().unwrap();
+    assert!(
+        a.breadth_first()
+            .map(|n| n.value)
+            .eq(["a", "a2", "a1", "a11"])
+    );
+}
+
+#[test]
+fn levels_are_contiguous() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut frontier = vec![root];
+    for depth in 1..5 {
+        frontier = frontier
+            .iter()
+            .flat_map(|&parent| (0..depth).map(move |_| (parent, depth)))
+            .map(|(parent, depth)| arena.push(parent, depth))
+            .collect();
+    }
+    arena.push(None, 9);
+
+    let depths: Vec<_> = root.breadth_first().map(|n| n.value).collect();
+    assert_eq!(depths.len(), 1 + 1 + 2 + 2 * 3 + 2 * 3 * 4);
+    assert!(depths.is_sorted());
+    assert!(
+        root.breadth_first()
+            .all(|n| n.ancestors().count() == n.value)
+    );
+}
+
+#[test]
+fn leaf() {
+    let arena = Arena::new();
+    let leaf = arena.push(None, 0);
+    let mut iter = leaf.breadth_first();
+    assert_eq!(iter.next().map(|n| n.value), Some(0));
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn deep_chain() {
+    let depth = if cfg!(miri) { 1_000 } else { 100_000 };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut parent = root;
+    for i in 1..depth {
+        parent = arena.push(parent, i);
+    }
+
+    assert!(root.breadth_first().map(|n| n.value).eq(0..depth));
+}
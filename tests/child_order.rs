@@ -0,0 +1,150 @@
+use std::thread;
+
+use silva::{Arena, ChildOrder, Node};
+
+fn indices<'a, T: 'a>(nodes: impl IntoIterator<Item = &'a Node<T>>) -> Vec<usize> {
+    nodes.into_iter().map(|n| n.index().as_usize()).collect()
+}
+
+#[test]
+fn default_is_newest_first() {
+    let arena = Arena::new();
+    assert_eq!(arena.child_order(), ChildOrder::NewestFirst);
+    let root = arena.push(None, 0);
+    arena.push_all(root, 1..4);
+    assert_eq!(root.child_order(), ChildOrder::NewestFirst);
+    assert!(root.child_values().eq(&[3, 2, 1]));
+}
+
+#[test]
+fn insertion_order() {
+    let arena = Arena::with_child_order(ChildOrder::InsertionOrder);
+    assert_eq!(arena.child_order(), ChildOrder::InsertionOrder);
+    let root = arena.push(None, 0);
+    assert_eq!(root.child_order(), ChildOrder::InsertionOrder);
+    assert!(root.child().is_none());
+
+    let a = arena.push(root, 1);
+    let (b, ordinal) = arena.push_with_ordinal(root, 2);
+    assert_eq!(ordinal, 1);
+    arena.push(a, 3);
+    arena.push(root, 4);
+
+    assert!(root.child_values().eq(&[1, 2, 4]));
+    assert_eq!(root.child().map(|n| n.value), Some(1));
+    assert!(b.iter_next().map(|n| n.value).eq([4]));
+    assert!(root.descendant_values().eq(&[0, 1, 3, 2, 4]));
+    assert!(root.post_order().map(|n| n.value).eq([3, 1, 2, 4, 0]));
+    assert!(root.breadth_first().map(|n| n.value).eq([0, 1, 2, 4, 3]));
+    assert_eq!(
+        root.children_array().map(|c: [_; 3]| c.map(|n| n.value)),
+        Some([1, 2, 4])
+    );
+    assert_eq!(
+        root.reduce_children(Vec::new(), |mut acc, n| {
+            acc.push(n.value);
+            acc
+        }),
+        [1, 2, 4]
+    );
+    let stream: Vec<_> = arena.to_depth_stream(root).map(|(d, &v)| (d, v)).collect();
+    assert_eq!(stream, [(0, 0), (1, 1), (2, 3), (1, 2), (1, 4)]);
+}
+
+#[test]
+fn attach_appends() {
+    let arena = Arena::with_child_order(ChildOrder::InsertionOrder);
+    let root = arena.push(None, 0);
+    let orphan = arena.push_orphan(1);
+    arena.push(root, 2);
+    arena.attach(root, orphan).unwrap();
+    arena.push(root, 3);
+    assert!(root.child_values().eq(&[2, 1, 3]));
+
+    // the first child can be an attached orphan too
+    let leaf = arena.push(None, 4);
+    let orphan = arena.push_orphan(5);
+    arena.attach(leaf, orphan).unwrap();
+    arena.push(leaf, 6);
+    assert!(leaf.child_values().eq(&[5, 6]));
+}
+
+#[test]
+fn map_keeps_order() {
+    let arena = Arena::with_child_order(ChildOrder::InsertionOrder);
+    let root = arena.push(None, 0);
+    arena.push(root, 1);
+    let mapped = arena.map(|v| v * 10);
+    assert_eq!(mapped.child_order(), ChildOrder::InsertionOrder);
+
+    let root = mapped.get_dense(0).unwrap();
+    mapped.push(root, 20);
+    mapped.push(root, 30);
+    assert!(root.child_values().eq(&[10, 20, 30]));
+}
+
+#[test]
+fn concurrent_appends() {
+    let (threads, per_thread) = if cfg!(miri) { (4, 16) } else { (8, 1_000) };
+    let arena = Arena::with_child_order(ChildOrder::InsertionOrder);
+    let root = arena.push(None, (usize::MAX, 0));
+
+    let ordinals: Vec<Vec<(usize, usize)>> = thread::scope(|s| {
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                let arena = &arena;
+                s.spawn(move || {
+                    (0..per_thread)
+                        .map(|i| {
+                            let (node, ordinal) = arena.push_with_ordinal(root, (t, i));
+                            (node.index().as_usize(), ordinal)
+                        })
+                        .collect()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    let children = indices(root.children());
+    assert_eq!(children.len(), threads * per_thread);
+    // every push got its own place, & was told where it is
+    for &(index, ordinal) in ordinals.iter().flatten() {
+        assert_eq!(children[ordinal], index);
+    }
+    // each thread's children are kept in the order it pushed them
+    for t in 0..threads {
+        let own = root.child_values().filter(|&&(thread, _)| thread == t);
+        assert!(own.map(|&(_, i)| i).eq(0..per_thread), "thread {t}");
+    }
+}
+
+#[test]
+fn reversed() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        arena.push_all(root, 1..6);
+        let forward: Vec<_> = root.child_values().copied().collect();
+        let mut backward: Vec<_> = root.children().rev().map(|n| n.value).collect();
+        backward.reverse();
+        assert_eq!(forward, backward, "{order:?}");
+
+        // both ends meet in the middle
+        let mut children = root.children().map(|n| n.value);
+        let (first, last) = (forward[0], forward[4]);
+        assert_eq!(children.next(), Some(first));
+        assert_eq!(children.next_back(), Some(last));
+        assert_eq!(children.next_back(), Some(forward[3]));
+        assert_eq!(children.next(), Some(forward[1]));
+        assert_eq!(children.next(), Some(forward[2]));
+        assert_eq!(children.next_back(), None);
+        assert_eq!(children.next(), None);
+    }
+
+    // newest first, so reversing gives insertion order
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    arena.push_all(root, 1..4);
+    assert!(root.children().rev().map(|n| n.value).eq([1, 2, 3]));
+}
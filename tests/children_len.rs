@@ -0,0 +1,90 @@
+use std::thread;
+
+use silva::{Arena, ChildOrder};
+
+const ORDERS: [ChildOrder; 2] = [ChildOrder::NewestFirst, ChildOrder::InsertionOrder];
+
+#[test]
+fn exact() {
+    for order in ORDERS {
+        let arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        assert_eq!(root.children().len(), 0, "{order:?}");
+
+        arena.push_all(root, 1..5);
+        let orphan = arena.push_orphan(5);
+        arena.attach(root, orphan).unwrap();
+        let mut children = root.children();
+        assert_eq!(children.len(), 5, "{order:?}");
+        children.next();
+        assert_eq!(children.len(), 4, "{order:?}");
+        children.next_back();
+        assert_eq!(children.len(), 3, "{order:?}");
+        assert_eq!(children.count(), 3, "{order:?}");
+
+        for child in root.children() {
+            assert_eq!(
+                child.iter_next().len(),
+                child.iter_next().count(),
+                "{order:?}"
+            );
+        }
+        assert_eq!(root.iter_next().len(), 0, "{order:?}");
+    }
+}
+
+#[test]
+fn fixed_when_created() {
+    for order in ORDERS {
+        let arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        arena.push_all(root, 1..3);
+        let children = root.children();
+        arena.push(root, 3);
+
+        // later children are left out whichever end they are linked at
+        let values: Vec<_> = children.map(|n| n.value).collect();
+        assert_eq!(values.len(), 2, "{order:?}");
+        assert!(!values.contains(&3), "{order:?}");
+        assert_eq!(root.children().len(), 3, "{order:?}");
+    }
+}
+
+#[test]
+fn concurrent() {
+    let (threads, per_thread) = if cfg!(miri) { (4, 16) } else { (8, 500) };
+    for order in ORDERS {
+        let arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        thread::scope(|s| {
+            for _ in 0..threads {
+                s.spawn(|| {
+                    for i in 0..per_thread {
+                        arena.push(root, i);
+                        let children = root.children();
+                        let len = children.len();
+                        assert!(len > i);
+                        assert_eq!(children.count(), len);
+                    }
+                });
+            }
+        });
+        assert_eq!(root.children().len(), threads * per_thread);
+    }
+}
+
+#[test]
+fn child_count() {
+    for order in ORDERS {
+        let arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        let leaf = arena.push(root, 1);
+        assert_eq!(leaf.child_count(), 0, "{order:?}");
+        arena.push_all(root, 2..10);
+        let orphan = arena.push_orphan(10);
+        assert_eq!(root.child_count(), 9, "{order:?}");
+        arena.attach(root, orphan).unwrap();
+        assert_eq!(root.child_count(), 10, "{order:?}");
+        assert_eq!(root.child_count(), root.children().count(), "{order:?}");
+    }
+}
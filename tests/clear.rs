@@ -0,0 +1,52 @@
+use std::rc::Rc;
+
+use silva::{Arena, ChildOrder};
+
+#[test]
+fn clear_and_rebuild() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let mut arena = Arena::with_child_order(order);
+        for _ in 0..3 {
+            let root = arena.push(None, 0);
+            for i in 1..500 {
+                arena.push(arena.get_dense(i / 3).unwrap(), i);
+            }
+            assert_eq!(root.index().as_usize(), 0);
+            assert_eq!(root.subtree_len(), 500);
+            let capacity = arena.capacity();
+
+            arena.clear();
+            assert!(arena.is_empty());
+            assert_eq!(arena.reserved(), 0);
+            assert!(arena.get_dense(0).is_none());
+            assert_eq!(arena.values().count(), 0);
+            assert_eq!(arena.capacity(), capacity);
+        }
+    }
+}
+
+#[test]
+fn drops_values() {
+    let shared = Rc::new(());
+    let mut arena = Arena::new();
+    let root = arena.push(None, Rc::clone(&shared));
+    arena.push(root, Rc::clone(&shared));
+    arena.clear();
+    assert_eq!(Rc::strong_count(&shared), 1);
+
+    let root = arena.push(None, Rc::clone(&shared));
+    assert!(root.child().is_none());
+    assert!(root.is_root());
+}
+
+#[test]
+fn keeps_subtree_counts() {
+    let mut arena = Arena::with_subtree_counts();
+    let root = arena.push(None, 0);
+    arena.push_all(root, 1..10);
+    arena.clear();
+
+    let root = arena.push(None, 0);
+    arena.push(root, 1);
+    assert_eq!(arena.subtree_len(root), 2);
+}
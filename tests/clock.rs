@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering::Relaxed;
+
+use silva::Arena;
+
+#[test]
+fn fake_clock() {
+    let ticks = Arc::new(AtomicU64::new(100));
+    let arena = Arena::with_clock({
+        let ticks = ticks.clone();
+        move || ticks.fetch_add(3, Relaxed) / 2
+    });
+
+    let root = arena.push(None, 0);
+    for i in 1..200 {
+        arena.push(root, i);
+    }
+    arena.push_all(root, 200..300);
+    arena.push_orphan(300);
+
+    let stamps = arena
+        .iter_range(..)
+        .map(|node| (node.seq(), arena.timestamp(node.index()).unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(stamps.len(), 301);
+    assert!(
+        stamps
+            .windows(2)
+            .all(|w| w[0].0 < w[1].0 && w[0].1 <= w[1].1)
+    );
+    assert_eq!(stamps[0].1, 50);
+    assert_eq!(ticks.load(Relaxed), 100 + 301 * 3);
+}
+
+#[test]
+fn no_clock() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    assert_eq!(root.seq(), 0);
+    assert_eq!(arena.push(root, 1).seq(), 1);
+    assert_eq!(arena.timestamp(root.index()), None);
+}
+
+#[test]
+fn missing_node() {
+    let arena = Arena::with_clock(|| 7);
+    let other = Arena::new();
+    other.push(None, 0);
+    let index = other.push(None, 1).index();
+
+    assert_eq!(arena.timestamp(index), None);
+    arena.push(None, 0);
+    arena.push(None, 1);
+    assert_eq!(arena.timestamp(index), Some(7));
+}
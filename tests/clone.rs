@@ -0,0 +1,148 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+use silva::{Arena, ChildOrder, Node};
+
+/// A node's index, parent, last child & children
+type Shape = (usize, Option<usize>, Option<usize>, Vec<usize>);
+
+/// The shape of each node, in index order
+fn shape<T>(arena: &Arena<T>) -> Vec<Shape> {
+    arena
+        .iter_range(..)
+        .map(|n| {
+            (
+                n.index().as_usize(),
+                n.parent().map(|p| p.index().as_usize()),
+                n.last_child().map(|c| c.index().as_usize()),
+                n.children().map(|c| c.index().as_usize()).collect(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn same_indices() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let mut arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        let a = arena.push(root, 1);
+        arena.push(a, 2);
+        arena.push(a, 3);
+        let b = arena.push(root, 4).index();
+        arena.push(None, 5);
+        arena.push(b, 6);
+        let (root, a) = (root.index(), a.index());
+        arena.remove_subtree(a);
+        arena.sort_children_by(root, |x, y| y.value.cmp(&x.value));
+
+        let clone = arena.clone();
+        assert_eq!(clone.child_order(), order);
+        assert_eq!(clone.len(), arena.len());
+        assert_eq!(clone.reserved(), arena.reserved());
+        assert_eq!(shape(&clone), shape(&arena), "{order:?}");
+        assert!(clone.values().eq(arena.values()));
+        assert!(clone.get_dense(1).is_none());
+    }
+}
+
+#[test]
+fn independent() {
+    let arena = Arena::new();
+    let root = arena.push(None, String::from("root")).index();
+    arena.push(root, String::from("a"));
+    let mut clone = arena.clone();
+    *clone.value_mut(root).unwrap() += "!";
+    let c = clone.push(root, String::from("c")).index();
+    assert_eq!(arena[root].value, "root");
+    assert!(arena[root].child_values().eq(["a"]));
+    assert_eq!(clone[root].value, "root!");
+    // both hand out the same next index
+    assert_eq!(arena.push(None, String::new()).index(), c);
+}
+
+#[test]
+fn orphans() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let attached = arena.push_orphan(1);
+    arena.push(attached, 2);
+    arena.attach(root, attached).unwrap();
+    let orphan = arena.push_orphan(3);
+    arena.push(orphan, 4);
+
+    let clone = arena.clone();
+    assert_eq!(shape(&clone), shape(&arena));
+    assert!(clone[orphan.index()].is_orphan());
+    assert!(clone.roots().map(Node::index).eq([root.index()]));
+}
+
+#[test]
+fn subtree_counts() {
+    let arena = Arena::with_subtree_counts();
+    let root = arena.push(None, 0);
+    let orphan = arena.push_orphan(1);
+    arena.push(orphan, 2);
+    arena.attach(root, orphan).unwrap();
+    arena.push(root, 3);
+
+    let clone = arena.clone();
+    for node in clone.iter_range(..) {
+        assert_eq!(
+            clone.subtree_len(node),
+            arena.subtree_len(&arena[node.index()])
+        );
+    }
+    clone.push(clone.get_dense(2).unwrap(), 4);
+    assert_eq!(clone.subtree_len(clone.get_dense(0).unwrap()), 5);
+    assert_eq!(arena.subtree_len(root), 4);
+}
+
+#[test]
+fn timestamps() {
+    static NOW: AtomicU64 = AtomicU64::new(0);
+    let arena = Arena::with_clock(|| NOW.fetch_add(1, Ordering::Relaxed));
+    let root = arena.push(None, 0).index();
+    let child = arena.push(root, 1).index();
+    NOW.store(10, Ordering::Relaxed);
+
+    let clone = arena.clone();
+    assert_eq!(clone.timestamp(root), Some(0));
+    assert_eq!(clone.timestamp(child), Some(1));
+    let new = clone.push(root, 2).index();
+    assert_eq!(clone.timestamp(new), Some(10));
+}
+
+#[test]
+fn push_check() {
+    fn no_children(parent: Option<&i32>, _: &i32) -> Result<(), std::fmt::Error> {
+        parent.map_or(Ok(()), |_| Err(std::fmt::Error))
+    }
+    let arena = Arena::with_push_check(no_children);
+    let root = arena.push(None, 0).index();
+    let clone = arena.clone();
+    assert!(clone.try_push(root, 1).is_err());
+}
+
+#[test]
+fn while_pushing() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    thread::scope(|s| {
+        s.spawn(|| {
+            for i in 0..200 {
+                let parent = arena.get_dense(i / 3).unwrap_or(root);
+                arena.push(parent, i);
+            }
+        });
+        for _ in 0..20 {
+            let clone = arena.clone();
+            for node in clone.iter_range(..) {
+                for child in node.children() {
+                    assert_eq!(child.parent().map(Node::index), Some(node.index()));
+                }
+                assert_eq!(node.value, arena[node.index()].value);
+            }
+        }
+    });
+}
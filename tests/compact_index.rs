@@ -0,0 +1,29 @@
+#![cfg(feature = "compact-index")]
+
+use std::mem::size_of;
+
+use silva::{AllocError, Arena, Index};
+
+#[test]
+fn size() {
+    // a generation takes another 4
+    let size = if cfg!(feature = "generations") { 8 } else { 4 };
+    assert_eq!(size_of::<Index>(), size);
+    assert_eq!(size_of::<Option<Index>>(), size);
+}
+
+#[test]
+fn capacity() {
+    let max = u32::MAX as usize;
+    assert!(matches!(
+        Arena::<u8>::try_with_capacity(max + 1),
+        Err(AllocError::CapacityOverflow)
+    ));
+    let arena = Arena::new();
+    assert!(arena.get_dense(max).is_none());
+
+    let root = arena.push(None, 0);
+    let leaf = arena.push(root, 1);
+    assert_eq!(leaf.index().as_usize(), 1);
+    assert_eq!(arena.get_dense(1).unwrap().value, 1);
+}
@@ -0,0 +1,15 @@
+#![cfg(feature = "contention-stats")]
+
+use silva::{Arena, ContentionStats};
+
+#[test]
+fn uncontended() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    for i in 1..100 {
+        arena.push(root, i);
+    }
+    assert_eq!(arena.iter_range(..).count(), 100);
+    assert_eq!(arena[root].children().count(), 99);
+    assert_eq!(arena.contention_stats(), ContentionStats::default());
+}
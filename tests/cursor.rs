@@ -0,0 +1,70 @@
+use silva::{Arena, ChildOrder, Cursor};
+
+#[test]
+fn navigate() {
+    let arena = Arena::new();
+    let root;
+    silva::tree![
+        &arena,
+        root = ("root") = [("a") = [("a1"), ("a2")], ("b"), ("c")]
+    ];
+
+    let mut cursor = Cursor::new(root);
+    assert!(!cursor.goto_parent());
+    assert!(!cursor.goto_next_sibling());
+    assert!(!cursor.goto_prev_sibling());
+
+    // newest first
+    assert!(cursor.goto_first_child());
+    assert_eq!(cursor.node().value, "c");
+    assert!(!cursor.goto_prev_sibling());
+    assert!(cursor.goto_next_sibling());
+    assert_eq!(cursor.node().value, "b");
+    assert!(cursor.goto_next_sibling());
+    assert_eq!(cursor.node().value, "a");
+    assert!(!cursor.goto_next_sibling());
+    assert!(cursor.goto_prev_sibling());
+    assert_eq!(cursor.node().value, "b");
+    assert!(!cursor.goto_first_child());
+    assert_eq!(cursor.node().value, "b");
+
+    assert!(cursor.goto_parent());
+    assert!(cursor.goto_last_child());
+    assert_eq!(cursor.node().value, "a");
+    assert!(cursor.goto_last_child());
+    assert_eq!(cursor.node().value, "a1");
+    assert!(cursor.goto_root());
+    assert_eq!(cursor.node().value, "root");
+    assert!(!cursor.goto_root());
+}
+
+#[test]
+fn insertion_order() {
+    let arena = Arena::with_child_order(ChildOrder::InsertionOrder);
+    let root = arena.push(None, 0);
+    arena.push_all(root, 1..4);
+
+    let mut cursor = Cursor::new(root);
+    assert!(cursor.goto_first_child());
+    assert_eq!(cursor.node().value, 1);
+    assert!(cursor.goto_next_sibling());
+    assert_eq!(cursor.node().value, 2);
+    let saved = cursor;
+    assert!(cursor.goto_parent());
+    assert!(cursor.goto_last_child());
+    assert_eq!(cursor.node().value, 3);
+    assert!(cursor.goto_prev_sibling());
+    assert!(std::ptr::eq(cursor.node(), saved.node()));
+}
+
+#[test]
+fn orphans() {
+    let arena = Arena::new();
+    let orphan = arena.push_orphan(0);
+    let leaf = arena.push(arena.push(orphan, 1), 2);
+
+    let mut cursor = Cursor::new(leaf);
+    assert!(cursor.goto_root());
+    assert!(std::ptr::eq(cursor.node(), orphan));
+    assert!(!cursor.goto_parent());
+}
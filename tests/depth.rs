@@ -0,0 +1,102 @@
+use std::thread;
+
+use silva::Arena;
+
+#[test]
+fn pushed() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    let a1 = arena.push(a, 2);
+    let b = arena.push(root, 3);
+
+    assert_eq!(root.depth(), 0);
+    assert_eq!(a.depth(), 1);
+    assert_eq!(a1.depth(), 2);
+    assert_eq!(b.depth(), 1);
+    assert_eq!(arena.push(None, 4).depth(), 0);
+}
+
+#[test]
+fn orphans() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    let orphan = arena.push_orphan(2);
+    let child = arena.push(orphan, 3);
+    let grandchild = arena.push(child, 4);
+
+    // measured from the orphan until it is attached
+    assert_eq!(orphan.depth(), 0);
+    assert_eq!(grandchild.depth(), 2);
+
+    arena.attach(a, orphan).unwrap();
+    assert_eq!(grandchild.depth(), 4);
+    assert_eq!(child.depth(), 3);
+    assert_eq!(orphan.depth(), 2);
+    assert_eq!(arena.push(grandchild, 5).depth(), 5);
+}
+
+#[test]
+fn nested_orphans() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let outer = arena.push_orphan(1);
+    let inner = arena.push_orphan(2);
+    let leaf = arena.push(inner, 3);
+
+    arena.attach(outer, inner).unwrap();
+    assert_eq!(leaf.depth(), 2);
+    arena.attach(root, outer).unwrap();
+    assert_eq!(leaf.depth(), 3);
+    assert_eq!(inner.depth(), 2);
+}
+
+#[test]
+fn matches_ancestors() {
+    let len = if cfg!(miri) { 200 } else { 5_000 };
+    let arena = Arena::new();
+    arena.push(None, 0);
+    for i in 1..len {
+        match i % 7 {
+            0 => {
+                arena.push_orphan(i);
+            }
+            1 => {
+                arena.push(None, i);
+            }
+            _ => {
+                arena.push(arena.get_dense((i * 31 + 7) % i).unwrap(), i);
+            }
+        }
+        // cycles & non-orphans are rejected, which is fine here
+        if i % 5 == 0 {
+            let parent = arena.get_dense(i / 3).unwrap();
+            let _ = arena.attach(parent, arena.get_dense(i / 2).unwrap());
+        }
+    }
+    for node in arena.iter_range(..) {
+        assert_eq!(node.depth(), node.ancestors().count(), "{node}");
+    }
+}
+
+#[test]
+fn concurrent() {
+    let (threads, len) = if cfg!(miri) { (4, 32) } else { (8, 1_000) };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let orphan = arena.push_orphan(0);
+    let mut leaf = orphan;
+    for i in 1..len {
+        leaf = arena.push(leaf, i);
+    }
+    arena.attach(root, orphan).unwrap();
+
+    // every thread records some of the way, & all agree
+    thread::scope(|s| {
+        for _ in 0..threads {
+            s.spawn(|| assert_eq!(leaf.depth(), len));
+        }
+    });
+    assert_eq!(orphan.depth(), 1);
+}
@@ -0,0 +1,80 @@
+use silva::{Arena, DepthError};
+
+#[test]
+fn round_trip() {
+    let arena = Arena::new();
+    let root;
+    silva::tree![
+        &arena,
+        root = ("root") = [
+            ("a") = [("a1") = [("a11")], ("a2")],
+            ("b"),
+            ("c") = [("c1")],
+        ]
+    ];
+
+    let items: Vec<_> = arena.to_depth_stream(root).map(|(d, v)| (d, *v)).collect();
+    assert_eq!(
+        items,
+        [
+            (0, "root"),
+            (1, "a"),
+            (2, "a1"),
+            (3, "a11"),
+            (2, "a2"),
+            (1, "b"),
+            (1, "c"),
+            (2, "c1"),
+        ]
+    );
+
+    let rebuilt = Arena::from_depth_stream(items.iter().copied()).unwrap();
+    assert_eq!(rebuilt.count(), items.len());
+    let rebuilt_root = rebuilt.get_dense(0).unwrap();
+    assert!(
+        rebuilt
+            .to_depth_stream(rebuilt_root)
+            .map(|(d, v)| (d, *v))
+            .eq(items)
+    );
+    assert_eq!(rebuilt_root.children().count(), 3);
+}
+
+#[test]
+fn consecutive_roots() {
+    let arena = Arena::from_depth_stream([(0, 1), (0, 2), (1, 3), (0, 4)]).unwrap();
+    let roots: Vec<_> = arena.roots().map(|n| n.value).collect();
+    assert_eq!(roots, [1, 2, 4]);
+    let second = arena.get_dense(1).unwrap();
+    assert!(arena.to_depth_stream(second).eq([(0, &2), (1, &3)]));
+}
+
+#[test]
+fn invalid_jump() {
+    let Err(err) = Arena::from_depth_stream([(0, 'a'), (1, 'b'), (3, 'c')]) else {
+        panic!("expected a depth error");
+    };
+    assert!(matches!(
+        err,
+        DepthError {
+            position: 2,
+            depth: 3,
+            max_depth: 2,
+            ..
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "item 2 is at depth 3, but can be at most 2 deep"
+    );
+
+    // the first item must be a root
+    let err = Arena::from_depth_stream([(1, 'a')]).err();
+    assert_eq!(err.map(|e| e.max_depth), Some(0));
+}
+
+#[test]
+fn empty() {
+    let arena = Arena::<()>::from_depth_stream([]).unwrap();
+    assert!(arena.is_empty());
+}
@@ -0,0 +1,193 @@
+use std::collections::HashSet;
+use std::thread;
+
+use silva::Arena;
+
+#[test]
+fn pre_order() {
+    let arena = Arena::new();
+    let root;
+    silva::tree![
+        &arena,
+        root = ("root") = [
+            ("a") = [("a1") = [("a11")], ("a2")],
+            ("b"),
+            ("c") = [("c1")]
+        ]
+    ];
+
+    let order: Vec<_> = root.descendants().map(|n| n.value).collect();
+    assert_eq!(order, ["root", "c", "c1", "b", "a", "a2", "a1", "a11"]);
+    assert!(
+        root.descendants().map(std::ptr::from_ref).eq(root
+            .descendants_snapshot()
+            .into_iter()
+            .map(std::ptr::from_ref))
+    );
+    assert!(root.descendant_values().copied().eq(order));
+}
+
+#[test]
+fn stays_in_subtree() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    arena.push(a, 2);
+    let b = arena.push(root, 3);
+    arena.push(b, 4);
+    arena.push(None, 5);
+
+    assert!(a.descendant_values().eq(&[1, 2]));
+    assert!(b.descendant_values().eq(&[3, 4]));
+    let leaf = arena.get_dense(4).unwrap();
+    assert!(leaf.descendant_values().eq(&[4]));
+
+    let mut iter = leaf.descendants();
+    iter.next();
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn deep_chain() {
+    let depth = if cfg!(miri) { 1_000 } else { 100_000 };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut parent = root;
+    for i in 1..depth {
+        parent = arena.push(parent, i);
+        arena.push(parent, 0);
+    }
+
+    assert_eq!(root.descendants().count(), depth * 2 - 1);
+}
+
+#[test]
+fn attached_orphan() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let orphan = arena.push_orphan(1);
+    arena.push(orphan, 2);
+    arena.push(root, 3);
+
+    assert!(orphan.descendant_values().eq(&[1, 2]));
+    arena.attach(root, orphan).unwrap();
+    assert!(root.descendant_values().eq(&[0, 1, 2, 3]));
+    assert!(orphan.descendant_values().eq(&[1, 2]));
+}
+
+#[test]
+fn concurrent_pushes() {
+    let (threads, pushes) = if cfg!(miri) { (2, 10) } else { (4, 1_000) };
+    let arena = Arena::new();
+    let root = arena.push(None, 0).index();
+
+    thread::scope(|s| {
+        let arena = &arena;
+        for _ in 0..threads {
+            s.spawn(move || {
+                for i in 0..pushes {
+                    let node = arena.push(root, 1);
+                    arena.push(node, i);
+                }
+            });
+        }
+        s.spawn(move || {
+            for _ in 0..pushes / 10 {
+                // no node is seen twice
+                let seen: Vec<_> = arena[root].descendants().map(|n| n.index()).collect();
+                let unique: HashSet<_> = seen.iter().collect();
+                assert_eq!(unique.len(), seen.len());
+            }
+        });
+    });
+
+    assert_eq!(arena[root].descendants().count(), 1 + threads * pushes * 2);
+}
+
+#[test]
+fn with_depth() {
+    let arena = Arena::new();
+    let root;
+    let a;
+    silva::tree![
+        &arena,
+        root = ("root") = [
+            a = ("a") = [("a1") = [("a11")], ("a2")],
+            ("b"),
+            ("c") = [("c1")]
+        ]
+    ];
+
+    let order: Vec<_> = root
+        .descendants_with_depth()
+        .map(|(depth, n)| (depth, n.value))
+        .collect();
+    assert_eq!(
+        order,
+        [
+            (0, "root"),
+            (1, "c"),
+            (2, "c1"),
+            (1, "b"),
+            (1, "a"),
+            (2, "a2"),
+            (2, "a1"),
+            (3, "a11")
+        ]
+    );
+    for (depth, node) in root.descendants_with_depth() {
+        assert_eq!(node.ancestors().count(), depth);
+    }
+    // depths are relative to where the walk starts
+    let sub: Vec<_> = a
+        .descendants_with_depth()
+        .map(|(d, n)| (d, n.value))
+        .collect();
+    assert_eq!(sub, [(0, "a"), (1, "a2"), (1, "a1"), (2, "a11")]);
+}
+
+#[test]
+fn skip_subtree() {
+    let arena = Arena::new();
+    let root;
+    silva::tree![
+        &arena,
+        root = ("root") = [
+            ("a") = [("a1") = [("a11")], ("a2")],
+            ("b"),
+            ("c") = [("c1")]
+        ]
+    ];
+
+    let walk = |skip: &[&str]| {
+        let mut walk = root.descendants_with_depth();
+        let mut visited = Vec::new();
+        while let Some((depth, node)) = walk.next() {
+            visited.push((depth, node.value));
+            if skip.contains(&node.value) {
+                walk.skip_subtree();
+            }
+        }
+        visited
+    };
+    assert_eq!(
+        walk(&["c", "a1"]),
+        [
+            (0, "root"),
+            (1, "c"),
+            (1, "b"),
+            (1, "a"),
+            (2, "a2"),
+            (2, "a1")
+        ]
+    );
+    // skipping a leaf, or the last node, changes nothing
+    assert_eq!(walk(&["b", "a11"]).len(), 8);
+    assert_eq!(walk(&["root"]), [(0, "root")]);
+
+    // before the first node, there's nothing to skip
+    let mut walk = root.descendants();
+    walk.skip_subtree();
+    assert_eq!(walk.count(), 8);
+}
@@ -0,0 +1,98 @@
+use silva::algo::{DiffOp, diff};
+use silva::{Arena, Node};
+
+/// Summarize ops as `(kind, old value, new value)`
+fn summary<'a>(ops: &[DiffOp<'_, &'a str>]) -> Vec<(&'static str, &'a str, &'a str)> {
+    ops.iter()
+        .map(|op| match *op {
+            DiffOp::Unchanged { old, new } => ("=", old.value, new.value),
+            DiffOp::ValueChanged { old, new } => ("~", old.value, new.value),
+            DiffOp::Inserted(new) => ("+", "", new.value),
+            DiffOp::Removed(old) => ("-", old.value, ""),
+        })
+        .collect()
+}
+
+fn config<'a>(arena: &'a Arena<&'static str>, leaf: &'static str) -> &'a Node<&'static str> {
+    let root;
+    silva::tree![
+        arena,
+        root = ("config") = [("net") = [("port"), (leaf)], ("log") = [("level")],]
+    ];
+    root
+}
+
+#[test]
+fn identical() {
+    let arena = Arena::new();
+    let (old, new) = (config(&arena, "host"), config(&arena, "host"));
+
+    let ops = diff(old, new);
+    assert!(ops.iter().all(|op| matches!(op, DiffOp::Unchanged { .. })));
+    assert_eq!(
+        summary(&ops).iter().map(|op| op.1).collect::<Vec<_>>(),
+        ["config", "net", "port", "host", "log", "level"]
+    );
+}
+
+#[test]
+fn renamed_leaf() {
+    let arena = Arena::new();
+    let (old, new) = (config(&arena, "host"), config(&arena, "addr"));
+
+    let ops = diff(old, new);
+    assert_eq!(ops.len(), 6);
+    let changed: Vec<_> = summary(&ops).into_iter().filter(|op| op.0 != "=").collect();
+    assert_eq!(changed, [("~", "host", "addr")]);
+}
+
+#[test]
+fn inserted_middle() {
+    let arena = Arena::new();
+    let (old, new);
+    silva::tree![
+        &arena,
+        old = ("root") = [("a"), ("b"), ("c")],
+        new = ("root") = [("a"), ("x"), ("b"), ("c")]
+    ];
+
+    // without move detection, every sibling after the insert shifts along
+    assert_eq!(
+        summary(&diff(old, new)),
+        [
+            ("=", "root", "root"),
+            ("=", "a", "a"),
+            ("~", "b", "x"),
+            ("~", "c", "b"),
+            ("+", "", "c"),
+        ]
+    );
+    assert_eq!(summary(&diff(new, old)).last(), Some(&("-", "c", "")));
+}
+
+#[test]
+fn different_depth() {
+    let arena = Arena::new();
+    let (old, new);
+    silva::tree![
+        &arena,
+        old = ("root") = [("a") = [("a1") = [("a2")]]],
+        new = ("root") = [("a"), ("b") = [("b1")]]
+    ];
+
+    let ops = diff(old, new);
+    assert_eq!(
+        summary(&ops),
+        [
+            ("=", "root", "root"),
+            ("=", "a", "a"),
+            ("-", "a1", ""),
+            ("+", "", "b"),
+        ]
+    );
+    // whole subtrees are inserted or removed at once
+    let DiffOp::Inserted(b) = ops[3] else {
+        unreachable!()
+    };
+    assert_eq!(b.child().unwrap().value, "b1");
+}
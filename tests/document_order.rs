@@ -0,0 +1,81 @@
+use silva::{Arena, ChildOrder};
+
+#[test]
+fn following_and_preceding() {
+    let arena = Arena::with_child_order(ChildOrder::InsertionOrder);
+    // 0 ( 1 ( 2 3 ( 4 ) ) 5 ( 6 ) 7 )
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    arena.push(a, 2);
+    let b = arena.push(a, 3);
+    let c = arena.push(b, 4);
+    let d = arena.push(root, 5);
+    arena.push(d, 6);
+    let e = arena.push(root, 7);
+
+    assert!(a.following().map(|n| n.value).eq([5, 6, 7]));
+    assert!(c.following().map(|n| n.value).eq([5, 6, 7]));
+    assert!(b.following().map(|n| n.value).eq([5, 6, 7]));
+    assert!(
+        arena
+            .get_dense(2)
+            .unwrap()
+            .following()
+            .map(|n| n.value)
+            .eq([3, 4, 5, 6, 7])
+    );
+    assert!(e.following().next().is_none());
+    assert!(root.following().next().is_none());
+
+    assert!(e.preceding().map(|n| n.value).eq([6, 5, 4, 3, 2, 1]));
+    assert!(c.preceding().map(|n| n.value).eq([2]));
+    assert!(
+        arena
+            .get_dense(6)
+            .unwrap()
+            .preceding()
+            .map(|n| n.value)
+            .eq([4, 3, 2, 1])
+    );
+    assert!(root.preceding().next().is_none());
+    assert!(a.preceding().next().is_none());
+}
+
+#[test]
+fn partitions_the_tree() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        for i in 1..60 {
+            arena.push(arena.get_dense(i * 7 % i.max(3) / 2).unwrap(), i);
+        }
+        let document: Vec<_> = root.descendant_values().copied().collect();
+        for node in root.descendants() {
+            let at = document.iter().position(|&v| v == node.value).unwrap();
+            let len = node.subtree_len();
+            let following: Vec<_> = node.following().map(|n| n.value).collect();
+            assert_eq!(following, document[at + len..], "{order:?}");
+
+            let mut preceding: Vec<_> = node.preceding().map(|n| n.value).collect();
+            preceding.reverse();
+            let mut expected = document[..at].to_vec();
+            expected.retain(|&v| !node.ancestors().any(|a| a.value == v));
+            assert_eq!(preceding, expected, "{order:?}");
+        }
+    }
+}
+
+#[test]
+fn stays_in_tree() {
+    let arena = Arena::new();
+    let first = arena.push(None, 0);
+    arena.push(first, 1);
+    let second = arena.push(None, 2);
+    arena.push(second, 3);
+    let orphan = arena.push_orphan(4);
+    arena.push(orphan, 5);
+
+    assert!(first.following().next().is_none());
+    assert!(second.preceding().next().is_none());
+    assert!(orphan.following().next().is_none());
+}
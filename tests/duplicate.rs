@@ -0,0 +1,70 @@
+use silva::{Arena, ChildOrder};
+
+fn items<T: Clone>(root: &silva::Node<T>) -> Vec<(usize, T)> {
+    root.descendants_with_depth()
+        .map(|(depth, n)| (depth, n.value.clone()))
+        .collect()
+}
+
+#[test]
+fn copies_under_parent() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let arena = Arena::with_child_order(order);
+        let root = arena.push(None, "root");
+        let proto = arena.push(root, "proto");
+        let a = arena.push(proto, "a");
+        arena.push(a, "a1");
+        arena.push(proto, "b");
+        let target = arena.push(None, "target");
+
+        let copy = arena.duplicate_subtree(proto, target);
+        assert!(!std::ptr::eq(copy, proto));
+        assert_eq!(copy.parent().map(|p| p.value), Some("target"));
+        assert_eq!(items(copy), items(proto), "{order:?}");
+        assert_eq!(arena.len(), 10);
+    }
+}
+
+#[test]
+fn as_root() {
+    let arena = Arena::new();
+    let proto = arena.push(None, 0);
+    arena.push(proto, 1);
+    let copy = arena.duplicate_subtree(proto, None);
+    assert!(copy.is_root());
+    assert_eq!(arena.roots().count(), 2);
+}
+
+#[test]
+fn within_itself() {
+    let arena = Arena::new();
+    let proto = arena.push(None, 0);
+    let leaf = arena.push(proto, 1);
+    arena.push(proto, 2);
+    let copy = arena.duplicate_subtree(proto, leaf);
+    assert_eq!(copy.parent().map(|p| p.index()), Some(leaf.index()));
+    assert_eq!(arena.len(), 6);
+    assert!(copy.child_values().eq(&[2, 1]));
+}
+
+#[test]
+fn many_times() {
+    let arena = Arena::new();
+    let root = arena.push(None, String::from("root"));
+    let proto = arena.push_orphan(String::from("item"));
+    arena.push(proto, String::from("label"));
+    for _ in 0..3 {
+        arena.duplicate_subtree(proto, root);
+    }
+    assert_eq!(root.children().count(), 3);
+    assert!(root.children().all(|c| c.child_values().eq(["label"])));
+}
+
+#[test]
+#[should_panic = "root does not belong to this arena"]
+fn foreign_root() {
+    let arena = Arena::new();
+    let other = Arena::new();
+    let root = other.push(None, 0);
+    arena.duplicate_subtree(root, None);
+}
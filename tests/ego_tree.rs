@@ -0,0 +1,58 @@
+#![cfg(feature = "ego-tree")]
+
+use silva::Arena;
+
+#[test]
+fn from_ego_tree() {
+    let mut tree = ego_tree::Tree::new("root");
+    let mut root = tree.root_mut();
+    let a = root.append("a").id();
+    let b = root.append("b").id();
+    let a0 = tree.get_mut(a).unwrap().prepend("a0").id();
+    let mut orphan = tree.orphan("orphan");
+    let below = orphan.append("below").id();
+    let orphan = orphan.id();
+
+    let (arena, indices) = Arena::from_ego_tree(tree);
+    assert_eq!(arena.len(), 6);
+    let values: Vec<&str> = arena.values().copied().collect();
+    assert_eq!(values, ["root", "a", "a0", "b", "orphan", "below"]);
+    assert_eq!(arena.roots().count(), 1);
+    assert!(arena[indices[&orphan]].is_orphan());
+    assert_eq!(arena[indices[&below]].parent().unwrap().value, "orphan");
+    assert_eq!(arena[indices[&a0]].depth(), 2);
+    let root = arena.roots().next().unwrap();
+    let mut children: Vec<&str> = root.children().map(|c| c.value).collect();
+    children.reverse();
+    assert_eq!(children, ["a", "b"]);
+    assert_eq!(arena[indices[&b]].parent().unwrap().index(), root.index());
+
+    let arena: Arena<u8> = ego_tree::Tree::new(0).into();
+    assert_eq!(arena.len(), 1);
+}
+
+#[test]
+fn into_ego_tree() {
+    let arena = Arena::new();
+    let root = arena.push(None, "root".to_owned()).index();
+    let a = arena.push(root, "a".to_owned()).index();
+    arena.push(a, "a1".to_owned());
+    let b = arena.push(root, "b".to_owned()).index();
+    let other = arena.push(None, "other".to_owned()).index();
+    arena.push(other, "dropped".to_owned());
+
+    let (tree, ids) = arena.into_ego_tree(root);
+    assert_eq!(ids.len(), 4);
+    assert!(ids.get(other).is_none());
+    assert_eq!(tree.values().count(), 4);
+    let children: Vec<&str> = tree.root().children().map(|c| c.value().as_str()).collect();
+    assert_eq!(children, ["a", "b"]);
+    assert_eq!(tree.get(ids[b]).unwrap().value(), "b");
+    let a = tree.get(ids[a]).unwrap();
+    assert_eq!(a.first_child().unwrap().value(), "a1");
+
+    // & back again
+    let (arena, _) = Arena::from_ego_tree(tree);
+    let values: Vec<&str> = arena.values().map(String::as_str).collect();
+    assert_eq!(values, ["root", "a", "a1", "b"]);
+}
@@ -0,0 +1,145 @@
+use std::error::Error;
+use std::fmt;
+
+use silva::error::{self, AllocError, AttachError, ForeignNodeError, PushError};
+use silva::{Arena, AsParent};
+
+#[derive(Debug)]
+struct Odd;
+
+impl fmt::Display for Odd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("odd values are not allowed")
+    }
+}
+
+impl Error for Odd {}
+
+fn even(_: Option<&u32>, value: &u32) -> Result<(), Odd> {
+    if value % 2 == 0 { Ok(()) } else { Err(Odd) }
+}
+
+#[test]
+fn display() {
+    let arena = Arena::new();
+    let a = arena.push(None, 0);
+    let b = arena.push(a, 1);
+    let orphan = arena.push_orphan(2);
+    arena.attach(b, orphan).unwrap();
+
+    let not_orphan = arena.attach(a, b).unwrap_err();
+    assert_eq!(not_orphan.to_string(), "node 1 is not an unattached orphan");
+
+    let other = Arena::new();
+    let cycle = other.push_orphan(0);
+    assert_eq!(
+        other.attach(cycle, cycle).unwrap_err().to_string(),
+        "attaching orphan 0 to node 0 would create a cycle"
+    );
+
+    let foreign = AsParent::try_get(b, &other).unwrap_err();
+    assert_eq!(
+        foreign.to_string(),
+        "node does not belong to this arena (index 1)"
+    );
+
+    assert_eq!(AllocError::Failed.to_string(), "memory allocation failed");
+    assert_eq!(
+        AllocError::CapacityOverflow.to_string(),
+        "capacity overflow"
+    );
+}
+
+#[test]
+fn push_error() {
+    let arena = Arena::with_push_check(even);
+    let err = arena.try_push(None, 3).unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        "value rejected: odd values are not allowed"
+    );
+    assert_eq!(format!("{err:?}"), "Rejected(Odd, ..)");
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "odd values are not allowed"
+    );
+    assert_eq!(err.into_value(), 3);
+}
+
+#[test]
+fn boxed() {
+    fn attach_twice() -> Result<(), Box<dyn Error>> {
+        let arena = Arena::new();
+        let root = arena.push(None, ());
+        let orphan = arena.push_orphan(());
+        arena.attach(root, orphan)?;
+        arena.attach(root, orphan)?;
+        Ok(())
+    }
+
+    fn foreign() -> Result<(), Box<dyn Error + Send + Sync>> {
+        let (a, b) = (Arena::new(), Arena::new());
+        let node = a.push(None, ());
+        b.try_resolve_parent(node)?;
+        Ok(())
+    }
+
+    fn reserve() -> Result<usize, Box<dyn Error>> {
+        Ok(Arena::<()>::try_with_capacity(usize::MAX)?.capacity())
+    }
+
+    fn rejected() -> Result<(), Box<dyn Error>> {
+        Arena::with_push_check(even).try_push(None, 1)?;
+        Ok(())
+    }
+
+    let err = attach_twice().unwrap_err();
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(AttachError::NotOrphan(_))
+    ));
+    assert!(foreign().unwrap_err().is::<ForeignNodeError>());
+    assert_eq!(
+        reserve().unwrap_err().downcast_ref(),
+        Some(&AllocError::CapacityOverflow)
+    );
+    let err = rejected().unwrap_err();
+    let push = err.downcast_ref::<PushError<u32>>().unwrap();
+    assert!(push.source().unwrap().is::<Odd>());
+}
+
+#[test]
+fn unified() {
+    fn rejected() -> Result<(), error::Error> {
+        let mut arena = Arena::with_push_check(even);
+        let root = arena.try_push(None, 2)?.index();
+        let leaf = arena.try_push(root, 4)?.index();
+        arena.reparent(root, leaf)?;
+        Ok(())
+    }
+
+    fn depth() -> Result<Arena<u32>, error::Error> {
+        Ok(Arena::from_depth_stream([(0, 0), (2, 1)])?)
+    }
+
+    let err = rejected().unwrap_err();
+    assert!(matches!(err, error::Error::Reparent(_)));
+    assert_eq!(
+        err.to_string(),
+        "moving node 0 under node 1 would create a cycle"
+    );
+    assert!(err.source().is_none());
+
+    let err = error::Error::from(Arena::with_push_check(even).try_push(None, 1).unwrap_err());
+    assert!(matches!(err, error::Error::Push(PushError::Rejected(..))));
+    assert_eq!(
+        err.to_string(),
+        "value rejected: odd values are not allowed"
+    );
+    assert!(err.source().unwrap().is::<Odd>());
+
+    assert!(matches!(depth(), Err(error::Error::Depth(_))));
+    let err: error::Error = AllocError::Failed.into();
+    assert_eq!(err.to_string(), "memory allocation failed");
+}
@@ -0,0 +1,177 @@
+use silva::export::{write_nested, write_xml};
+use silva::{Arena, ChildOrder};
+
+fn xml<T: std::fmt::Display>(root: &silva::Node<T>) -> String {
+    let mut out = Vec::new();
+    write_xml(root, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn tree_xml() {
+    let root;
+
+    let arena = Arena::new();
+    silva::tree![
+        &arena,
+        root = ("root") = [
+            ("one") = [("one one"), ("one two")],
+            ("two"),
+            ("three") = [("three one") = [("three one one")]] //
+        ]
+    ];
+
+    assert_eq!(
+        xml(root),
+        concat!(
+            r#"<node value="root">"#,
+            r#"<node value="one">"#,
+            r#"<node value="one one"></node>"#,
+            r#"<node value="one two"></node>"#,
+            r#"</node>"#,
+            r#"<node value="two"></node>"#,
+            r#"<node value="three">"#,
+            r#"<node value="three one">"#,
+            r#"<node value="three one one"></node>"#,
+            r#"</node>"#,
+            r#"</node>"#,
+            r#"</node>"#,
+        )
+    );
+}
+
+#[test]
+fn escaped_xml() {
+    let arena = Arena::new();
+    let root = arena.push(None, r#"a&b<c>d"e'f"#);
+    arena.push(root, "&&");
+    arena.push(root, "plain");
+
+    assert_eq!(
+        xml(root),
+        concat!(
+            r#"<node value="a&amp;b&lt;c&gt;d&quot;e&apos;f">"#,
+            r#"<node value="&amp;&amp;"></node>"#,
+            r#"<node value="plain"></node>"#,
+            r#"</node>"#,
+        )
+    );
+}
+
+#[test]
+fn nested_errors() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    arena.push(root, 1);
+    arena.push(root, 2);
+
+    let mut opened = Vec::new();
+    let result = write_nested(
+        root,
+        &mut std::io::sink(),
+        |node, _| {
+            opened.push(node.value);
+            if node.value == 1 {
+                return Err(std::io::Error::other("stop"));
+            }
+            Ok(())
+        },
+        |_, _| Ok(()),
+    );
+
+    assert!(result.is_err());
+    assert_eq!(opened, [0, 1]);
+}
+
+#[test]
+fn deep_chain() {
+    let depth = if cfg!(miri) { 100 } else { 100_000 };
+
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut node = root;
+    for i in 1..depth {
+        node = arena.push(node, i);
+    }
+
+    let (mut opens, mut closes) = (0, 0);
+    write_nested(
+        root,
+        &mut std::io::sink(),
+        |_, _| {
+            opens += 1;
+            Ok(())
+        },
+        |node, _| {
+            assert_eq!(node.value, depth - 1 - closes);
+            closes += 1;
+            Ok(())
+        },
+    )
+    .unwrap();
+    assert_eq!((opens, closes), (depth, depth));
+}
+
+#[test]
+fn dot() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let arena = Arena::with_child_order(order);
+        let root = arena.push(None, "root");
+        let a = arena.push(root, "a");
+        arena.push(root, r#"say "hi"\"#);
+        arena.push(a, "two\nlines");
+        let orphan = arena.push_orphan("orphan");
+        arena.push(orphan, "below");
+
+        assert_eq!(
+            arena.to_dot(|node| node.value.to_string()),
+            concat!(
+                "digraph {\n",
+                "    0 [label=\"root\"];\n",
+                "    0 -> 1;\n",
+                "    0 -> 2;\n",
+                "    1 [label=\"a\"];\n",
+                "    1 -> 3;\n",
+                "    2 [label=\"say \\\"hi\\\"\\\\\"];\n",
+                "    3 [label=\"two\\nlines\"];\n",
+                "    4 [label=\"orphan\"];\n",
+                "    4 -> 5;\n",
+                "    5 [label=\"below\"];\n",
+                "}\n",
+            )
+        );
+    }
+    assert_eq!(
+        Arena::<()>::new().to_dot(|_| String::new()),
+        "digraph {\n}\n"
+    );
+}
+
+#[test]
+fn mermaid() {
+    let arena = Arena::new();
+    let root = arena.push(None, "root");
+    let a = arena.push(root, "a");
+    arena.push(a, "a1");
+    arena.push(root, "say \"hi\"\nthere");
+    arena.push(None, "other");
+
+    assert_eq!(
+        root.mermaid(|node| node.value.to_string()).to_string(),
+        concat!(
+            "graph TD\n",
+            "    n0[\"root\"]\n",
+            "    n0 --> n1\n",
+            "    n0 --> n3\n",
+            "    n1[\"a\"]\n",
+            "    n1 --> n2\n",
+            "    n2[\"a1\"]\n",
+            "    n3[\"say #quot;hi#quot;<br>there\"]\n",
+        )
+    );
+    assert_eq!(
+        a.mermaid(|node| format!("{}: {}", node.index(), node.depth()))
+            .to_string(),
+        "graph TD\n    n1[\"1: 1\"]\n    n1 --> n2\n    n2[\"2: 2\"]\n"
+    );
+}
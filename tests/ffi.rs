@@ -0,0 +1,129 @@
+#![cfg(feature = "ffi")]
+
+use std::ptr;
+
+use silva::ffi::*;
+
+/// The declarations in `include/silva.h`, as Rust function pointers
+mod header {
+    use super::silva_arena_t;
+
+    pub type New = extern "C" fn() -> *mut silva_arena_t;
+    pub type Free = unsafe extern "C" fn(*mut silva_arena_t);
+    pub type Push = unsafe extern "C" fn(*const silva_arena_t, u64, usize) -> u64;
+    pub type Get = unsafe extern "C" fn(*const silva_arena_t, u64, *mut usize) -> i32;
+    pub type Link = unsafe extern "C" fn(*const silva_arena_t, u64) -> u64;
+    pub type Count = unsafe extern "C" fn(*const silva_arena_t) -> u64;
+}
+
+#[test]
+fn header_matches() {
+    let header = include_str!("../include/silva.h");
+    let declarations = [
+        "silva_arena_t *silva_arena_new(void);",
+        "void silva_arena_free(silva_arena_t *arena);",
+        "uint64_t silva_push(const silva_arena_t *arena, uint64_t parent, uintptr_t value);",
+        "int32_t silva_get(const silva_arena_t *arena, uint64_t index, uintptr_t *out);",
+        "uint64_t silva_parent(const silva_arena_t *arena, uint64_t index);",
+        "uint64_t silva_child(const silva_arena_t *arena, uint64_t index);",
+        "uint64_t silva_next(const silva_arena_t *arena, uint64_t index);",
+        "uint64_t silva_count(const silva_arena_t *arena);",
+    ];
+    for declaration in declarations {
+        assert!(header.contains(declaration), "missing {declaration}");
+    }
+
+    let constants = [
+        ("SILVA_OK", SILVA_OK),
+        ("SILVA_ERR_NULL", SILVA_ERR_NULL),
+        ("SILVA_ERR_NOT_FOUND", SILVA_ERR_NOT_FOUND),
+        ("SILVA_ERR_PANIC", SILVA_ERR_PANIC),
+    ];
+    for (name, value) in constants {
+        assert!(
+            header.contains(&format!("#define {name} {value}\n")),
+            "{name}"
+        );
+    }
+    assert_eq!(SILVA_NONE, u64::MAX);
+    assert_eq!(SILVA_ERROR, u64::MAX - 1);
+}
+
+#[test]
+fn tree() {
+    let (new, free): (header::New, header::Free) = (silva_arena_new, silva_arena_free);
+    let push: header::Push = silva_push;
+    let get: header::Get = silva_get;
+    let (parent, child, next): (header::Link, header::Link, header::Link) =
+        (silva_parent, silva_child, silva_next);
+    let count: header::Count = silva_count;
+
+    let arena = new();
+    assert!(!arena.is_null());
+    unsafe {
+        let root = push(arena, SILVA_NONE, 10);
+        let a = push(arena, root, 20);
+        let b = push(arena, root, 30);
+        assert_eq!((root, a, b), (0, 1, 2));
+        assert_eq!(count(arena), 3);
+
+        let mut value = 0;
+        assert_eq!(get(arena, b, &mut value), SILVA_OK);
+        assert_eq!(value, 30);
+        assert_eq!(get(arena, 3, &mut value), SILVA_ERR_NOT_FOUND);
+        assert_eq!(get(arena, b, ptr::null_mut()), SILVA_ERR_NULL);
+        assert_eq!(value, 30);
+
+        assert_eq!(parent(arena, a), root);
+        assert_eq!(parent(arena, root), SILVA_NONE);
+        assert_eq!(child(arena, root), b);
+        assert_eq!(child(arena, a), SILVA_NONE);
+        assert_eq!(next(arena, b), a);
+        assert_eq!(next(arena, a), SILVA_NONE);
+        assert_eq!(next(arena, 7), SILVA_ERROR);
+
+        free(arena);
+    }
+}
+
+#[test]
+fn invalid() {
+    unsafe {
+        assert_eq!(silva_push(ptr::null(), SILVA_NONE, 0), SILVA_ERROR);
+        assert_eq!(silva_get(ptr::null(), 0, &mut 0), SILVA_ERR_NULL);
+        assert_eq!(silva_parent(ptr::null(), 0), SILVA_ERROR);
+        assert_eq!(silva_count(ptr::null()), 0);
+        silva_arena_free(ptr::null_mut());
+
+        let arena = silva_arena_new();
+        assert_eq!(silva_push(arena, 0, 1), SILVA_ERROR);
+        assert_eq!(silva_push(arena, SILVA_ERROR, 1), SILVA_ERROR);
+        assert_eq!(silva_count(arena), 0);
+        silva_arena_free(arena);
+    }
+}
+
+#[test]
+fn threads() {
+    struct Shared(*mut silva_arena_t);
+    // SAFETY: arenas may be shared, see the ffi module's docs
+    unsafe impl Sync for Shared {}
+
+    let arena = Shared(silva_arena_new());
+    let root = unsafe { silva_push(arena.0, SILVA_NONE, 0) };
+    std::thread::scope(|s| {
+        for t in 0..4 {
+            let arena = &arena;
+            s.spawn(move || {
+                for i in 0..100 {
+                    let node = unsafe { silva_push(arena.0, root, t * 100 + i) };
+                    assert_eq!(unsafe { silva_parent(arena.0, node) }, root);
+                }
+            });
+        }
+    });
+    unsafe {
+        assert_eq!(silva_count(arena.0), 401);
+        silva_arena_free(arena.0);
+    }
+}
@@ -0,0 +1,42 @@
+use silva::Arena;
+
+#[test]
+fn arena_find() {
+    let arena = Arena::new();
+    assert!(arena.find(|_| true).is_none());
+    let root = arena.push(None, 0);
+    arena.push_all(root, 1..10);
+    let found = arena.find(|n| n.value % 4 == 3).unwrap();
+    assert_eq!(found.index().as_usize(), 3);
+    assert!(arena.find(|n| n.value > 9).is_none());
+}
+
+#[test]
+fn find_descendant() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    arena.push(a, 2);
+    let b = arena.push(root, 3);
+    arena.push(b, 4);
+
+    assert_eq!(root.find_descendant(|_| true).map(|n| n.value), Some(0));
+    // pre-order, newest child first
+    assert_eq!(
+        root.find_descendant(|n| n.value % 2 == 0 && n.value > 0)
+            .map(|n| n.value),
+        Some(4)
+    );
+    assert_eq!(
+        a.find_descendant(|n| n.value == 2).map(|n| n.value),
+        Some(2)
+    );
+    assert!(a.find_descendant(|n| n.value == 4).is_none());
+
+    let mut checked = Vec::new();
+    root.find_descendant(|n| {
+        checked.push(n.value);
+        n.value == 4
+    });
+    assert_eq!(checked, [0, 3, 4]);
+}
@@ -0,0 +1,83 @@
+use std::thread;
+
+use silva::{Arena, ChildOrder};
+
+const ORDERS: [ChildOrder; 2] = [ChildOrder::NewestFirst, ChildOrder::InsertionOrder];
+
+#[test]
+fn leaf() {
+    for order in ORDERS {
+        let arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        assert!(root.first_child().is_none(), "{order:?}");
+        assert!(root.last_child().is_none(), "{order:?}");
+    }
+}
+
+#[test]
+fn both_ends() {
+    for order in ORDERS {
+        let arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        let only = arena.push(root, 1);
+        assert!(std::ptr::eq(root.first_child().unwrap(), only), "{order:?}");
+        assert!(std::ptr::eq(root.last_child().unwrap(), only), "{order:?}");
+
+        arena.push_all(root, 2..5);
+        assert_eq!(root.first_child().map(|n| n.value), Some(1), "{order:?}");
+        assert_eq!(root.last_child().map(|n| n.value), Some(4), "{order:?}");
+
+        let orphan = arena.push_orphan(5);
+        arena.attach(root, orphan).unwrap();
+        assert_eq!(root.first_child().map(|n| n.value), Some(1), "{order:?}");
+        assert_eq!(root.last_child().map(|n| n.value), Some(5), "{order:?}");
+    }
+}
+
+#[test]
+fn kept_by_map() {
+    for order in ORDERS {
+        let arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        arena.push_all(root, 1..4);
+        let mapped = arena.map(|v| v * 10);
+        let root = mapped.get_dense(0).unwrap();
+        assert_eq!(root.first_child().map(|n| n.value), Some(10), "{order:?}");
+        assert_eq!(root.last_child().map(|n| n.value), Some(30), "{order:?}");
+        mapped.push(root, 40);
+        assert_eq!(root.first_child().map(|n| n.value), Some(10), "{order:?}");
+        assert_eq!(root.last_child().map(|n| n.value), Some(40), "{order:?}");
+    }
+}
+
+#[test]
+fn concurrent_pushes() {
+    let (threads, per_thread) = if cfg!(miri) { (4, 16) } else { (8, 1_000) };
+    for order in ORDERS {
+        let arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        thread::scope(|s| {
+            for _ in 0..threads {
+                let arena = &arena;
+                s.spawn(move || {
+                    for i in 0..per_thread {
+                        arena.push(root, i);
+                        // either end is always one of the children
+                        let first = root.first_child().unwrap();
+                        let last = root.last_child().unwrap();
+                        assert!(root.children().any(|n| std::ptr::eq(n, first)));
+                        assert!(root.children().any(|n| std::ptr::eq(n, last)));
+                    }
+                });
+            }
+        });
+
+        let children: Vec<_> = root.children().collect();
+        let (oldest, newest) = match order {
+            ChildOrder::NewestFirst => (children.last(), children.first()),
+            ChildOrder::InsertionOrder => (children.first(), children.last()),
+        };
+        assert!(std::ptr::eq(root.first_child().unwrap(), *oldest.unwrap()));
+        assert!(std::ptr::eq(root.last_child().unwrap(), *newest.unwrap()));
+    }
+}
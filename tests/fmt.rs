@@ -0,0 +1,240 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use silva::Arena;
+
+fn three_levels(arena: &Arena<&'static str>) -> silva::Index {
+    let root;
+    silva::tree![
+        arena,
+        root = ("root") = [("a") = [("a1"), ("a2")], ("b") = [("b1")],]
+    ];
+    root.index()
+}
+
+#[test]
+fn debug_node() {
+    let arena = Arena::new();
+    let root = &arena[three_levels(&arena)];
+
+    assert_eq!(
+        format!("{:?}", root.debug()),
+        "Node { index: Index(0), value: \"root\", children: [\
+            Node { index: Index(4), value: \"b\", children: [\
+                Node { index: Index(5), value: \"b1\", children: [] }\
+            ] }, \
+            Node { index: Index(1), value: \"a\", children: [\
+                Node { index: Index(3), value: \"a2\", children: [] }, \
+                Node { index: Index(2), value: \"a1\", children: [] }\
+            ] }\
+        ] }"
+    );
+}
+
+#[test]
+fn debug_node_alternate() {
+    let arena = Arena::new();
+    let root = &arena[three_levels(&arena)];
+
+    assert_eq!(
+        format!("{:#?}", root.debug()),
+        "\
+0: \"root\"
+    4: \"b\"
+        5: \"b1\"
+    1: \"a\"
+        3: \"a2\"
+        2: \"a1\""
+    );
+}
+
+#[test]
+fn debug_node_fields() {
+    let arena = Arena::new();
+    let root = &arena[three_levels(&arena)];
+    let a = arena.get_dense(1).unwrap();
+
+    assert_eq!(
+        format!("{root:?}"),
+        "Node { index: Index(0), parent: None, value: \"root\", .. }"
+    );
+    assert_eq!(
+        format!("{a:?}"),
+        "Node { index: Index(1), parent: Some(Index(0)), value: \"a\", .. }"
+    );
+}
+
+/// Not [`Debug`], to check structure dumps don't need it
+struct Opaque;
+
+#[test]
+fn debug_structure() {
+    let arena = Arena::new();
+    let root = arena.push(None, Opaque);
+    arena.push(root, Opaque);
+    let b = arena.push(root, Opaque);
+    arena.push_all(b, [Opaque, Opaque]);
+    arena.push(root, Opaque);
+    arena.push(None, Opaque);
+    arena.push_orphan(Opaque);
+
+    assert_eq!(
+        format!("{:?}", root.debug_structure()),
+        "0 -> [5, 2 -> [4, 3], 1]"
+    );
+    assert_eq!(
+        format!("{:#?}", root.debug_structure()),
+        "\
+0
+    5
+    2
+        4
+        3
+    1"
+    );
+    assert_eq!(
+        format!("{:?}", root.debug_structure().max_children(1).max_depth(1)),
+        "0 -> [5, … +2 more]"
+    );
+    assert_eq!(
+        format!("{:?}", root.debug_structure().max_depth(0)),
+        "0 -> [… +3 more]"
+    );
+    assert_eq!(
+        format!("{:?}", arena.debug_structure()),
+        "[0 -> [5, 2 -> [4, 3], 1], 6]"
+    );
+}
+
+#[test]
+fn display() {
+    let arena = Arc::new(Arena::new());
+    let root = &arena[three_levels(&arena)];
+    let handle = root.child().unwrap().handle(&arena);
+
+    assert_eq!(root.to_string(), "root@0");
+    assert_eq!(handle.to_string(), "b@4");
+    assert_eq!(format!("{handle:?}"), format!("{:?}", handle.node()));
+}
+
+#[test]
+fn index_hash() {
+    let arena = Arena::new();
+    let root = &arena[three_levels(&arena)];
+
+    let set: HashSet<_> = root.children().map(silva::Node::index).collect();
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&root.child().unwrap().index()));
+}
+
+#[test]
+fn debug_elided() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    arena.push_all(root, 1..21);
+    arena.push(arena.get_dense(20).unwrap(), 21);
+
+    assert_eq!(
+        format!("{:?}", root.debug().max_children(2).max_depth(1)),
+        "Node { index: Index(0), value: 0, children: [\
+            Node { index: Index(20), value: 20, children: [… +1 more] }, \
+            Node { index: Index(19), value: 19, children: [] }, \
+            … +18 more\
+        ] }"
+    );
+    assert_eq!(
+        format!("{:#?}", root.debug().max_children(2).max_depth(1)),
+        "\
+0: 0
+    20: 20
+        … +1 more
+    19: 19
+    … +18 more"
+    );
+    assert_eq!(
+        format!("{:?}", root.debug().max_depth(0)),
+        "Node { index: Index(0), value: 0, children: [… +20 more] }"
+    );
+    assert_eq!(
+        format!("{:?}", root.debug_compact())
+            .matches("Node {")
+            .count(),
+        18
+    );
+}
+
+#[test]
+fn debug_deep_chain() {
+    let depth = if cfg!(miri) { 1_000 } else { 100_000 };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut parent = root;
+    for i in 1..depth {
+        parent = arena.push(parent, i);
+    }
+
+    let full = format!("{:?}", root.debug());
+    assert_eq!(full.matches("Node {").count(), depth);
+    assert!(full.ends_with(&"] }".repeat(depth)));
+
+    let compact = format!("{:#?}", root.debug_compact());
+    assert_eq!(compact.lines().count(), 10);
+    assert_eq!(compact.lines().last().unwrap().trim(), "… +1 more");
+}
+
+#[test]
+fn display_tree() {
+    let arena = Arena::new();
+    let root = &arena[three_levels(&arena)];
+
+    assert_eq!(
+        root.display_tree().to_string(),
+        "root\n├── a\n│   ├── a1\n│   └── a2\n└── b\n    └── b1"
+    );
+    assert_eq!(
+        root.display_tree().ascii().to_string(),
+        "root\n|-- a\n|   |-- a1\n|   `-- a2\n`-- b\n    `-- b1"
+    );
+    assert_eq!(
+        root.display_tree().max_depth(1).to_string(),
+        "root\n├── a\n│   └── … +2 more\n└── b\n    └── … +1 more"
+    );
+    assert_eq!(
+        root.display_tree().ascii().max_depth(0).to_string(),
+        "root\n`-- ... +2 more"
+    );
+    let charset = silva::TreeCharset {
+        branch: "+ ",
+        last: "+ ",
+        vertical: "  ",
+        blank: "  ",
+        ..silva::TreeCharset::ASCII
+    };
+    assert_eq!(
+        arena[root.child().unwrap().index()]
+            .display_tree()
+            .charset(charset)
+            .to_string(),
+        "b\n+ b1"
+    );
+}
+
+#[test]
+fn display_tree_deep_chain() {
+    // each line is indented by its depth, so the output grows quadratically
+    let depth = if cfg!(miri) { 100 } else { 2_000 };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut parent = root;
+    for i in 1..depth {
+        parent = arena.push(parent, i);
+    }
+
+    let tree = root.display_tree().ascii().max_depth(3).to_string();
+    assert_eq!(
+        tree,
+        "0\n`-- 1\n    `-- 2\n        `-- 3\n            `-- ... +1 more"
+    );
+    let full = root.display_tree().ascii().to_string();
+    assert_eq!(full.lines().count(), depth);
+}
@@ -0,0 +1,83 @@
+use silva::{Arena, Node};
+
+fn sizes(arena: &Arena<u64>) -> &Node<u64> {
+    let root;
+    silva::tree![
+        arena,
+        root = (1) = [(2) = [(3), (4) = [(5)]], (6), (7) = [(8), (9)],]
+    ];
+    root
+}
+
+/// The depth of `node` below `root`, by counting ancestors
+fn depth_below(node: &Node<u64>, root: &Node<u64>) -> usize {
+    node.ancestors()
+        .position(|n| std::ptr::eq(n, root))
+        .map_or(0, |n| n + 1)
+}
+
+#[test]
+fn subtree_sum() {
+    let arena = Arena::new();
+    let root = sizes(&arena);
+
+    let sum = root.fold_post_order(|node, children| node.value + children.sum::<u64>());
+    assert_eq!(sum, (1..=9).sum::<u64>());
+
+    let a = root.children().last().unwrap();
+    assert_eq!(
+        a.fold_post_order(|n, c| n.value + c.sum::<u64>()),
+        2 + 3 + 4 + 5
+    );
+}
+
+#[test]
+fn insertion_order() {
+    let arena = Arena::new();
+    let root = sizes(&arena);
+
+    let outline = root.fold_post_order(|node, children| {
+        let children: Vec<String> = children.collect();
+        if children.is_empty() {
+            node.value.to_string()
+        } else {
+            format!("{}[{}]", node.value, children.join(" "))
+        }
+    });
+    assert_eq!(outline, "1[2[3 4[5]] 6 7[8 9]]");
+
+    let values = root.reduce_children(Vec::new(), |mut values, child| {
+        values.push(child.value);
+        values
+    });
+    assert_eq!(values, [2, 6, 7]);
+}
+
+#[test]
+fn max_depth() {
+    let arena = Arena::new();
+    let root = sizes(&arena);
+
+    let depth = root.fold_post_order(|_, children| children.max().map_or(0, |d| d + 1));
+    let manual = arena
+        .iter_range(..)
+        .map(|n| depth_below(n, root))
+        .max()
+        .unwrap();
+    assert_eq!(depth, manual);
+    assert_eq!(depth, 3);
+}
+
+#[test]
+fn deep_chain() {
+    let depth = if cfg!(miri) { 1_000 } else { 200_000 };
+    let arena = Arena::new();
+    let root = arena.push(None, 1);
+    let mut parent = root;
+    for _ in 1..depth {
+        parent = arena.push(parent, 1);
+    }
+
+    let count = root.fold_post_order(|node, children| node.value + children.sum::<u64>());
+    assert_eq!(count, depth);
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "generations")]
+
+use silva::{Arena, ConcurrentSecondaryMap, SecondaryMap};
+
+#[test]
+fn stale_after_clear() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, "old").index();
+    let leaf = arena.push(root, "leaf").index();
+    assert_eq!(root.generation(), 0);
+
+    arena.clear();
+    let new = arena.push(None, "new").index();
+    assert_eq!(new.as_usize(), root.as_usize());
+    assert_eq!(new.generation(), 1);
+    assert_ne!(new, root);
+    assert_eq!(format!("{root:?} {new:?}"), "Index(0) Index(0, 1)");
+
+    assert!(arena.get(root).is_none());
+    assert!(arena.get(leaf).is_none());
+    assert!(arena.value_mut(root).is_none());
+    assert_eq!(arena[new].value, "new");
+    assert_eq!(arena.get_dense(0).unwrap().index(), new);
+    assert!(arena.iter_mut().all(|(index, _)| index == new));
+}
+
+#[test]
+fn kept_by_copies() {
+    let mut arena = Arena::new();
+    arena.clear();
+    let root = arena.push(None, 1).index();
+    assert_eq!(arena.clone()[root].value, 1);
+    assert_eq!(arena.map(|v| v * 2)[root].value, 2);
+}
+
+#[test]
+fn secondary_map() {
+    let mut arena = Arena::new();
+    let old = arena.push(None, 0).index();
+    arena.clear();
+    let new = arena.push(None, 0).index();
+
+    let mut map = SecondaryMap::new();
+    map.insert(old, "old");
+    assert_eq!(map.get(new), None);
+    assert_eq!(map.remove(new), None);
+    assert_eq!(map.insert(new, "new"), None);
+    assert_eq!(map.get(old), None);
+    assert_eq!(map.len(), 1);
+    assert!(map.iter().eq([(new, &"new")]));
+}
+
+#[test]
+fn concurrent_secondary_map() {
+    let mut arena = Arena::new();
+    let old = arena.push(None, 0).index();
+    arena.clear();
+    let new = arena.push(None, 0).index();
+
+    let map = ConcurrentSecondaryMap::new();
+    assert_eq!(map.insert(old, "old"), Ok(()));
+    assert_eq!(map.get(new), None);
+    assert_eq!(*map.get_or_compute(new, || "new"), "new");
+    assert_eq!(map.insert(new, "again"), Err("again"));
+    assert_eq!(map.get(old), Some(&"old"));
+    assert_eq!(map.get(new), Some(&"new"));
+}
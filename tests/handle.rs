@@ -0,0 +1,148 @@
+use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use silva::{Arena, Handle, Node};
+
+fn value<N: Borrow<Node<u32>>>(node: &N) -> u32 {
+    node.borrow().value
+}
+
+#[test]
+fn group_by_arena() {
+    let (a, b) = (Arc::new(Arena::new()), Arc::new(Arena::new()));
+    let a_root = a.push(None, 0);
+    let b_root = b.push(None, 100);
+    let mut handles = Vec::new();
+    for i in 1..4 {
+        handles.push(a.push(a_root, i).handle(&a));
+        handles.push(b.push(b_root, 100 + i).handle(&b));
+    }
+
+    assert!(handles[0].same_arena(&handles[2]));
+    assert!(!handles[0].same_arena(&handles[1]));
+
+    let mut groups: HashMap<*const Arena<u32>, Vec<u32>> = HashMap::new();
+    for handle in &handles {
+        groups
+            .entry(handle.arena_ptr())
+            .or_default()
+            .push(value(handle));
+    }
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[&Arc::as_ptr(&a)], [1, 2, 3]);
+    assert_eq!(groups[&Arc::as_ptr(&b)], [101, 102, 103]);
+    assert_eq!(value(&a_root), 0);
+}
+
+#[test]
+fn strong_count() {
+    let arena = Arc::new(Arena::new());
+    let root = arena.push(None, 0).handle(&arena);
+    assert_eq!(root.strong_count(), 2);
+
+    let clones: Vec<Handle<u32>> = (0..3).map(|_| root.clone()).collect();
+    assert_eq!(root.strong_count(), 5);
+    drop(clones);
+    assert_eq!(root.strong_count(), 2);
+
+    drop(arena);
+    assert_eq!(root.strong_count(), 1);
+    assert_eq!(value(&root), 0);
+}
+
+#[test]
+fn weak() {
+    let mut arena = Arc::new(Arena::new());
+    let root = arena.push(None, 0).index();
+    let leaf = arena.push(root, 1).handle(&arena).downgrade();
+    assert_eq!(leaf.strong_count(), 1);
+
+    let upgraded = leaf.upgrade().unwrap();
+    assert_eq!(value(&upgraded), 1);
+    assert_eq!(upgraded.parent().unwrap().value, 0);
+    assert_eq!(leaf.strong_count(), 2);
+    drop(upgraded);
+    assert!(Arc::get_mut(&mut arena).is_none());
+
+    // the arena can still be taken out & mutated, after which upgrading fails
+    let mut arena = Arc::into_inner(arena).unwrap();
+    arena.clear();
+    let arena = Arc::new(arena);
+    assert!(leaf.upgrade().is_none());
+    assert_eq!(leaf.clone().strong_count(), 0);
+    assert_eq!(format!("{leaf:?}"), "WeakHandle { alive: false, .. }");
+    drop(arena);
+}
+
+#[test]
+fn navigation() {
+    let arena = Arc::new(Arena::new());
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    arena.push(root, 2);
+    let leaf = arena.push(a, 3).handle(&arena);
+
+    let a = leaf.parent_handle().unwrap();
+    let root = a.parent_handle().unwrap();
+    assert!(root.parent_handle().is_none());
+    assert!(root.same_arena(&leaf));
+    assert_eq!(leaf.strong_count(), 4);
+
+    let children: Vec<u32> = root.children_handles().map(|h| value(&h)).collect();
+    assert_eq!(children, [2, 1]);
+    assert_eq!(root.children_handles().len(), 2);
+    assert_eq!(
+        root.children_handles().next_back().map(|h| value(&h)),
+        Some(1)
+    );
+    assert_eq!(value(&root.first_child_handle().unwrap()), 1);
+    assert_eq!(value(&root.last_child_handle().unwrap()), 2);
+    assert_eq!(
+        value(&root.last_child_handle().unwrap().next_handle().unwrap()),
+        1
+    );
+    assert!(leaf.first_child_handle().is_none());
+
+    let up: Vec<u32> = leaf.ancestor_handles().map(|h| value(&h)).collect();
+    assert_eq!(up, [1, 0]);
+}
+
+#[test]
+// handles only hash their node's address, which never changes
+#[allow(clippy::mutable_key_type)]
+fn identity() {
+    let arena = Arc::new(Arena::new());
+    let root = arena.push(None, 0).handle(&arena);
+    let twin = arena.push(None, 0).handle(&arena);
+    let other = Arc::new(Arena::new());
+    let foreign = other.push(None, 0).handle(&other);
+
+    assert_eq!(root, root.clone());
+    assert_eq!(root, arena[root.index()].handle(&arena));
+    assert_ne!(root, twin);
+    assert_ne!(root, foreign);
+
+    let set: HashSet<Handle<u32>> = [&root, &twin, &root, &foreign]
+        .into_iter()
+        .cloned()
+        .collect();
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(&twin));
+    assert_eq!(format!("{root:?}"), format!("{:?}", &arena[root.index()]));
+}
+
+#[test]
+fn handle_at() {
+    let arena = Arc::new(Arena::new());
+    let root = arena.push(None, 0).index();
+    let leaf = arena.push(root, 1).index();
+
+    let handle = arena.handle_at(leaf).unwrap();
+    assert_eq!(handle.index(), leaf);
+    assert_eq!(handle, arena[leaf].handle(&arena));
+    assert!(Arc::ptr_eq(handle.arena(), &arena));
+
+    let other = Arc::new(Arena::<u32>::new());
+    assert!(other.handle_at(leaf).is_none());
+}
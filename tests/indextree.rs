@@ -0,0 +1,40 @@
+#![cfg(feature = "indextree")]
+
+use silva::Arena;
+
+#[test]
+fn from_indextree() {
+    let mut tree = indextree::Arena::new();
+    let a = tree.new_node("a");
+    let gone = a.append_value("gone", &mut tree);
+    let a1 = a.append_value("a1", &mut tree);
+    let a2 = a.append_value("a2", &mut tree);
+    let a0 = a.prepend_value("a0", &mut tree);
+    let a21 = a2.append_value("a21", &mut tree);
+    let b = tree.new_node("b");
+    gone.remove(&mut tree);
+
+    let (arena, indices) = Arena::from_indextree(tree);
+    assert_eq!(arena.len(), 6);
+    assert_eq!(indices.len(), 6);
+    assert!(!indices.contains_key(&gone));
+
+    let values: Vec<&str> = arena.values().copied().collect();
+    assert_eq!(values, ["a", "a0", "a1", "a2", "a21", "b"]);
+    assert!(
+        arena
+            .roots()
+            .map(|r| r.index())
+            .eq([indices[&a], indices[&b]])
+    );
+    let root = &arena[indices[&a]];
+    let mut children: Vec<&str> = root.children().map(|c| c.value).collect();
+    children.reverse();
+    assert_eq!(children, ["a0", "a1", "a2"]);
+    assert_eq!(arena[indices[&a21]].depth(), 2);
+    assert_eq!(arena[indices[&a0]].parent().unwrap().index(), indices[&a]);
+    assert_eq!(arena[indices[&a1]].value, "a1");
+
+    let arena: Arena<u8> = indextree::Arena::new().into();
+    assert!(arena.is_empty());
+}
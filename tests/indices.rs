@@ -0,0 +1,51 @@
+use std::collections::BTreeSet;
+
+use silva::prelude::*;
+
+#[test]
+fn collect() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    let b = arena.push(root, 2);
+    let leaf = arena.push(a, 3);
+
+    assert_eq!(root.children().collect_indices(), [b.index(), a.index()]);
+    assert_eq!(
+        leaf.ancestors().collect_indices(),
+        [a.index(), root.index()]
+    );
+    assert_eq!(
+        root.descendants_snapshot().into_iter().collect_index_set(),
+        BTreeSet::from([root.index(), a.index(), b.index(), leaf.index()])
+    );
+    assert!(leaf.children().collect_indices().is_empty());
+    assert_eq!(arena.roots().collect_index_set().len(), 1);
+}
+
+#[test]
+fn resolve_all() {
+    let arena = Arena::new();
+    let root = arena.push(None, "root");
+    arena.push(root, "leaf");
+
+    let other = Arena::new();
+    let mut foreign = other.push(None, "foreign");
+    for _ in 0..4 {
+        foreign = other.push(foreign, "foreign");
+    }
+
+    let indices = root.descendants_snapshot().into_iter().collect_indices();
+    let resolved = arena.resolve_all(indices.iter().copied().chain([foreign.index()]));
+    let values: Vec<_> = resolved.map(|n| n.map(|n| n.value)).collect();
+    assert_eq!(values, [Some("root"), Some("leaf"), None]);
+}
+
+#[test]
+fn try_from_usize() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    assert_eq!(Index::try_from_usize(&arena, 0), Some(root));
+    assert_eq!(Index::try_from_usize(&arena, 1), None);
+    assert_eq!(Index::try_from_usize(&arena, usize::MAX), None);
+}
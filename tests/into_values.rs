@@ -0,0 +1,46 @@
+use std::rc::Rc;
+
+use silva::Arena;
+
+#[test]
+fn into_values() {
+    let arena = Arena::new();
+    let root = arena.push(None, String::from("root"));
+    arena.push_all(root, ["a", "b"].map(String::from));
+    arena.push_orphan(String::from("orphan"));
+    let values: Vec<_> = arena.into_values().collect();
+    assert_eq!(values, ["root", "a", "b", "orphan"]);
+}
+
+#[test]
+fn into_iter() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    arena.push_all(root, 1..50);
+    for (i, (index, value)) in arena.into_iter().enumerate() {
+        assert_eq!(index.as_usize(), i);
+        assert_eq!(value, i);
+    }
+}
+
+#[test]
+fn drops_the_rest() {
+    let shared = Rc::new(());
+    let arena = Arena::new();
+    let root = arena.push(None, Rc::clone(&shared));
+    for _ in 0..10 {
+        arena.push(root, Rc::clone(&shared));
+    }
+    let mut values = arena.into_values();
+    let first = values.next().unwrap();
+    assert_eq!(Rc::strong_count(&shared), 12);
+    drop(values);
+    assert_eq!(Rc::strong_count(&shared), 2);
+    drop(first);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
+
+#[test]
+fn empty() {
+    assert!(Arena::<u8>::new().into_iter().next().is_none());
+}
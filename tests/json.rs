@@ -0,0 +1,77 @@
+#![cfg(feature = "serde_json")]
+
+use serde_json::json;
+use silva::{Arena, ChildOrder};
+
+#[test]
+fn round_trip() {
+    let arena = Arena::with_child_order(ChildOrder::InsertionOrder);
+    let root = arena.push(None, "root".to_owned());
+    let a = arena.push(root, "a".to_owned());
+    arena.push(a, "a1".to_owned());
+    arena.push(root, "b".to_owned());
+    arena.push(None, "other".to_owned());
+    arena.push_orphan("orphan".to_owned());
+
+    let json = arena.to_json().unwrap();
+    assert_eq!(
+        json,
+        json!([
+            {
+                "value": "root",
+                "children": [
+                    { "value": "a", "children": [{ "value": "a1", "children": [] }] },
+                    { "value": "b", "children": [] },
+                ],
+            },
+            { "value": "other", "children": [] },
+        ])
+    );
+    assert_eq!(root.to_json().unwrap(), json[0]);
+
+    let back = Arena::<String>::from_json(&json).unwrap();
+    assert_eq!(back.len(), 5);
+    assert_eq!(back.to_json().unwrap(), json);
+    let values: Vec<&str> = back.values().map(String::as_str).collect();
+    assert_eq!(values, ["root", "a", "a1", "b", "other"]);
+}
+
+#[test]
+fn from_json() {
+    // a single tree, with leaves' children left out
+    let arena = Arena::<u8>::from_json(&json!({
+        "value": 0,
+        "children": [{ "value": 1 }, { "value": 2, "children": null }],
+    }))
+    .unwrap();
+    assert_eq!(arena.len(), 3);
+    assert_eq!(arena.roots().count(), 1);
+
+    let err = |json| Arena::<u8>::from_json(&json).err().unwrap().to_string();
+    assert_eq!(err(json!([1])), "expected a node object");
+    assert_eq!(err(json!({ "children": [] })), "missing field `value`");
+    assert_eq!(
+        err(json!({ "value": 0, "children": {} })),
+        "expected an array of children"
+    );
+    assert!(err(json!({ "value": "zero" })).starts_with("invalid type"));
+    assert!(Arena::<u8>::from_json(&json!([])).unwrap().is_empty());
+}
+
+#[test]
+fn deep_chain() {
+    // dropping the json recurses, so it is kept shallower than in export.rs
+    let depth = if cfg!(miri) { 100 } else { 1_000 };
+
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut node = root;
+    for i in 1..depth {
+        node = arena.push(node, i);
+    }
+
+    let json = root.to_json().unwrap();
+    let back = Arena::<usize>::from_json(&json).unwrap();
+    assert_eq!(back.len(), depth);
+    assert_eq!(back.get_dense(depth - 1).unwrap().depth(), depth - 1);
+}
@@ -0,0 +1,134 @@
+use silva::algo::layout_tidy;
+use silva::{Arena, Node, SecondaryMap};
+
+const EPSILON: f32 = 1e-4;
+
+fn perfect<'a>(arena: &'a Arena<u32>, parent: &'a Node<u32>, depth: u32) {
+    if depth == 0 {
+        return;
+    }
+    for i in 0..2 {
+        let child = arena.push(parent, parent.value * 2 + i);
+        perfect(arena, child, depth - 1);
+    }
+}
+
+/// Assert no two nodes on the same level are closer than `gap`
+fn assert_no_overlap<T>(
+    root: &Node<T>,
+    layout: &SecondaryMap<(f32, f32)>,
+    width: impl Fn(&Node<T>) -> f32,
+    (gap, level_step): (f32, f32),
+) {
+    let mut levels: Vec<Vec<(f32, f32)>> = Vec::new();
+    for node in root.descendants_snapshot() {
+        let (x, y) = layout[node.index()];
+        let depth = node
+            .ancestors()
+            .take_while(|a| !std::ptr::eq(*a, root))
+            .count()
+            + usize::from(!std::ptr::eq(node, root));
+        if levels.len() <= depth {
+            levels.resize(depth + 1, Vec::new());
+        }
+        let half = width(node) / 2.0;
+        levels[depth].push((x - half, x + half));
+        assert!((y - depth as f32 * level_step).abs() < EPSILON);
+    }
+    for mut level in levels {
+        level.sort_by(|a, b| a.0.total_cmp(&b.0));
+        for pair in level.windows(2) {
+            assert!(pair[1].0 - pair[0].1 >= gap - EPSILON, "{pair:?} overlap");
+        }
+    }
+}
+
+#[test]
+fn perfect_binary_tree() {
+    let arena = Arena::new();
+    let root = arena.push(None, 1);
+    perfect(&arena, root, 4);
+
+    let layout = layout_tidy(root, |_| (1.0, 1.0), (1.0, 1.0));
+    assert_eq!(layout.len(), 31);
+    assert_eq!(layout[root.index()], (0.0, 0.0));
+
+    for node in root.descendants_snapshot() {
+        let (x, y) = layout[node.index()];
+        let children: Vec<_> = node.children().map(|c| layout[c.index()]).collect();
+        if let [right, left] = children[..] {
+            assert!((x - (left.0 + right.0) / 2.0).abs() < EPSILON);
+            assert!(left.0 < right.0);
+            assert_eq!(left.1, y + 2.0);
+        }
+    }
+
+    // the tree is its own mirror image
+    let values: Vec<_> = root.descendants_snapshot();
+    for node in &values {
+        let level = 31 - node.value.leading_zeros();
+        let mirror = (1 << level) + (1 << (level + 1)) - 1 - node.value;
+        let mirror = values.iter().find(|n| n.value == mirror).unwrap();
+        let (x, _) = layout[node.index()];
+        assert!((x + layout[mirror.index()].0).abs() < EPSILON);
+    }
+    assert_no_overlap(root, &layout, |_| 1.0, (1.0, 2.0));
+}
+
+#[test]
+fn tricky_shape() {
+    let root;
+    let arena = Arena::new();
+    // a wide left subtree's deep right edge against a right subtree's deep
+    // left edge, with a short subtree between them
+    silva::tree![
+        &arena,
+        root = ("root") = [
+            ("a") = [("a1"), ("a2") = [("a21"), ("a22") = [("a221"), ("a222")]]],
+            ("b"),
+            ("c") = [("c1") = [("c11") = [("c111"), ("wide")], ("c12")], ("c2")] //
+        ]
+    ];
+
+    let width = |node: &Node<&str>| if node.value == "wide" { 6.0 } else { 1.0 };
+    let layout = layout_tidy(root, |node| (width(node), 1.0), (0.5, 1.0));
+    assert_eq!(layout.len(), arena.count());
+    assert_no_overlap(root, &layout, width, (0.5, 2.0));
+
+    // children keep their insertion order left to right
+    for node in root.descendants_snapshot() {
+        let xs: Vec<_> = node.children().map(|c| layout[c.index()].0).collect();
+        assert!(xs.windows(2).all(|w| w[0] > w[1]));
+    }
+}
+
+#[test]
+fn chain() {
+    let depth = if cfg!(miri) { 100 } else { 100_000 };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut node = root;
+    for i in 1..depth {
+        node = arena.push(node, i);
+    }
+
+    let layout = layout_tidy(root, |_| (2.0, 1.0), (1.0, 0.5));
+    assert_eq!(layout.len(), depth);
+    assert_eq!(layout[node.index()], (0.0, (depth - 1) as f32 * 1.5));
+}
+
+#[test]
+fn deterministic() {
+    let arena = Arena::new();
+    let root = arena.push(None, 1);
+    perfect(&arena, root, 3);
+    for i in 0..5 {
+        arena.push(arena.get_dense(i * 2).unwrap(), 100 + i as u32);
+    }
+
+    let size = |node: &Node<u32>| ((node.value % 5) as f32 + 0.5, 1.0);
+    let a = layout_tidy(root, size, (0.3, 0.7));
+    let b = layout_tidy(root, size, (0.3, 0.7));
+    assert_eq!(a, b);
+    assert_no_overlap(root, &a, |n| size(n).0, (0.3, 1.7));
+}
@@ -0,0 +1,61 @@
+use std::thread;
+
+use silva::{Arena, Locked};
+
+#[test]
+fn write_while_shared() {
+    let arena = Arena::new();
+    let root = arena.push_locked(None, Vec::new());
+    let children: Vec<_> = (0..4).map(|i| arena.push_locked(root, vec![i])).collect();
+    thread::scope(|s| {
+        for (i, &child) in children.iter().enumerate() {
+            let arena = &arena;
+            s.spawn(move || {
+                child.value.write().push(i * 10);
+                arena.write(root.index()).unwrap().push(i);
+                // the tree can still be grown meanwhile
+                arena.push_locked(child, vec![i]);
+            });
+        }
+    });
+    let mut pushed = root.value.read().clone();
+    pushed.sort_unstable();
+    assert_eq!(pushed, [0, 1, 2, 3]);
+    for (i, child) in children.iter().enumerate() {
+        assert_eq!(*child.value.read(), [i, i * 10]);
+        assert_eq!(child.children().count(), 1);
+    }
+}
+
+#[test]
+fn missing_node() {
+    let mut arena = Arena::new();
+    let root = arena.push_locked(None, 0).index();
+    arena.remove_subtree(root);
+    assert!(arena.read(root).is_none());
+    assert!(arena.write(root).is_none());
+}
+
+#[test]
+fn poisoned() {
+    let arena = Arena::new();
+    let root = arena.push_locked(None, 1);
+    let _ = thread::scope(|s| {
+        s.spawn(|| {
+            let _guard = root.value.write();
+            panic!("poison the lock");
+        })
+        .join()
+    });
+    assert_eq!(*arena.read(root.index()).unwrap(), 1);
+}
+
+#[test]
+fn unlocked_access() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, Locked::from(1)).index();
+    *arena.value_mut(root).unwrap().get_mut() += 1;
+    assert_eq!(format!("{:?}", arena[root].value), "Locked(2)");
+    let values: Vec<_> = arena.into_values().map(Locked::into_inner).collect();
+    assert_eq!(values, [2]);
+}
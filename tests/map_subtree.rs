@@ -0,0 +1,53 @@
+use silva::{Arena, ChildOrder};
+
+#[test]
+fn keeps_shape() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let arena = Arena::with_child_order(order);
+        let other = arena.push(None, 100);
+        let root = arena.push(None, 0);
+        for i in 1..40 {
+            // value `i` is at index `i + 1`, after `other`
+            let parent = if i % 5 == 0 { 1 } else { 1 + (i * 13 + 3) % i };
+            arena.push(arena.get_dense(parent).unwrap(), i);
+        }
+        arena.push(other, 101);
+
+        let mapped = arena.map_subtree(root, |v| v.to_string());
+        assert_eq!(mapped.child_order(), order);
+        let new_root = mapped.get_dense(0).unwrap();
+        assert!(new_root.is_root());
+        assert_eq!(mapped.len(), root.subtree_len());
+        let copied: Vec<_> = new_root
+            .descendants_with_depth()
+            .map(|(depth, node)| (depth, node.value.clone()))
+            .collect();
+        let expected: Vec<_> = root
+            .descendants_with_depth()
+            .map(|(depth, node)| (depth, node.value.to_string()))
+            .collect();
+        assert_eq!(copied, expected, "{order:?}");
+    }
+}
+
+#[test]
+fn inner_subtree() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let inner = arena.push(root, 1);
+    arena.push(inner, 2);
+    arena.push(root, 3);
+
+    let mapped = arena.map_subtree(inner, |v| v * 10);
+    assert!(mapped.values().eq(&[10, 20]));
+    assert!(mapped.get_dense(0).unwrap().is_root());
+}
+
+#[test]
+#[should_panic = "root does not belong to this arena"]
+fn foreign_root() {
+    let arena = Arena::<u8>::new();
+    let other = Arena::new();
+    let root = other.push(None, 0);
+    arena.map_subtree(root, |&v| v);
+}
@@ -0,0 +1,101 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::Relaxed;
+use std::thread;
+
+use silva::{Arena, ConcurrentSecondaryMap, Index};
+
+fn index(arena: &Arena<usize>, i: usize) -> Index {
+    arena.get_dense(i).unwrap().index()
+}
+
+#[test]
+fn computed_once() {
+    let (threads, len) = if cfg!(miri) { (4, 64) } else { (8, 1_000) };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    arena.push_all(root, 1..len);
+
+    let map = ConcurrentSecondaryMap::with_capacity(arena.capacity());
+    let calls: Vec<_> = (0..len).map(|_| AtomicUsize::new(0)).collect();
+
+    // the address each thread saw for each index
+    let seen: Vec<Vec<usize>> = thread::scope(|s| {
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                let (arena, map, calls) = (&arena, &map, &calls);
+                s.spawn(move || {
+                    let mut seen = vec![0; len];
+                    // overlapping ranges, walked from different starts
+                    for i in (0..len).map(|i| (i + t * len / threads) % len) {
+                        let value = map.get_or_compute(index(arena, i), || {
+                            calls[i].fetch_add(1, Relaxed);
+                            i * 2
+                        });
+                        assert_eq!(*value, i * 2);
+                        seen[i] = std::ptr::from_ref(value).addr();
+                    }
+                    seen
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    for (i, calls) in calls.iter().enumerate() {
+        assert_eq!(calls.load(Relaxed), 1, "index {i}");
+        assert_eq!(map.get(index(&arena, i)), Some(&(i * 2)));
+    }
+    // every thread saw the same values, at the same addresses
+    assert!(seen.windows(2).all(|w| w[0] == w[1]));
+}
+
+#[test]
+fn insert_first_wins() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    let map = ConcurrentSecondaryMap::new();
+
+    assert_eq!(map.get(root), None);
+    assert_eq!(map.insert(root, "a"), Ok(()));
+    assert_eq!(map.insert(root, "b"), Err("b"));
+    assert_eq!(*map.get_or_compute(root, || unreachable!()), "a");
+    assert_eq!(map.get(root), Some(&"a"));
+}
+
+#[test]
+fn grows_past_capacity() {
+    let len = if cfg!(miri) { 300 } else { 10_000 };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let map = ConcurrentSecondaryMap::with_capacity(arena.capacity());
+
+    let first = std::ptr::from_ref(map.get_or_compute(root.index(), || String::from("0")));
+    for i in 1..len {
+        let node = arena.push(root, i);
+        assert_eq!(map.get(node.index()), None);
+        assert_eq!(
+            map.get_or_compute(node.index(), || i.to_string()),
+            &i.to_string()
+        );
+    }
+
+    // earlier entries never move as the map grows
+    assert!(std::ptr::eq(first, map.get(root.index()).unwrap()));
+    for i in 0..len {
+        assert_eq!(map.get(index(&arena, i)), Some(&i.to_string()));
+    }
+}
+
+#[test]
+fn panic_leaves_unset() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    let map = ConcurrentSecondaryMap::new();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        map.get_or_compute(root, || panic!("failed"));
+    }));
+    assert!(result.is_err());
+    assert_eq!(map.get(root), None);
+    assert_eq!(*map.get_or_compute(root, || 1), 1);
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "rayon")]
+
+use rayon::iter::ParallelIterator;
+use silva::{Arena, Node};
+
+fn sorted<'a, T: 'a>(nodes: impl IntoIterator<Item = &'a Node<T>>) -> Vec<usize> {
+    let mut indices: Vec<_> = nodes.into_iter().map(|n| n.index().as_usize()).collect();
+    indices.sort_unstable();
+    indices
+}
+
+#[test]
+fn matches_descendants() {
+    let len = if cfg!(miri) { 200 } else { 20_000 };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    for i in 1..len {
+        // wide near the root, with the odd long chain
+        let parent = if i % 10 == 0 { i - 1 } else { i / 8 };
+        arena.push(arena.get_dense(parent).unwrap(), i);
+    }
+
+    for node in [
+        root,
+        arena.get_dense(3).unwrap(),
+        arena.get_dense(len - 1).unwrap(),
+    ] {
+        let par: Vec<_> = node.par_descendants().collect();
+        assert_eq!(sorted(par), sorted(node.descendants()));
+    }
+    let sum: usize = root.par_descendants().map(|n| n.value).sum();
+    assert_eq!(sum, (0..len).sum::<usize>());
+}
+
+#[test]
+fn chain() {
+    let len = if cfg!(miri) { 100 } else { 10_000 };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut leaf = root;
+    for i in 1..len {
+        leaf = arena.push(leaf, i);
+    }
+    assert_eq!(root.par_descendants().count(), len);
+    assert_eq!(leaf.par_descendants().count(), 1);
+}
+
+#[test]
+fn stops_early() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    arena.push_all(root, 1..1_000);
+    assert!(root.par_descendants().any(|n| n.value == 500));
+    assert!(
+        root.par_descendants()
+            .find_any(|n| n.value == 2000)
+            .is_none()
+    );
+}
+
+#[test]
+fn par_iter() {
+    let len = if cfg!(miri) { 300 } else { 100_000 };
+    let arena = Arena::new();
+    assert_eq!(arena.par_iter().count(), 0);
+    let root = arena.push(None, 0);
+    for i in 1..len {
+        arena.push(arena.get_dense(i / 4).unwrap_or(root), i);
+    }
+    assert_eq!(
+        sorted(arena.par_iter().collect::<Vec<_>>()),
+        sorted(arena.iter_range(..))
+    );
+    let sum: usize = arena.par_iter().map(|n| n.value).sum();
+    assert_eq!(sum, (0..len).sum::<usize>());
+}
+
+#[test]
+fn par_iter_skips_pushes_after() {
+    let arena = Arena::new();
+    arena.push_all(None, 0..100);
+    let iter = arena.par_iter();
+    arena.push_all(None, 100..200);
+    assert!(iter.clone().all(|n| n.value < 100));
+    assert_eq!(iter.count(), 100);
+}
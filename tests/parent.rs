@@ -0,0 +1,111 @@
+use std::panic;
+
+use silva::{Arena, AsParent};
+
+fn arenas() -> (Arena<u32>, Arena<u32>) {
+    let small = Arena::new();
+    small.push(None, 0);
+    let large = Arena::new();
+    for i in 0..10 {
+        large.push(None, i);
+    }
+    (small, large)
+}
+
+fn panic_message(f: impl FnOnce()) -> String {
+    let payload = panic::catch_unwind(panic::AssertUnwindSafe(f)).unwrap_err();
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .unwrap_or_else(|| payload.downcast_ref::<&str>().unwrap().to_string())
+}
+
+#[test]
+fn foreign_in_range() {
+    let (small, large) = arenas();
+    let foreign = small.get_dense(0).unwrap();
+
+    let err = large.try_resolve_parent(foreign).unwrap_err();
+    assert_eq!(err.index(), foreign.index());
+    assert!(!err.is_pending());
+    assert_eq!(
+        panic_message(|| {
+            large.push(foreign, 1);
+        }),
+        "node does not belong to this arena (index 0)"
+    );
+}
+
+#[test]
+fn foreign_out_of_range() {
+    let (small, large) = arenas();
+    let foreign = large.get_dense(9).unwrap();
+
+    assert!(small.try_resolve_parent(foreign).is_err());
+    assert!(small.try_resolve_parent(foreign.index()).is_err());
+    assert_eq!(
+        panic_message(|| {
+            small.push(foreign, 1);
+        }),
+        "node does not belong to this arena (index 9)"
+    );
+    assert_eq!(
+        panic_message(|| {
+            small.push(foreign.index(), 1);
+        }),
+        "node does not belong to this arena (index 9)"
+    );
+}
+
+#[test]
+fn same_arena() {
+    let (_, large) = arenas();
+    let node = large.get_dense(3).unwrap();
+
+    let found = large.try_resolve_parent(node).unwrap().unwrap();
+    assert!(std::ptr::eq(found, node));
+    assert!(std::ptr::eq(node.index().get(&large).unwrap(), node));
+    assert!(matches!(large.try_resolve_parent(None), Ok(None)));
+    assert_eq!(large.push(node, 42).parent().unwrap().value, 3);
+}
+
+#[test]
+fn pending_index() {
+    use std::sync::mpsc;
+
+    let arena = Arena::new();
+    let (index_tx, index_rx) = mpsc::channel();
+    let (done_tx, done_rx) = mpsc::channel();
+
+    let index = std::thread::scope(|s| {
+        let arena = &arena;
+        s.spawn(move || {
+            arena.push_with(None, |index| {
+                index_tx.send(index).unwrap();
+                // hold the push open until the other thread has tried it
+                done_rx.recv().unwrap();
+                0
+            });
+        });
+
+        let index = index_rx.recv().unwrap();
+        let err = arena.try_resolve_parent(index).unwrap_err();
+        assert_eq!(err.index(), index);
+        assert!(err.is_pending());
+        assert_eq!(
+            panic_message(|| {
+                arena.push(index, 1);
+            }),
+            "node has not been written yet, its push is ongoing or failed (index 0)"
+        );
+        done_tx.send(()).unwrap();
+        index
+    });
+
+    // once written, the same index resolves
+    assert_eq!(arena.push(index, 1).parent().unwrap().value, 0);
+
+    let (_, large) = arenas();
+    let err = arena.try_resolve_parent(large.get_dense(9).unwrap().index());
+    assert!(!err.unwrap_err().is_pending());
+}
@@ -0,0 +1,32 @@
+use silva::{Arena, Node};
+
+fn values(path: Vec<&Node<&'static str>>) -> Vec<&'static str> {
+    path.into_iter().map(|n| n.value).collect()
+}
+
+#[test]
+fn root_down() {
+    let arena = Arena::new();
+    let root = arena.push(None, "root");
+    let a = arena.push(root, "a");
+    let a1 = arena.push(a, "a1");
+    arena.push(root, "b");
+
+    assert_eq!(values(root.path()), ["root"]);
+    assert_eq!(values(a1.path()), ["root", "a", "a1"]);
+    let mut ancestors: Vec<_> = a1.ancestors().map(|n| n.value).collect();
+    ancestors.reverse();
+    assert_eq!(values(a1.path())[..2], ancestors);
+}
+
+#[test]
+fn orphans() {
+    let arena = Arena::new();
+    let root = arena.push(None, "root");
+    let orphan = arena.push_orphan("orphan");
+    let leaf = arena.push(orphan, "leaf");
+
+    assert_eq!(values(leaf.path()), ["orphan", "leaf"]);
+    arena.attach(root, orphan).unwrap();
+    assert_eq!(values(leaf.path()), ["root", "orphan", "leaf"]);
+}
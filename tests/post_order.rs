@@ -0,0 +1,84 @@
+use silva::Arena;
+
+#[test]
+fn children_first() {
+    let arena = Arena::new();
+    let root;
+    silva::tree![
+        &arena,
+        root = ("root") = [
+            ("a") = [("a1") = [("a11")], ("a2")],
+            ("b"),
+            ("c") = [("c1")]
+        ]
+    ];
+
+    assert!(
+        root.post_order()
+            .map(|n| n.value)
+            .eq(["a11", "a1", "a2", "a", "b", "c1", "c", "root"])
+    );
+    let folded = root.fold_post_order(|node, children| {
+        let mut order: Vec<_> = children.flatten().collect();
+        order.push(node.value);
+        order
+    });
+    assert!(root.post_order().map(|n| n.value).eq(folded));
+}
+
+#[test]
+fn evaluate() {
+    #[derive(Clone, Copy)]
+    enum Expr {
+        Num(i64),
+        Sub,
+        Mul,
+    }
+
+    // (7 - 2) * 3
+    let arena = Arena::new();
+    let mul = arena.push(None, Expr::Mul);
+    let sub = arena.push(mul, Expr::Sub);
+    arena.push(sub, Expr::Num(7));
+    arena.push(sub, Expr::Num(2));
+    arena.push(mul, Expr::Num(3));
+
+    let mut stack = Vec::new();
+    for node in mul.post_order() {
+        let value = match node.value {
+            Expr::Num(n) => n,
+            op => {
+                let [a, b] = [0; 2].map(|_| stack.pop().unwrap());
+                match op {
+                    Expr::Sub => b - a,
+                    _ => b * a,
+                }
+            }
+        };
+        stack.push(value);
+    }
+    assert_eq!(stack, [15]);
+}
+
+#[test]
+fn leaf() {
+    let arena = Arena::new();
+    let leaf = arena.push(None, 0);
+    let mut iter = leaf.post_order();
+    assert_eq!(iter.next().map(|n| n.value), Some(0));
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn deep_chain() {
+    let depth = if cfg!(miri) { 1_000 } else { 100_000 };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut parent = root;
+    for i in 1..depth {
+        parent = arena.push(parent, i);
+    }
+
+    assert!(root.post_order().map(|n| n.value).eq((0..depth).rev()));
+}
@@ -0,0 +1,160 @@
+use std::error::Error;
+use std::fmt;
+
+use silva::{Arena, PushError};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Markup {
+    Element(&'static str),
+    Text(&'static str),
+    Comment,
+}
+
+#[derive(Debug)]
+struct LeafParent;
+
+impl fmt::Display for LeafParent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("only elements can have children")
+    }
+}
+
+impl Error for LeafParent {}
+
+fn markup(parent: Option<&Markup>, _: &Markup) -> Result<(), LeafParent> {
+    match parent {
+        Some(Markup::Text(_) | Markup::Comment) => Err(LeafParent),
+        _ => Ok(()),
+    }
+}
+
+#[test]
+fn element_text() {
+    let arena = Arena::with_push_check(markup);
+    let body = arena.try_push(None, Markup::Element("body")).unwrap();
+    let text = arena.try_push(body, Markup::Text("hello")).unwrap();
+    arena.try_push(body, Markup::Comment).unwrap();
+
+    let err = arena.try_push(text, Markup::Element("p")).unwrap_err();
+    assert!(matches!(err, PushError::Rejected(..)));
+    assert_eq!(
+        err.to_string(),
+        "value rejected: only elements can have children"
+    );
+    assert!(err.source().unwrap().is::<LeafParent>());
+    assert_eq!(err.into_value(), Markup::Element("p"));
+
+    assert_eq!(arena.count(), 3);
+    assert!(text.child().is_none());
+}
+
+#[test]
+#[should_panic = "only elements can have children"]
+fn push_rejected() {
+    let arena = Arena::with_push_check(markup);
+    let comment = arena.push(None, Markup::Comment);
+    arena.push(comment, Markup::Text("nope"));
+}
+
+#[test]
+fn borrowed_values() {
+    fn not_empty(_: Option<&&str>, value: &&str) -> Result<(), fmt::Error> {
+        if value.is_empty() {
+            Err(fmt::Error)
+        } else {
+            Ok(())
+        }
+    }
+
+    let source = String::from("a b");
+    let arena = Arena::with_push_check(not_empty);
+    let root = arena.push(None, &source[..1]);
+    assert!(arena.try_push(root, &source[1..1]).is_err());
+    assert_eq!(arena.push(root, &source[2..]).value, "b");
+}
+
+#[test]
+fn try_push_with() {
+    let arena = Arena::with_push_check(markup);
+    let body = arena
+        .try_push_with(None, |_| Markup::Element("body"))
+        .unwrap();
+    let text = arena
+        .try_push_with(body, |index| {
+            assert_eq!(index.as_usize(), 1);
+            Markup::Text("hello")
+        })
+        .unwrap();
+
+    let err = arena.try_push_with(text, |_| Markup::Comment).unwrap_err();
+    assert!(matches!(err, PushError::Rejected(..)));
+    assert_eq!(err.into_value(), Some(Markup::Comment));
+    // the rejected value's index is left unused
+    assert_eq!(arena.count(), 2);
+    assert_eq!(arena.push(body, Markup::Comment).index().as_usize(), 3);
+}
+
+#[test]
+fn try_push_all() {
+    let arena = Arena::with_push_check(markup);
+    let body = arena.push(None, Markup::Element("body"));
+    let text = arena.push(body, Markup::Text("hello"));
+
+    let pushed = arena
+        .try_push_all(body, [Markup::Comment, Markup::Text("world")])
+        .unwrap();
+    assert_eq!(pushed.len(), 2);
+    assert_eq!(body.child_count(), 3);
+
+    // none are pushed if any is rejected
+    let err = arena
+        .try_push_all(text, [Markup::Text("a"), Markup::Comment])
+        .unwrap_err();
+    assert_eq!(err.into_value().len(), 2);
+    assert_eq!(arena.count(), 4);
+    assert!(text.child().is_none());
+}
+
+#[test]
+fn push_within_capacity() {
+    let arena = Arena::with_capacity(3);
+    let capacity = arena.capacity();
+    let root = arena.push_within_capacity(None, 0).unwrap().index();
+    for i in 1..capacity {
+        arena.push_within_capacity(root, i).unwrap();
+    }
+    assert_eq!(arena.push_within_capacity(root, 7).unwrap_err(), 7);
+    assert_eq!(arena.count(), capacity);
+    assert_eq!(arena.capacity(), capacity);
+
+    arena.reserve(1);
+    assert_eq!(arena.push_within_capacity(root, 8).unwrap().value, 8);
+    assert!(Arena::<usize>::new().push_within_capacity(None, 0).is_err());
+}
+
+#[test]
+fn foreign_parent() {
+    let (arena, other) = (Arena::new(), Arena::new());
+    let root = arena.push(None, 0).index();
+    let foreign = other.push(None, 0);
+    other.push(foreign, 1);
+
+    let err = arena.try_push(foreign, 2).unwrap_err();
+    assert!(matches!(err, PushError::Foreign(ref e, 2) if !e.is_pending()));
+    assert_eq!(
+        err.to_string(),
+        "invalid parent: node does not belong to this arena (index 0)"
+    );
+    assert!(err.source().is_some());
+
+    // an index past the end of the arena
+    let index = other.get_dense(1).unwrap().index();
+    let err = arena.try_push_with(index, |_| 3).unwrap_err();
+    assert!(matches!(err, PushError::Foreign(_, None)));
+    let err = arena.try_push_all(index, [4, 5]).unwrap_err();
+    assert_eq!(err.into_value(), [4, 5]);
+
+    // nothing was pushed, & the index space is untouched
+    assert_eq!(arena.count(), 1);
+    assert_eq!(arena.try_push(root, 6).unwrap().index().as_usize(), 1);
+}
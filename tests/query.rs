@@ -0,0 +1,158 @@
+use std::collections::HashSet;
+
+use silva::{Arena, ChildOrder, Node, Query};
+
+fn values<'a>(nodes: impl IntoIterator<Item = &'a Node<usize>>) -> Vec<usize> {
+    nodes.into_iter().map(|n| n.value).collect()
+}
+
+/// Evaluate a path one step at a time, over sets of nodes
+fn brute_force(start: &Node<usize>, steps: &[(bool, usize)]) -> Vec<usize> {
+    let mut selected = HashSet::from([start.value]);
+    for &(descendant, modulus) in steps {
+        let mut next = HashSet::new();
+        for node in start.descendants() {
+            let reached = if descendant {
+                node.ancestors().any(|a| selected.contains(&a.value))
+            } else {
+                node.parent().is_some_and(|p| selected.contains(&p.value))
+            };
+            if reached && node.value % modulus == 0 {
+                next.insert(node.value);
+            }
+        }
+        selected = next;
+    }
+    start
+        .descendant_values()
+        .copied()
+        .filter(|v| selected.contains(v))
+        .collect()
+}
+
+#[test]
+fn steps() {
+    let arena = Arena::with_child_order(ChildOrder::InsertionOrder);
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    let b = arena.push(a, 2);
+    arena.push(b, 3);
+    arena.push(a, 4);
+    let c = arena.push(root, 5);
+    arena.push(c, 6);
+
+    assert_eq!(values(Query::new().select(b)), [2]);
+    assert_eq!(values(Query::new().root().select(b)), [0]);
+    assert_eq!(values(Query::new().child(|_| true).select(root)), [1, 5]);
+    assert_eq!(
+        values(Query::new().descendant(|_| true).select(root)),
+        [1, 2, 3, 4, 5, 6]
+    );
+    assert_eq!(
+        values(Query::new().descendant(|n| n.value % 2 == 0).select(root)),
+        [2, 4, 6]
+    );
+    assert_eq!(
+        values(
+            Query::new()
+                .child(|n| n.value == 1)
+                .child(|_| true)
+                .select(root)
+        ),
+        [2, 4]
+    );
+    assert_eq!(
+        values(
+            Query::new()
+                .root()
+                .descendant(|_| true)
+                .descendant(|_| true)
+                .select(c)
+        ),
+        [2, 3, 4, 6]
+    );
+    assert!(
+        Query::new()
+            .child(|_| true)
+            .first(b.child().unwrap())
+            .is_none()
+    );
+    assert_eq!(
+        Query::new()
+            .descendant(|n| n.value > 2)
+            .first(root)
+            .map(|n| n.value),
+        Some(3)
+    );
+}
+
+#[test]
+fn matches_brute_force() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    for i in 1..200 {
+        arena.push(arena.get_dense((i * 37 + 11) % i).unwrap(), i);
+    }
+    let paths: &[&[(bool, usize)]] = &[
+        &[],
+        &[(true, 2)],
+        &[(false, 1), (false, 3)],
+        &[(true, 2), (true, 3)],
+        &[(true, 1), (false, 2), (true, 5)],
+        &[(false, 1), (true, 1), (false, 1), (true, 7)],
+    ];
+    for &path in paths {
+        let query = path.iter().fold(Query::new(), |query, &(descendant, m)| {
+            if descendant {
+                query.descendant(move |n| n.value % m == 0)
+            } else {
+                query.child(move |n| n.value % m == 0)
+            }
+        });
+        for start in [
+            root,
+            arena.get_dense(1).unwrap(),
+            arena.get_dense(3).unwrap(),
+        ] {
+            assert_eq!(
+                values(query.matches(start)),
+                brute_force(start, path),
+                "{path:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn prunes_child_paths() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut leaf = root;
+    for i in 1..100 {
+        leaf = arena.push(leaf, i);
+    }
+    let checked = std::cell::Cell::new(0);
+    let query = Query::new()
+        .child(|_| {
+            checked.set(checked.get() + 1);
+            true
+        })
+        .child(|_| true);
+    assert_eq!(values(query.select(root)), [2]);
+    assert_eq!(checked.get(), 1);
+}
+
+#[test]
+fn arena_query() {
+    let arena = Arena::new();
+    for tree in 0..3 {
+        let root = arena.push(None, tree * 10);
+        arena.push_all(root, tree * 10 + 1..tree * 10 + 4);
+    }
+    let orphan = arena.push_orphan(100);
+    arena.push(orphan, 101);
+
+    let query = Query::new().child(|n| n.value % 2 == 1);
+    assert_eq!(values(arena.query(&query)), [3, 1, 13, 11, 23, 21]);
+    assert!(format!("{query:?}").contains("child"));
+}
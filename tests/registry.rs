@@ -0,0 +1,85 @@
+#![cfg(feature = "registry")]
+
+use std::sync::Arc;
+
+use silva::Arena;
+use silva::registry::{ArenaId, Registry};
+
+#[test]
+fn resolve() {
+    let registry = Registry::new();
+    let a = Arc::new(Arena::new());
+    let b = Arc::new(Arena::new());
+    let (a_id, b_id) = (registry.register(&a), registry.register(&b));
+    assert_ne!(a_id, b_id);
+    assert_eq!(registry.len(), 2);
+
+    let a_root = a.push(None, "a").index();
+    let b_root = b.push(None, "b").index();
+    let b_child = b.push(b_root, "b child").index();
+
+    // the same index resolves differently per arena
+    assert_eq!(registry.resolve(a_id, a_root).unwrap().value, "a");
+    assert_eq!(registry.resolve(b_id, b_root).unwrap().value, "b");
+    assert!(registry.resolve(a_id, b_child).is_none());
+    assert!(Arc::ptr_eq(&registry.arena(b_id).unwrap(), &b));
+
+    // ids round trip through plain numbers
+    let logged = b_id.get();
+    let id = ArenaId::from_u64(logged).unwrap();
+    assert_eq!(registry.resolve(id, b_child).unwrap().value, "b child");
+    assert_eq!(ArenaId::from_u64(0), None);
+}
+
+#[test]
+fn dropped() {
+    let registry = Registry::new();
+    let a = Arc::new(Arena::new());
+    let b = Arc::new(Arena::new());
+    let (a_id, b_id) = (registry.register(&a), registry.register(&b));
+    let index = a.push(None, 1).index();
+    b.push(None, 2);
+
+    let handle = registry.resolve(a_id, index).unwrap();
+    drop(a);
+    // the handle keeps the arena alive
+    assert_eq!(registry.resolve(a_id, index).unwrap().value, 1);
+    drop(handle);
+
+    assert!(registry.resolve(a_id, index).is_none());
+    assert!(registry.arena(a_id).is_none());
+    assert_eq!(registry.len(), 1);
+    assert!(registry.resolve(b_id, index).is_some());
+
+    drop(b);
+    registry.prune();
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn swept_on_register() {
+    let registry = Registry::new();
+    let ids: Vec<_> = (0..32)
+        .map(|_| registry.register(&Arc::new(Arena::<()>::new())))
+        .collect();
+    assert!(registry.len() < ids.len());
+
+    let arena = Arc::new(Arena::new());
+    let id = registry.register(&arena);
+    assert!(registry.unregister(id));
+    assert!(!registry.unregister(id));
+    assert!(registry.resolve(id, arena.push(None, ()).index()).is_none());
+}
+
+#[test]
+fn global() {
+    static REGISTRY: Registry<u32> = Registry::new();
+
+    let arena = Arc::new(Arena::new());
+    let id = REGISTRY.register(&arena);
+    let index = arena.push(None, 7).index();
+    let handle = std::thread::spawn(move || REGISTRY.resolve(id, index).map(|h| h.value))
+        .join()
+        .unwrap();
+    assert_eq!(handle, Some(7));
+}
@@ -0,0 +1,159 @@
+use std::rc::Rc;
+
+use silva::{Arena, ChildOrder};
+
+#[test]
+fn removes_subtree() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let mut arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        let a = arena.push(root, 1);
+        let b = arena.push(root, 2);
+        arena.push(b, 3);
+        arena.push(b, 4);
+        arena.push(root, 5);
+        let (a, b) = (a.index(), b.index());
+
+        assert_eq!(arena.remove_subtree(b), 3);
+        assert_eq!(arena.len(), 3);
+        assert!(arena.get(b).is_none());
+        assert!(arena.values().eq(&[0, 1, 5]));
+        let root = arena.get_dense(0).unwrap();
+        let mut children: Vec<_> = root.child_values().copied().collect();
+        children.sort_unstable();
+        assert_eq!(children, [1, 5], "{order:?}");
+        assert_eq!(root.child_count(), 2);
+        assert!(root.descendants().all(|n| n.value != 3 && n.value != 4));
+
+        // nothing is left to remove
+        assert_eq!(arena.remove_subtree(b), 0);
+        assert_eq!(arena.remove_subtree(a), 1);
+        assert!(arena.values().eq(&[0, 5]));
+    }
+}
+
+#[test]
+fn keeps_order_and_positions() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        for removed in 1..=4 {
+            let mut arena = Arena::with_child_order(order);
+            let root = arena.push(None, 0);
+            arena.push_all(root, 1..5);
+            arena.remove_subtree(arena.get_dense(removed).unwrap().index());
+
+            let root = arena.get_dense(0).unwrap();
+            let expected: Vec<_> = (1..=4).filter(|&v| v != removed).collect();
+            let mut forward: Vec<_> = root.child_values().copied().collect();
+            if order == ChildOrder::NewestFirst {
+                forward.reverse();
+            }
+            assert_eq!(forward, expected, "{order:?} {removed}");
+            assert_eq!(root.children().len(), 3);
+            assert_eq!(root.children().rev().count(), 3);
+            assert_eq!(
+                root.first_child().map(|n| n.value),
+                expected.first().copied()
+            );
+            assert_eq!(root.last_child().map(|n| n.value), expected.last().copied());
+
+            // appends carry on from the right place
+            let (_, ordinal) = arena.push_with_ordinal(root, 5);
+            assert_eq!(ordinal, 3, "{order:?} {removed}");
+            assert_eq!(root.last_child().map(|n| n.value), Some(5));
+            assert_eq!(root.children().len(), 4);
+        }
+    }
+}
+
+#[test]
+fn only_child() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let mut arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        let child = arena.push(root, 1).index();
+        arena.remove_subtree(child);
+
+        let root = arena.get_dense(0).unwrap();
+        assert!(root.child().is_none());
+        assert!(root.first_child().is_none());
+        assert!(root.last_child().is_none());
+        assert_eq!(root.child_order(), order);
+        arena.push(root, 2);
+        arena.push(root, 3);
+        assert_eq!(root.first_child().map(|n| n.value), Some(2));
+        assert_eq!(root.last_child().map(|n| n.value), Some(3));
+    }
+}
+
+#[test]
+fn roots_and_orphans() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0);
+    arena.push(root, 1);
+    let other = arena.push(None, 2).index();
+    let orphan = arena.push_orphan(3);
+    arena.push(orphan, 4);
+    let (root, orphan) = (root.index(), orphan.index());
+
+    assert_eq!(arena.remove_subtree(root), 2);
+    assert_eq!(arena.remove_subtree(orphan), 2);
+    assert!(arena.roots().map(|n| n.index()).eq([other]));
+    assert_eq!(arena.len(), 1);
+}
+
+#[test]
+fn updates_subtree_counts() {
+    let mut arena = Arena::with_subtree_counts();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    arena.push_all(a, 2..5);
+    arena.push(root, 5);
+    let a = a.index();
+
+    arena.remove_subtree(a);
+    let root = arena.get_dense(0).unwrap();
+    assert_eq!(arena.subtree_len(root), 2);
+    assert_eq!(root.subtree_len(), 2);
+}
+
+#[test]
+fn drops_values() {
+    let shared = Rc::new(());
+    let mut arena = Arena::new();
+    let root = arena.push(None, Rc::clone(&shared));
+    let child = arena.push(root, Rc::clone(&shared));
+    arena.push(child, Rc::clone(&shared));
+    let child = child.index();
+
+    arena.remove_subtree(child);
+    assert_eq!(Rc::strong_count(&shared), 2);
+    drop(arena);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
+
+#[test]
+fn retain() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0);
+    let odd = arena.push(root, 1);
+    arena.push(odd, 2);
+    let even = arena.push(root, 4);
+    arena.push(even, 6);
+    arena.push(even, 7);
+    arena.push(None, 8);
+
+    let mut checked = Vec::new();
+    arena.retain(|n| {
+        checked.push(n.value);
+        n.value % 2 == 0
+    });
+    // 2 goes with 1, so is never checked
+    assert_eq!(checked, [0, 1, 4, 6, 7, 8]);
+    assert!(arena.values().eq(&[0, 4, 6, 8]));
+    let root = arena.get_dense(0).unwrap();
+    assert!(root.descendant_values().eq(&[0, 4, 6]));
+    assert_eq!(arena.len(), 4);
+
+    arena.retain(|_| false);
+    assert!(arena.is_empty());
+}
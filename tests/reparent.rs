@@ -0,0 +1,141 @@
+use silva::{Arena, ChildOrder, ReparentError};
+
+#[test]
+fn moves_subtree() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let mut arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        let a = arena.push(root, 1);
+        let b = arena.push(root, 2);
+        let moved = arena.push(a, 3);
+        arena.push(moved, 4);
+        arena.push(a, 5);
+        arena.push(b, 6);
+        let (b, moved) = (b.index(), moved.index());
+
+        arena.reparent(moved, b).unwrap();
+        let a = arena.get_dense(1).unwrap();
+        let b = arena.get(b).unwrap();
+        let moved = arena.get(moved).unwrap();
+        assert!(a.child_values().eq(&[5]), "{order:?}");
+        assert_eq!(a.child_count(), 1);
+        assert_eq!(b.child_count(), 2);
+        // linked as a new child would be
+        assert_eq!(
+            b.children().next().map(|n| n.value),
+            Some(match order {
+                ChildOrder::NewestFirst => 3,
+                ChildOrder::InsertionOrder => 6,
+            })
+        );
+        assert_eq!(b.last_child().map(|n| n.value), Some(3));
+        assert!(std::ptr::eq(moved.parent().unwrap(), b));
+        assert_eq!(moved.depth(), 2);
+        assert_eq!(moved.child().unwrap().depth(), 3);
+        assert!(b.is_ancestor_of(moved.child().unwrap()));
+        assert!(!a.is_ancestor_of(moved));
+    }
+}
+
+#[test]
+fn cycles() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0);
+    let child = arena.push(root, 1);
+    let leaf = arena.push(child, 2);
+    let (root, child, leaf) = (root.index(), child.index(), leaf.index());
+
+    let err = arena.reparent(root, leaf).unwrap_err();
+    assert_eq!(
+        err,
+        ReparentError::Cycle {
+            node: root,
+            parent: leaf
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "moving node 0 under node 2 would create a cycle"
+    );
+    assert!(arena.reparent(child, child).is_err());
+    // nothing changed
+    let root = arena.get(root).unwrap();
+    assert!(root.descendant_values().eq(&[0, 1, 2]));
+}
+
+#[test]
+fn roots_and_orphans() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0);
+    let other = arena.push(None, 1);
+    arena.push(other, 2);
+    let orphan = arena.push_orphan(3);
+    arena.push(orphan, 4);
+    let (root, other, orphan) = (root.index(), other.index(), orphan.index());
+
+    arena.reparent(other, root).unwrap();
+    arena.reparent(orphan, other).unwrap();
+    assert!(arena.roots().map(|n| n.index()).eq([root]));
+    let root = arena.get(root).unwrap();
+    assert!(root.descendant_values().eq(&[0, 1, 3, 4, 2]));
+    assert!(
+        root.descendants_with_depth()
+            .all(|(depth, n)| n.depth() == depth)
+    );
+    assert!(!arena.get(orphan).unwrap().is_orphan());
+}
+
+#[test]
+fn within_orphan() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0);
+    let orphan = arena.push_orphan(1);
+    let inner = arena.push(orphan, 2);
+    let (root, orphan, inner) = (root.index(), orphan.index(), inner.index());
+
+    let moved = arena.push(root, 3).index();
+    arena.reparent(moved, inner).unwrap();
+    assert_eq!(arena.get(moved).unwrap().depth(), 2);
+
+    arena
+        .attach(arena.get(root).unwrap(), arena.get(orphan).unwrap())
+        .unwrap();
+    assert_eq!(arena.get(moved).unwrap().depth(), 3);
+}
+
+#[test]
+fn updates_subtree_counts() {
+    let mut arena = Arena::with_subtree_counts();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    let b = arena.push(root, 2);
+    let moved = arena.push(a, 3);
+    arena.push_all(moved, 4..7);
+    let orphan = arena.push_orphan(7);
+    arena.push(orphan, 8);
+    let (a, b, moved, orphan) = (a.index(), b.index(), moved.index(), orphan.index());
+
+    arena.reparent(moved, b).unwrap();
+    arena.reparent(orphan, a).unwrap();
+    for index in [a, b] {
+        let node = arena.get(index).unwrap();
+        assert_eq!(arena.subtree_len(node), node.subtree_len());
+    }
+    let root = arena.get_dense(0).unwrap();
+    assert_eq!(arena.subtree_len(root), 9);
+}
+
+#[test]
+fn missing() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    let child = arena.push(root, 1).index();
+    arena.remove_subtree(child);
+    let err = arena.reparent(child, root).unwrap_err();
+    assert_eq!(err, ReparentError::Missing(child));
+    assert_eq!(err.to_string(), "no node at index 1");
+    assert_eq!(
+        arena.reparent(root, child),
+        Err(ReparentError::Missing(child))
+    );
+}
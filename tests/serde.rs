@@ -0,0 +1,83 @@
+#![cfg(feature = "serde")]
+
+use silva::{Arena, Index};
+
+#[test]
+fn index_round_trip() {
+    let arena = Arena::new();
+    let root = arena.push(None, "root");
+    let leaf = arena.push(root, "leaf").index();
+
+    let json = serde_json::to_string(&[root.index(), leaf]).unwrap();
+    assert_eq!(json, "[0,1]");
+    let back: Vec<Index> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back, [root.index(), leaf]);
+
+    assert!(serde_json::from_str::<Index>("-1").is_err());
+    assert!(serde_json::from_str::<Index>(&u64::MAX.to_string()).is_err());
+}
+
+fn round_trip(arena: &Arena<&'static str>) -> Arena<String> {
+    let json = serde_json::to_string(arena).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn arena_round_trip() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, "root").index();
+    let a = arena.push(root, "a").index();
+    let b = arena.push(root, "b").index();
+    let gone = arena.push(a, "gone").index();
+    let orphan = arena.push_orphan("orphan").index();
+    arena.push(b, "b1");
+    arena.push(None, "other");
+    arena.remove_subtree(gone);
+    // relinked, so siblings are no longer in index order
+    arena.reparent(b, a).unwrap();
+
+    let back = round_trip(&arena);
+    assert_eq!(back.len(), arena.len());
+    assert_eq!(back.reserved(), arena.reserved());
+    assert!(back.get(gone).is_none());
+    assert!(back[orphan].is_orphan());
+    for node in arena.iter_range(..) {
+        let copy = &back[node.index()];
+        assert_eq!(copy.value, node.value);
+        assert_eq!(copy.depth(), node.depth());
+        assert_eq!(
+            copy.parent().map(|p| p.index()),
+            node.parent().map(|p| p.index())
+        );
+        assert!((copy.children().map(|c| c.index())).eq(node.children().map(|c| c.index())));
+    }
+    assert_eq!(
+        format!("{:?}", back.debug_structure()),
+        format!("{:?}", arena.debug_structure())
+    );
+
+    // attaching the orphan is kept too
+    arena.attach(&arena[root], &arena[orphan]).unwrap();
+    let back = round_trip(&arena);
+    assert_eq!(back[orphan].parent().unwrap().index(), root);
+    assert!(round_trip(&Arena::new()).is_empty());
+}
+
+#[test]
+fn arena_invalid() {
+    let parse = |json| match serde_json::from_str::<Arena<u8>>(json) {
+        Ok(_) => panic!("invalid arena was deserialized"),
+        Err(e) => e.to_string(),
+    };
+    assert!(parse("[[0,null,1],[0,null,2]]").starts_with("duplicate index 0"));
+    assert!(parse("[[1,0,1],[0,null,2]]").starts_with("parent 0 of 1 is not given before it"));
+
+    // storage for huge indices is refused rather than panicking or aborting
+    assert!(parse("[[4000000000000000000,null,1]]").contains("index"));
+    assert!(parse("[[1125899906842624,null,1]]").contains("index"));
+
+    let arena: Arena<u8> = serde_json::from_str("[[3,null,1],[0,3,2]]").unwrap();
+    assert_eq!(arena.len(), 2);
+    assert_eq!(arena.reserved(), 4);
+    assert!(arena.get_dense(1).is_none());
+}
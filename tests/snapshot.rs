@@ -0,0 +1,76 @@
+use std::thread;
+
+use silva::Arena;
+
+#[test]
+fn excludes_later_pushes() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    let a = arena.push(root, 1).index();
+    arena.push(a, 2);
+
+    let snapshot = arena.snapshot();
+    let children = arena[root].children_snapshot();
+    let descendants = arena[root].descendants_snapshot();
+
+    thread::scope(|s| {
+        let arena = &arena;
+        for t in 0..4 {
+            s.spawn(move || {
+                for i in 0..10 {
+                    let node = arena.push(root, 100 + t * 10 + i);
+                    arena.push(node, 0);
+                    arena.push(a, 0);
+                }
+            });
+        }
+    });
+    arena.push(None, 0);
+
+    assert_eq!(snapshot.watermark(), 3);
+    assert!(snapshot.children(&arena[root]).map(|n| n.value).eq([1]));
+    assert!(
+        snapshot
+            .descendants(&arena[root])
+            .map(|n| n.value)
+            .eq([0, 1, 2])
+    );
+    assert!(snapshot.iter().map(|n| n.value).eq([0, 1, 2]));
+    assert_eq!(snapshot.roots().count(), 1);
+    assert!(snapshot.get(a).is_some());
+    assert!(snapshot.get(arena[root].child().unwrap().index()).is_none());
+    assert!(!snapshot.contains(arena[root].child().unwrap()));
+
+    assert!(children.iter().map(|n| n.value).eq([1]));
+    assert!(descendants.iter().map(|n| n.value).eq([0, 1, 2]));
+
+    // the arena itself sees everything
+    assert_eq!(arena[root].children().count(), 41);
+    assert_eq!(arena[a].children().count(), 41);
+    assert_eq!(
+        arena[root].descendants_snapshot().len(),
+        1 + 40 * 2 + 41 + 1
+    );
+    assert_eq!(arena.roots().count(), 2);
+}
+
+#[test]
+fn descendants_order() {
+    let arena = Arena::new();
+    let root;
+    silva::tree![&arena, root = ("root") = [("a") = [("a1"), ("a2")], ("b")]];
+
+    assert!(
+        root.descendants_snapshot()
+            .into_iter()
+            .map(|n| n.value)
+            .eq(["root", "b", "a", "a2", "a1"])
+    );
+    assert!(
+        arena
+            .snapshot()
+            .descendants(root)
+            .map(|n| n.value)
+            .eq(["root", "b", "a", "a2", "a1"])
+    );
+}
@@ -0,0 +1,58 @@
+use silva::{Arena, ChildOrder};
+
+#[test]
+fn sorts() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let mut arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        arena.push_all(root, [5, 3, 9, 1, 7]);
+        let first = arena.push(root, 4);
+        arena.push(first, 10);
+        let root = root.index();
+
+        arena.sort_children_by(root, |a, b| a.value.cmp(&b.value));
+        let node = arena.get(root).unwrap();
+        assert!(node.child_values().eq(&[1, 3, 4, 5, 7, 9]), "{order:?}");
+        assert!(
+            node.children()
+                .rev()
+                .map(|n| n.value)
+                .eq([9, 7, 5, 4, 3, 1])
+        );
+        assert_eq!(node.children().len(), 6);
+        // the first child given counts as the newest, newest first
+        let (oldest, newest) = match order {
+            ChildOrder::NewestFirst => (9, 1),
+            ChildOrder::InsertionOrder => (1, 9),
+        };
+        assert_eq!(node.first_child().map(|n| n.value), Some(oldest));
+        assert_eq!(node.last_child().map(|n| n.value), Some(newest));
+        assert!(node.descendant_values().eq(&[0, 1, 3, 4, 10, 5, 7, 9]));
+
+        // new children go where they would after the sorted ones
+        let (_, ordinal) = arena.push_with_ordinal(node, 2);
+        assert_eq!(ordinal, 6);
+        let expected: &[_] = match order {
+            ChildOrder::NewestFirst => &[2, 1, 3, 4, 5, 7, 9],
+            ChildOrder::InsertionOrder => &[1, 3, 4, 5, 7, 9, 2],
+        };
+        assert!(node.child_values().eq(expected), "{order:?}");
+    }
+}
+
+#[test]
+fn stable_and_small() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, (0, 0));
+    arena.push_all(root, [(1, 0), (0, 1), (1, 2), (0, 3)]);
+    let leaf = arena.push(None, (0, 0)).index();
+    let root = root.index();
+
+    // equal children keep the order they were given in, newest first
+    arena.sort_children_by(root, |a, b| a.value.0.cmp(&b.value.0));
+    let node = arena.get(root).unwrap();
+    assert!(node.child_values().eq(&[(0, 3), (0, 1), (1, 2), (1, 0)]));
+
+    arena.sort_children_by(leaf, |_, _| unreachable!());
+    assert!(arena.get(leaf).unwrap().child().is_none());
+}
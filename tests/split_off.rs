@@ -0,0 +1,66 @@
+use std::rc::Rc;
+
+use silva::{Arena, ChildOrder};
+
+#[test]
+fn moves_values() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let mut arena = Arena::with_child_order(order);
+        let root = arena.push(None, String::from("root"));
+        let a = arena.push(root, String::from("a"));
+        arena.push(a, String::from("a1"));
+        arena.push(a, String::from("a2"));
+        arena.push(root, String::from("b"));
+        let before: Vec<_> = a
+            .descendants_with_depth()
+            .map(|(depth, n)| (depth, n.value.clone()))
+            .collect();
+        let a = a.index();
+
+        let split = arena.split_off_subtree(a);
+        assert_eq!(split.child_order(), order);
+        assert_eq!(split.len(), 3);
+        let new_root = split.get_dense(0).unwrap();
+        assert!(new_root.is_root());
+        let after: Vec<_> = new_root
+            .descendants_with_depth()
+            .map(|(depth, n)| (depth, n.value.clone()))
+            .collect();
+        assert_eq!(after, before, "{order:?}");
+
+        assert_eq!(arena.len(), 2);
+        assert!(arena.get(a).is_none());
+        let root = arena.get_dense(0).unwrap();
+        assert!(root.child_values().eq(["b"]));
+    }
+}
+
+#[test]
+fn no_clones() {
+    let shared = Rc::new(());
+    let mut arena = Arena::new();
+    let root = arena.push(None, Rc::clone(&shared));
+    arena.push(root, Rc::clone(&shared));
+    let root = root.index();
+
+    let split = arena.split_off_subtree(root);
+    assert!(arena.is_empty());
+    assert_eq!(Rc::strong_count(&shared), 3);
+    drop(split);
+    assert_eq!(Rc::strong_count(&shared), 1);
+}
+
+#[test]
+fn keeps_subtree_counts() {
+    let mut arena = Arena::with_subtree_counts();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    arena.push_all(a, 2..5);
+    let a = a.index();
+
+    let split = arena.split_off_subtree(a);
+    let root = arena.get_dense(0).unwrap();
+    assert_eq!(arena.subtree_len(root), 1);
+    let new_root = split.get_dense(0).unwrap();
+    assert_eq!(split.subtree_len(new_root), 4);
+}
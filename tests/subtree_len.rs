@@ -0,0 +1,111 @@
+use std::thread;
+
+use silva::{Arena, Node};
+
+/// Every node's kept count matches a walk of its subtree
+fn check(arena: &Arena<usize>) {
+    for node in arena.iter_range(..) {
+        assert_eq!(arena.subtree_len(node), node.subtree_len(), "{node}");
+    }
+}
+
+#[test]
+fn walked() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    arena.push_all(a, 2..5);
+    arena.push(root, 5);
+
+    assert_eq!(root.subtree_len(), 6);
+    assert_eq!(a.subtree_len(), 4);
+    assert_eq!(arena.subtree_len(a), 4);
+    assert_eq!(arena.push(None, 6).subtree_len(), 1);
+}
+
+#[test]
+fn counted() {
+    let arena = Arena::with_subtree_counts();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    arena.push_all(a, 2..5);
+    arena.push(root, 5);
+    assert_eq!(arena.subtree_len(root), 6);
+    assert_eq!(arena.subtree_len(a), 4);
+    check(&arena);
+}
+
+#[test]
+fn orphans() {
+    let arena = Arena::with_subtree_counts();
+    let root = arena.push(None, 0);
+    let outer = arena.push_orphan(1);
+    let inner = arena.push_orphan(2);
+    arena.push_all(inner, 3..6);
+    arena.push(outer, 6);
+
+    // unattached orphans aren't counted by anyone else
+    assert_eq!(arena.subtree_len(root), 1);
+    assert_eq!(arena.subtree_len(inner), 4);
+    check(&arena);
+
+    arena.attach(outer, inner).unwrap();
+    assert_eq!(arena.subtree_len(outer), 6);
+    assert_eq!(arena.subtree_len(root), 1);
+    arena.attach(root, outer).unwrap();
+    assert_eq!(arena.subtree_len(root), 7);
+
+    // failed attaches change nothing
+    assert!(arena.attach(inner, outer).is_err());
+    arena.push(inner, 7);
+    assert_eq!(arena.subtree_len(root), 8);
+    check(&arena);
+}
+
+#[test]
+fn kept_by_map() {
+    let arena = Arena::with_subtree_counts();
+    let root = arena.push(None, 0);
+    arena.push_all(root, 1..4);
+    let arena = arena.map(|v| v * 10);
+    let root = arena.get_dense(0).unwrap();
+    arena.push(root, 40);
+    assert_eq!(arena.subtree_len(root), 5);
+    check(&arena);
+}
+
+#[test]
+fn concurrent_attach() {
+    let (threads, per_thread) = if cfg!(miri) { (3, 16) } else { (6, 1_000) };
+    for _ in 0..if cfg!(miri) { 1 } else { 20 } {
+        let arena = Arena::with_subtree_counts();
+        let root = arena.push(None, 0);
+        let orphan = arena.push_orphan(0);
+        let mid = arena.push(orphan, 0);
+
+        // pushes race the attach, & must each be counted once
+        thread::scope(|s| {
+            for t in 0..threads {
+                let (arena, parent): (_, &Node<_>) =
+                    (&arena, if t % 2 == 0 { orphan } else { mid });
+                s.spawn(move || {
+                    for i in 0..per_thread {
+                        arena.push(parent, i);
+                    }
+                });
+            }
+            s.spawn(|| arena.attach(root, orphan).unwrap());
+        });
+
+        assert_eq!(arena.subtree_len(root), threads * per_thread + 3);
+        check(&arena);
+    }
+}
+
+#[test]
+#[should_panic = "node does not belong to this arena"]
+fn foreign_node() {
+    let arena = Arena::<usize>::with_subtree_counts();
+    let other = Arena::new();
+    arena.subtree_len(other.push(None, 0));
+}
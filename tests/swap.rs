@@ -0,0 +1,162 @@
+use silva::{Arena, ChildOrder, Index, Node, SwapError};
+
+fn values<T: Copy>(node: &Node<T>) -> Vec<(usize, T)> {
+    node.descendants_with_depth()
+        .map(|(depth, n)| (depth, n.value))
+        .collect()
+}
+
+#[test]
+fn siblings() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let mut arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        let children: Vec<_> = (1..5).map(|i| arena.push(root, i).index()).collect();
+        let before: Vec<_> = root.child_values().copied().collect();
+        let root = root.index();
+
+        // adjacent, then apart
+        arena.swap_subtrees(children[0], children[1]).unwrap();
+        arena.swap_subtrees(children[0], children[3]).unwrap();
+        let swap = |x, y| {
+            move |v| {
+                if v == x {
+                    y
+                } else if v == y {
+                    x
+                } else {
+                    v
+                }
+            }
+        };
+        let mut expected: Vec<_> = before.into_iter().map(swap(1, 2)).map(swap(1, 4)).collect();
+        assert!(arena[root].child_values().eq(&expected), "{order:?}");
+
+        // the tail still lines up with the new order
+        arena.push(root, 5);
+        match order {
+            ChildOrder::NewestFirst => expected.insert(0, 5),
+            ChildOrder::InsertionOrder => expected.push(5),
+        }
+        assert!(arena[root].child_values().eq(&expected), "{order:?}");
+        assert!(
+            arena[root]
+                .children()
+                .rev()
+                .map(|n| n.value)
+                .eq(expected.into_iter().rev())
+        );
+    }
+}
+
+#[test]
+fn across_parents() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    let a1 = arena.push(a, 11);
+    arena.push(a1, 111);
+    let b = arena.push(root, 2);
+    let b1 = arena.push(b, 21);
+    let (a, a1, b, b1) = (a.index(), a1.index(), b.index(), b1.index());
+
+    arena.swap_subtrees(a1, b1).unwrap();
+    assert_eq!(values(&arena[a]), [(0, 1), (1, 21)]);
+    assert_eq!(values(&arena[b]), [(0, 2), (1, 11), (2, 111)]);
+    assert_eq!(arena[a1].parent().unwrap().index(), b);
+    assert_eq!(arena[a1].first_child().unwrap().depth(), 3);
+}
+
+#[test]
+fn root_with_child() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    let child = arena.push(root, 1).index();
+    let other = arena.push(None, 2);
+    arena.push(other, 3);
+    let other = other.index();
+
+    arena.swap_subtrees(child, other).unwrap();
+    assert!(arena[child].is_root());
+    assert_eq!(arena[child].depth(), 0);
+    assert_eq!(values(&arena[root]), [(0, 0), (1, 2), (2, 3)]);
+    let roots: Vec<Index> = arena.roots().map(Node::index).collect();
+    assert_eq!(roots, [root, child]);
+}
+
+#[test]
+fn orphan_with_child() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    let child = arena.push(root, 1).index();
+    let orphan = arena.push_orphan(2);
+    let leaf = arena.push(orphan, 3).index();
+    let orphan = orphan.index();
+
+    arena.swap_subtrees(orphan, child).unwrap();
+    assert!(arena[child].is_orphan());
+    assert_eq!(arena[leaf].depth(), 2);
+    assert!(arena[root].child_values().eq(&[2]));
+    // the swapped out child can be attached again
+    arena.attach(&arena[orphan], &arena[child]).unwrap();
+    assert_eq!(values(&arena[root]), [(0, 0), (1, 2), (2, 1), (2, 3)]);
+}
+
+#[test]
+fn nested() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    let child = arena.push(root, 1).index();
+    let leaf = arena.push(child, 2).index();
+    let nested = SwapError::Nested {
+        ancestor: root,
+        descendant: leaf,
+    };
+    assert_eq!(arena.swap_subtrees(root, leaf), Err(nested.clone()));
+    assert_eq!(arena.swap_subtrees(leaf, root), Err(nested.clone()));
+    assert_eq!(
+        nested.to_string(),
+        "can't swap node 0 with its descendant 2"
+    );
+    assert_eq!(arena.swap_subtrees(child, child), Ok(()));
+    assert_eq!(values(&arena[root]), [(0, 0), (1, 1), (2, 2)]);
+}
+
+#[test]
+fn subtree_counts() {
+    let mut arena = Arena::with_subtree_counts();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    arena.push_all(a, 10..13);
+    let b = arena.push(root, 2);
+    let b1 = arena.push(b, 20).index();
+    let orphan = arena.push_orphan(3);
+    arena.push(orphan, 30);
+    let (root, a, b, orphan) = (root.index(), a.index(), b.index(), orphan.index());
+
+    arena.swap_subtrees(a, b1).unwrap();
+    assert_eq!(arena.subtree_len(&arena[root]), 7);
+    assert_eq!(arena.subtree_len(&arena[b]), 5);
+    arena.swap_subtrees(b, orphan).unwrap();
+    assert_eq!(arena.subtree_len(&arena[root]), 4);
+    assert_eq!(arena.subtree_len(&arena[b]), 5);
+
+    // the orphan swapped out counts toward its ancestors once attached
+    arena.attach(&arena[root], &arena[b]).unwrap();
+    assert_eq!(arena.subtree_len(&arena[root]), 9);
+}
+
+#[test]
+fn missing_node() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    let child = arena.push(root, 1).index();
+    arena.remove_subtree(child);
+    let err = arena.swap_subtrees(root, child).unwrap_err();
+    assert_eq!(err, SwapError::Missing(child));
+    assert_eq!(err.to_string(), "no node at index 1");
+    assert_eq!(
+        arena.swap_subtrees(child, root),
+        Err(SwapError::Missing(child))
+    );
+}
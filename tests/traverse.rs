@@ -0,0 +1,107 @@
+use silva::{Arena, ChildOrder, Edge, Node};
+
+/// Render a subtree as an s-expression
+fn sexpr<T: std::fmt::Display>(root: &Node<T>) -> String {
+    let mut out = String::new();
+    for edge in root.traverse() {
+        match edge {
+            Edge::Open(node) => {
+                if node.child().is_some() {
+                    out.push('(');
+                }
+                out.push_str(&node.value.to_string());
+                out.push(' ');
+            }
+            Edge::Close(node) => {
+                out.pop();
+                if node.child().is_some() {
+                    out.push(')');
+                }
+                out.push(' ');
+            }
+        }
+    }
+    out.pop();
+    out
+}
+
+#[test]
+fn nested() {
+    let arena = Arena::new();
+    let root;
+    silva::tree![
+        &arena,
+        root = ("root") = [("a") = [("a1"), ("a2")], ("b"), ("c") = [("c1")]]
+    ];
+
+    assert_eq!(sexpr(root), "(root (c c1) b (a a2 a1))");
+    let opened: Vec<_> = root
+        .traverse()
+        .filter_map(|edge| match edge {
+            Edge::Open(node) => Some(node.index()),
+            Edge::Close(_) => None,
+        })
+        .collect();
+    assert_eq!(
+        opened,
+        root.descendants().map(Node::index).collect::<Vec<_>>()
+    );
+    assert_eq!(root.traverse().count(), 2 * root.descendants().count());
+}
+
+#[test]
+fn leaf_with_siblings() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    arena.push(root, 2);
+    arena.push(a, 3);
+
+    // the walk never leaves the subtree it starts at
+    let edges: Vec<_> = a
+        .traverse()
+        .map(|edge| match edge {
+            Edge::Open(node) => (true, node.value),
+            Edge::Close(node) => (false, node.value),
+        })
+        .collect();
+    assert_eq!(edges, [(true, 1), (true, 3), (false, 3), (false, 1)]);
+
+    let leaf = arena.push(root, 4);
+    assert_eq!(leaf.traverse().count(), 2);
+    let mut walk = leaf.traverse();
+    walk.by_ref().for_each(drop);
+    assert!(walk.next().is_none());
+}
+
+#[test]
+fn insertion_order() {
+    let arena = Arena::with_child_order(ChildOrder::InsertionOrder);
+    let root = arena.push(None, 0);
+    let a = arena.push(root, 1);
+    arena.push(root, 2);
+    arena.push_all(a, 3..5);
+    assert_eq!(sexpr(root), "(0 (1 3 4) 2)");
+}
+
+#[test]
+fn deep_chain() {
+    let depth = if cfg!(miri) { 1_000 } else { 100_000 };
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let mut leaf = root;
+    for i in 1..depth {
+        leaf = arena.push(leaf, i);
+    }
+
+    let mut open = 0;
+    let mut max = 0;
+    for edge in root.traverse() {
+        match edge {
+            Edge::Open(_) => open += 1,
+            Edge::Close(_) => open -= 1,
+        }
+        max = max.max(open);
+    }
+    assert_eq!((open, max), (0, depth));
+}
@@ -0,0 +1,42 @@
+use std::collections::HashSet;
+
+use silva::{Index, TypedArena};
+
+silva::new_index_type!(
+    /// Indexes the syntax tree
+    pub struct AstIndex;
+);
+silva::new_index_type!(
+    struct TypeIndex;
+);
+
+#[test]
+fn round_trip() {
+    let ast = TypedArena::<_, AstIndex>::new();
+    let types = TypedArena::<_, TypeIndex>::new();
+
+    let item = ast.push(None, "fn");
+    let name = ast.push(item, "main");
+    let ret = ast.push(Some(item), "-> i32");
+    let i32 = types.push(None, "i32");
+
+    assert_eq!(ast[name].value, "main");
+    assert_eq!(ast.get(ret).unwrap().parent().unwrap().value, "fn");
+    assert_eq!(types[i32].value, "i32");
+    assert_eq!(ast.index_of(&ast[name]), Some(name));
+    assert_eq!(types.index_of(&ast[name]), None);
+
+    let raw: Index = name.into();
+    assert_eq!(AstIndex::from(raw), name);
+    assert_eq!(ast.arena()[raw].value, "main");
+    assert_eq!(format!("{name:?} {name}"), "AstIndex(1) 1");
+    assert_eq!(format!("{i32:?}"), "TypeIndex(0)");
+
+    let set: HashSet<_> = ast
+        .arena()
+        .iter_range(..)
+        .map(|n| AstIndex::from(n.index()))
+        .collect();
+    assert!(set.contains(&item) && set.contains(&ret));
+    assert_eq!(ast.into_inner().count(), 3);
+}
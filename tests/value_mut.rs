@@ -0,0 +1,106 @@
+use silva::{Arena, ChildOrder};
+
+#[test]
+fn value_mut() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    let child = arena.push(root, 1).index();
+    let orphan = arena.push_orphan(2).index();
+
+    *arena.value_mut(child).unwrap() += 10;
+    *arena.value_mut(orphan).unwrap() += 10;
+    assert_eq!(arena.get(child).unwrap().value, 11);
+    // the change is seen through the links too
+    let root = arena.get(root).unwrap();
+    assert_eq!(root.child().unwrap().value, 11);
+    assert_eq!(root.child().unwrap().parent().unwrap().value, 0);
+
+    // an index past what this arena has handed out
+    let past = arena.push(None, 3).index();
+    let mut small = Arena::new();
+    small.push(None, 0);
+    assert!(small.value_mut(past).is_none());
+}
+
+#[test]
+fn iter_mut() {
+    for order in [ChildOrder::NewestFirst, ChildOrder::InsertionOrder] {
+        let mut arena = Arena::with_child_order(order);
+        let root = arena.push(None, 0);
+        arena.push_all(root, 1..100);
+
+        for (index, value) in arena.iter_mut() {
+            assert_eq!(index.as_usize(), *value);
+            *value *= 2;
+        }
+        assert!(arena.values().copied().eq((0..100).map(|v| v * 2)));
+        let root = arena.get_dense(0).unwrap();
+        assert!(
+            root.descendant_values()
+                .skip(1)
+                .all(|v| v % 2 == 0 && *v > 0)
+        );
+        assert_eq!(
+            root.child_values().sum::<usize>(),
+            (1..100).sum::<usize>() * 2
+        );
+    }
+}
+
+#[test]
+fn iter_mut_empty() {
+    let mut arena = Arena::<String>::new();
+    assert!(arena.iter_mut().next().is_none());
+}
+
+#[test]
+fn after_attach() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0);
+    let orphan = arena.push_orphan(1);
+    arena.push(orphan, 2);
+    arena.attach(root, orphan).unwrap();
+
+    for value in (&mut arena).into_iter().map(|(_, value)| value) {
+        *value += 1;
+    }
+    let root = arena.get_dense(0).unwrap();
+    assert!(root.descendant_values().eq(&[1, 2, 3]));
+    let leaf = root.child().unwrap().child().unwrap();
+    assert!(leaf.ancestors().map(|n| n.value).eq([2, 1]));
+}
+
+#[test]
+fn values_mut() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, String::from("root"));
+    arena.push_all(root, ["a", "b"].map(String::from));
+    for value in arena.values_mut() {
+        value.push('!');
+    }
+    assert!(arena.values().eq(&["root!", "a!", "b!"]));
+    assert_eq!(arena.get_dense(0).unwrap().child().unwrap().value, "b!");
+}
+
+#[test]
+fn replace_and_take() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, String::from("root"));
+    let child = arena.push(root, String::from("child")).index();
+    let root = root.index();
+
+    assert_eq!(arena.replace(child, String::from("new")), "child");
+    assert_eq!(arena.take(root), "root");
+    assert!(arena.values().eq(["", "new"]));
+    let root = arena.get(root).unwrap();
+    assert_eq!(root.child().unwrap().value, "new");
+}
+
+#[test]
+#[should_panic = "no node at index"]
+fn replace_removed() {
+    let mut arena = Arena::new();
+    let root = arena.push(None, 0).index();
+    arena.remove_subtree(root);
+    arena.replace(root, 1);
+}
@@ -0,0 +1,55 @@
+use silva::Arena;
+
+#[test]
+fn arena_values() {
+    let arena = Arena::new();
+    let root = arena.push(None, 1);
+    for i in 2..=10 {
+        arena.push(root, i);
+    }
+    arena.push(None, 11);
+
+    let values = arena.values();
+    assert_eq!(values.size_hint(), (0, Some(arena.count())));
+    assert_eq!(
+        values.sum::<i32>(),
+        arena.iter_range(..).map(|n| n.value).sum::<i32>()
+    );
+    assert!(arena.values().copied().eq(1..=11));
+}
+
+#[test]
+fn arena_entries() {
+    let arena = Arena::new();
+    let root = arena.push(None, "root");
+    arena.push(root, "one");
+    arena.push(root, "two");
+
+    assert_eq!(arena.entries().size_hint(), (0, Some(3)));
+    for (index, value) in arena.entries() {
+        let node = &arena[index];
+        assert!(std::ptr::eq(&node.value, value));
+    }
+    assert_eq!(arena.entries().count(), arena.count());
+}
+
+#[test]
+fn node_values() {
+    let arena = Arena::new();
+    let root = arena.push(None, 0);
+    let one = arena.push(root, 1);
+    arena.push(one, 2);
+    arena.push(root, 3);
+
+    assert!(root.child_values().copied().eq([3, 1]));
+    assert!(one.child_values().copied().eq([2]));
+    assert!(
+        root.descendant_values()
+            .eq(root.descendants_snapshot().into_iter().map(|n| &n.value))
+    );
+    assert_eq!(root.descendant_values().sum::<i32>(), 6);
+
+    let mut leaf = one.child().unwrap().child_values();
+    assert_eq!(leaf.next(), None);
+    assert_eq!(leaf.next(), None);
+}
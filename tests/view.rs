@@ -0,0 +1,74 @@
+use silva::{Arena, Index, SecondaryMap, View};
+
+/// root -> [a -> [a1, a2], b -> [b1]], with names for every node
+fn tree(arena: &Arena<&'static str>) -> Index {
+    let root;
+    silva::tree![
+        arena,
+        root = ("root") = [("a") = [("a1"), ("a2")], ("b") = [("b1")],]
+    ];
+    root.index()
+}
+
+#[test]
+fn secondary_map() {
+    let arena = Arena::new();
+    let root = tree(&arena);
+    let mut lengths: SecondaryMap<usize> = arena
+        .iter_range(..)
+        .map(|n| (n.index(), n.value.len()))
+        .collect();
+
+    assert_eq!(lengths.len(), 6);
+    assert_eq!(lengths[root], 4);
+    lengths[root] += 1;
+    assert_eq!(lengths.insert(root, 0), Some(5));
+    assert_eq!(lengths.remove(root), Some(0));
+    assert_eq!(lengths.remove(root), None);
+    assert!(!lengths.contains_key(root));
+
+    let entries = lengths.iter();
+    assert_eq!(entries.len(), 5);
+    assert!(entries.map(|(i, _)| i.as_usize()).eq(1..6));
+}
+
+#[test]
+fn half_column() {
+    let arena = Arena::new();
+    let root = tree(&arena);
+    let mut column = SecondaryMap::new();
+    for node in arena.iter_range(..).step_by(2) {
+        column.insert(node.index(), node.value.to_uppercase());
+    }
+
+    let view = View::new(&arena, &column);
+    assert_eq!(view.get(root).unwrap().1, "ROOT");
+    let a = arena[root].children().last().unwrap().index();
+    assert!(view.get(a).is_none());
+    assert!(
+        view.iter()
+            .map(|(node, upper)| (node.value, upper.as_str()))
+            .eq([("root", "ROOT"), ("a1", "A1"), ("b", "B")])
+    );
+
+    let zipped: Vec<_> = view
+        .descendants(root)
+        .map(|(node, upper)| (node.value, upper.map(String::as_str)))
+        .collect();
+    assert_eq!(
+        zipped,
+        [
+            ("root", Some("ROOT")),
+            ("b", Some("B")),
+            ("b1", None),
+            ("a", None),
+            ("a2", None),
+            ("a1", Some("A1")),
+        ]
+    );
+    assert!(
+        view.descendants(a)
+            .map(|(node, _)| node.value)
+            .eq(["a", "a2", "a1"])
+    );
+}
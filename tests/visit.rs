@@ -0,0 +1,120 @@
+use silva::visit::{Collector, Flow, Visitor, walk};
+use silva::{Arena, Node};
+
+fn tree<'a>(arena: &'a Arena<&'static str>) -> &'a Node<&'static str> {
+    let root;
+    silva::tree![
+        arena,
+        root = ("root") = [("a") = [("a1"), ("a2") = [("a2x")]], ("b") = [("b1")],]
+    ];
+    root
+}
+
+/// Records every event, acting on nodes as configured
+#[derive(Default)]
+struct Events {
+    events: Vec<String>,
+    skip: &'static str,
+    stop: &'static str,
+}
+
+impl<'a> Visitor<'a, &'static str> for Events {
+    fn enter(&mut self, node: &'a Node<&'static str>) -> Flow {
+        self.events.push(format!("+{}", node.value));
+        match node.value {
+            v if v == self.skip => Flow::SkipSubtree,
+            v if v == self.stop => Flow::Break,
+            _ => Flow::Continue,
+        }
+    }
+
+    fn leave(&mut self, node: &'a Node<&'static str>) {
+        self.events.push(format!("-{}", node.value));
+    }
+}
+
+#[test]
+fn enter_leave_pairs() {
+    let arena = Arena::new();
+    let root = tree(&arena);
+    let mut events = Events::default();
+
+    assert!(walk(root, &mut events));
+    assert_eq!(
+        events.events.join(" "),
+        "+root +b +b1 -b1 -b +a +a2 +a2x -a2x -a2 +a1 -a1 -a -root"
+    );
+}
+
+#[test]
+fn skip_subtree() {
+    let arena = Arena::new();
+    let root = tree(&arena);
+    let mut events = Events {
+        skip: "a2",
+        ..Events::default()
+    };
+
+    assert!(walk(root, &mut events));
+    assert_eq!(
+        events.events.join(" "),
+        "+root +b +b1 -b1 -b +a +a2 -a2 +a1 -a1 -a -root"
+    );
+
+    let mut events = Events {
+        skip: "root",
+        ..Events::default()
+    };
+    assert!(walk(root, &mut events));
+    assert_eq!(events.events, ["+root", "-root"]);
+}
+
+#[test]
+fn break_walk() {
+    let arena = Arena::new();
+    let root = tree(&arena);
+    let mut events = Events {
+        stop: "a2",
+        ..Events::default()
+    };
+
+    assert!(!walk(root, &mut events));
+    assert_eq!(events.events.join(" "), "+root +b +b1 -b1 -b +a +a2");
+}
+
+#[test]
+fn collector() {
+    let arena = Arena::new();
+    let root = tree(&arena);
+    let mut leaves = Collector::new(|node: &Node<_>| node.child().is_none());
+
+    assert!(walk(root, &mut leaves));
+    assert!(
+        leaves
+            .into_nodes()
+            .into_iter()
+            .map(|n| n.value)
+            .eq(["b1", "a2x", "a1"])
+    );
+}
+
+#[test]
+fn arena_walk() {
+    let arena = Arena::new();
+    let root = tree(&arena);
+    let mut events = Events {
+        skip: "a",
+        ..Events::default()
+    };
+
+    assert!(arena.walk(root, &mut events));
+    assert_eq!(events.events.join(" "), "+root +b +b1 -b1 -b +a -a -root");
+}
+
+#[test]
+#[should_panic = "root does not belong to this arena"]
+fn arena_walk_foreign() {
+    let (arena, other) = (Arena::new(), Arena::new());
+    let root = tree(&other);
+    arena.walk(root, &mut Events::default());
+}
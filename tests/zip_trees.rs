@@ -0,0 +1,65 @@
+use silva::algo::{self, Mismatch};
+use silva::{Arena, ChildOrder};
+
+#[test]
+fn same_shape_across_orders() {
+    let newest = Arena::new();
+    let a = newest.push(None, 0);
+    let a1 = newest.push(a, 1);
+    newest.push(a1, 2);
+    newest.push(a, 3);
+
+    let insertion = Arena::with_child_order(ChildOrder::InsertionOrder);
+    let b = insertion.push(None, 10);
+    let b1 = insertion.push(b, 11);
+    insertion.push(b1, 12);
+    insertion.push(b, 13);
+
+    let pairs: Vec<_> = algo::zip_trees(a, b)
+        .map(|pair| pair.ok().map(|(a, b)| (a.value, b.value)))
+        .collect();
+    assert_eq!(pairs, [(0, 10), (1, 11), (2, 12), (3, 13)].map(Some));
+}
+
+#[test]
+fn stops_at_mismatch() {
+    let arena = Arena::new();
+    let a = arena.push(None, 0);
+    let a1 = arena.push(a, 1);
+    arena.push(a1, 2);
+    arena.push(a, 3);
+
+    let b = arena.push(None, 0);
+    let b1 = arena.push(b, 1);
+    arena.push(b, 3);
+
+    let mut zipped = arena.zip_trees(a, b);
+    assert!(matches!(zipped.next(), Some(Ok((x, y))) if x.value == 0 && y.value == 0));
+    assert!(matches!(zipped.next(), Some(Ok((x, y))) if x.value == 1 && y.value == 1));
+    let Some(Err(Mismatch { a: x, b: y })) = zipped.next() else {
+        panic!("expected a mismatch");
+    };
+    assert!(std::ptr::eq(x, a1) && std::ptr::eq(y, b1));
+    assert!(zipped.next().is_none());
+    assert!(zipped.next().is_none());
+}
+
+#[test]
+fn leaves() {
+    let arena = Arena::new();
+    let a = arena.push(None, 0);
+    let b = arena.push(None, 1);
+    assert_eq!(arena.zip_trees(a, b).count(), 1);
+    arena.push(b, 2);
+    assert!(matches!(arena.zip_trees(a, b).nth(1), Some(Err(_))));
+}
+
+#[test]
+#[should_panic = "root does not belong to this arena"]
+fn foreign_root() {
+    let arena = Arena::new();
+    let other = Arena::new();
+    let a = arena.push(None, 0);
+    let b = other.push(None, 0);
+    arena.zip_trees(a, b);
+}